@@ -0,0 +1,9 @@
+DROP TABLE IF EXISTS rapport_indicators;
+DROP TABLE IF EXISTS run_metrics;
+DROP TABLE IF EXISTS speaker_id_mappings;
+DROP TABLE IF EXISTS session_embeddings;
+DROP TABLE IF EXISTS transcript_fingerprints;
+DROP TABLE IF EXISTS marker_events;
+DROP TABLE IF EXISTS transcript_search;
+DROP TABLE IF EXISTS transcript_segments;
+DROP TABLE IF EXISTS conversation_sessions;
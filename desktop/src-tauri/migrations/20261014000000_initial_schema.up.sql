@@ -0,0 +1,91 @@
+-- Schema as of the switch to versioned migrations. IF NOT EXISTS lets databases
+-- created before migrations existed adopt this version unchanged.
+
+CREATE TABLE IF NOT EXISTS conversation_sessions (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    session_type TEXT NOT NULL,
+    client_reference TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'created',
+    duration REAL,
+    file_path TEXT
+);
+
+CREATE TABLE IF NOT EXISTS transcript_segments (
+    id TEXT PRIMARY KEY,
+    session_id TEXT NOT NULL REFERENCES conversation_sessions(id),
+    speaker_id TEXT NOT NULL,
+    speaker_label TEXT NOT NULL,
+    start_time REAL NOT NULL,
+    end_time REAL NOT NULL,
+    text TEXT NOT NULL,
+    confidence REAL NOT NULL,
+    words TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_transcript_segments_session ON transcript_segments(session_id, start_time);
+
+CREATE VIRTUAL TABLE IF NOT EXISTS transcript_search USING fts5(
+    text,
+    content = 'transcript_segments',
+    content_rowid = 'rowid'
+);
+
+CREATE TABLE IF NOT EXISTS marker_events (
+    id TEXT NOT NULL,
+    session_id TEXT NOT NULL,
+    marker_type TEXT NOT NULL,
+    start_time REAL NOT NULL,
+    end_time REAL NOT NULL,
+    confidence REAL NOT NULL,
+    evidence TEXT NOT NULL,
+    explanation TEXT NOT NULL,
+    speaker TEXT,
+    PRIMARY KEY (session_id, id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_marker_events_session ON marker_events(session_id, start_time);
+
+CREATE INDEX IF NOT EXISTS idx_marker_events_type ON marker_events(marker_type, confidence);
+
+CREATE TABLE IF NOT EXISTS transcript_fingerprints (
+    session_id TEXT PRIMARY KEY,
+    fingerprint TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS session_embeddings (
+    session_id TEXT PRIMARY KEY,
+    dimensions INTEGER NOT NULL,
+    vector TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS speaker_id_mappings (
+    session_id TEXT NOT NULL,
+    speaker_id TEXT NOT NULL,
+    original_speaker_id TEXT NOT NULL,
+    PRIMARY KEY (session_id, speaker_id)
+);
+
+CREATE TABLE IF NOT EXISTS run_metrics (
+    id TEXT PRIMARY KEY,
+    session_id TEXT,
+    script TEXT NOT NULL,
+    success INTEGER NOT NULL,
+    exit_code INTEGER,
+    duration_ms INTEGER NOT NULL,
+    command_line TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS rapport_indicators (
+    session_id TEXT NOT NULL,
+    timestamp REAL NOT NULL,
+    value REAL NOT NULL,
+    trend TEXT NOT NULL,
+    contributing_markers TEXT NOT NULL DEFAULT '[]',
+    PRIMARY KEY (session_id, timestamp)
+);
@@ -0,0 +1,3 @@
+DROP TRIGGER IF EXISTS transcript_segments_search_update;
+DROP TRIGGER IF EXISTS transcript_segments_search_delete;
+DROP TRIGGER IF EXISTS transcript_segments_search_insert;
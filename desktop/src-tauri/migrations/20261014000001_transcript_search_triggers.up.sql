@@ -0,0 +1,17 @@
+-- Keep the external-content transcript index in step with transcript_segments
+
+CREATE TRIGGER IF NOT EXISTS transcript_segments_search_insert AFTER INSERT ON transcript_segments BEGIN
+    INSERT INTO transcript_search(rowid, text) VALUES (new.rowid, new.text);
+END;
+
+CREATE TRIGGER IF NOT EXISTS transcript_segments_search_delete AFTER DELETE ON transcript_segments BEGIN
+    INSERT INTO transcript_search(transcript_search, rowid, text) VALUES ('delete', old.rowid, old.text);
+END;
+
+CREATE TRIGGER IF NOT EXISTS transcript_segments_search_update AFTER UPDATE OF text ON transcript_segments BEGIN
+    INSERT INTO transcript_search(transcript_search, rowid, text) VALUES ('delete', old.rowid, old.text);
+    INSERT INTO transcript_search(rowid, text) VALUES (new.rowid, new.text);
+END;
+
+-- Segments written before the triggers existed were never indexed
+INSERT INTO transcript_search(transcript_search) VALUES ('rebuild');
@@ -0,0 +1 @@
+ALTER TABLE conversation_sessions DROP COLUMN audio_sha256;
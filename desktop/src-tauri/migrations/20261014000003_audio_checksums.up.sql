@@ -0,0 +1 @@
+ALTER TABLE conversation_sessions ADD COLUMN audio_sha256 TEXT;
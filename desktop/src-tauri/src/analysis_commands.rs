@@ -1,10 +1,112 @@
-use tauri::State;
+use tauri::{Emitter, State};
 use serde::{Deserialize, Serialize};
+use sqlx::Row;
+
+use crate::error::AppError;
+use crate::state::AppState;
+
+/// LD-3.4 marker levels, serialized and stored by their codes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MarkerType {
+    #[serde(rename = "ATO")]
+    Ato,
+    #[serde(rename = "SEM")]
+    Sem,
+    #[serde(rename = "CLU")]
+    Clu,
+    #[serde(rename = "MEMA")]
+    Mema,
+}
+
+impl MarkerType {
+    /// Every level in pipeline order, from single cues to aggregated patterns
+    pub const ALL: [MarkerType; 4] = [MarkerType::Ato, MarkerType::Sem, MarkerType::Clu, MarkerType::Mema];
+
+    /// Code used on the wire and in the database
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MarkerType::Ato => "ATO",
+            MarkerType::Sem => "SEM",
+            MarkerType::Clu => "CLU",
+            MarkerType::Mema => "MEMA",
+        }
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            MarkerType::Ato => "Atomic",
+            MarkerType::Sem => "Semantic",
+            MarkerType::Clu => "Cluster",
+            MarkerType::Mema => "Meta",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            MarkerType::Ato => "Single linguistic cue such as a word, phrase or pause",
+            MarkerType::Sem => "Meaningful combination of atomic cues within an utterance",
+            MarkerType::Clu => "Recurring semantic pattern across several turns",
+            MarkerType::Mema => "Conversation-level dynamic emerging from clusters",
+        }
+    }
+
+    /// Legend color as a hex RGB string
+    pub fn default_color(self) -> &'static str {
+        match self {
+            MarkerType::Ato => "#4C9AFF",
+            MarkerType::Sem => "#36B37E",
+            MarkerType::Clu => "#FFAB00",
+            MarkerType::Mema => "#DE350B",
+        }
+    }
+}
+
+impl std::fmt::Display for MarkerType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for MarkerType {
+    type Err = String;
+
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        MarkerType::ALL
+            .into_iter()
+            .find(|marker_type| marker_type.as_str() == code)
+            .ok_or_else(|| {
+                let codes: Vec<&str> = MarkerType::ALL.iter().map(|t| t.as_str()).collect();
+                format!("Unknown marker type: {} (expected one of: {})", code, codes.join(", "))
+            })
+    }
+}
+
+/// Registry entry describing a marker type for the UI legend
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MarkerTypeInfo {
+    pub code: MarkerType,
+    pub display_name: String,
+    pub description: String,
+    pub color: String,
+}
+
+#[tauri::command]
+pub async fn list_marker_types() -> Result<Vec<MarkerTypeInfo>, AppError> {
+    Ok(MarkerType::ALL
+        .into_iter()
+        .map(|marker_type| MarkerTypeInfo {
+            code: marker_type,
+            display_name: marker_type.display_name().to_string(),
+            description: marker_type.description().to_string(),
+            color: marker_type.default_color().to_string(),
+        })
+        .collect())
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MarkerEvent {
     pub id: String,
-    pub marker_type: String, // ATO, SEM, CLU, MEMA
+    pub marker_type: MarkerType,
     pub start_time: f64,
     pub end_time: f64,
     pub confidence: f64,
@@ -13,70 +115,1771 @@ pub struct MarkerEvent {
     pub speaker: Option<String>,
 }
 
+/// Column list matching `marker_from_row`
+pub const MARKER_COLUMNS: &str =
+    "id, marker_type, start_time, end_time, confidence, evidence, explanation, speaker";
+
+pub fn marker_from_row(row: &sqlx::sqlite::SqliteRow) -> Result<MarkerEvent, sqlx::Error> {
+    Ok(MarkerEvent {
+        id: row.try_get("id")?,
+        marker_type: row
+            .try_get::<String, _>("marker_type")?
+            .parse()
+            .map_err(|e: String| sqlx::Error::ColumnDecode {
+                index: "marker_type".to_string(),
+                source: e.into(),
+            })?,
+        start_time: row.try_get("start_time")?,
+        end_time: row.try_get("end_time")?,
+        confidence: row.try_get("confidence")?,
+        evidence: row.try_get("evidence")?,
+        explanation: row.try_get("explanation")?,
+        speaker: row.try_get("speaker")?,
+    })
+}
+
+/// Load all persisted markers of a session ordered by start time
+pub async fn load_markers(
+    pool: &sqlx::SqlitePool,
+    session_id: &str
+) -> Result<Vec<MarkerEvent>, sqlx::Error> {
+    let sql = format!(
+        "SELECT {} FROM marker_events WHERE session_id = ? ORDER BY start_time",
+        MARKER_COLUMNS
+    );
+    let rows = sqlx::query(&sql).bind(session_id).fetch_all(pool).await?;
+    rows.iter().map(marker_from_row).collect()
+}
+
+/// Results returned by a global marker query when no limit is given
+const DEFAULT_MARKER_QUERY_LIMIT: u32 = 100;
+
+/// Upper bound on one page of a global marker query
+const MAX_MARKER_QUERY_LIMIT: u32 = 1000;
+
+/// Corpus-wide marker filters for `query_markers_global`
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MarkerFilters {
+    /// Marker types to include; empty means all types
+    pub marker_types: Vec<String>,
+    pub min_confidence: Option<f64>,
+    pub max_confidence: Option<f64>,
+    /// Free text matched against evidence and explanation
+    pub text: Option<String>,
+    pub speaker: Option<String>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RapportIndicator {
     pub timestamp: f64,
     pub value: f64, // -1.0 to 1.0
     pub trend: String, // "increasing", "decreasing", "stable"
     pub contributing_markers: Vec<String>,
+    /// Confidence band around `value`, present when bands were requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lower: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upper: Option<f64>,
+}
+
+/// Confidence band around a rapport value from its contributing marker confidences.
+/// The half-width grows with low mean confidence and with confidence spread, and
+/// shrinks with the square root of the number of markers; no markers means the
+/// full -1.0..=1.0 range.
+pub fn confidence_band(value: f64, confidences: &[f64]) -> (f64, f64) {
+    if confidences.is_empty() {
+        return (-1.0, 1.0);
+    }
+
+    let n = confidences.len() as f64;
+    let mean = confidences.iter().map(|c| c.clamp(0.0, 1.0)).sum::<f64>() / n;
+    let variance = confidences
+        .iter()
+        .map(|c| (c.clamp(0.0, 1.0) - mean).powi(2))
+        .sum::<f64>()
+        / n;
+    let half_width = ((1.0 - mean) + variance.sqrt()) / n.sqrt();
+
+    ((value - half_width).max(-1.0), (value + half_width).min(1.0))
+}
+
+/// Fill `lower`/`upper` on each indicator from the confidences of its contributing markers
+pub fn apply_confidence_bands(indicators: &mut [RapportIndicator], markers: &[MarkerEvent]) {
+    let confidence_by_id: std::collections::HashMap<&str, f64> = markers
+        .iter()
+        .map(|marker| (marker.id.as_str(), marker.confidence))
+        .collect();
+
+    for indicator in indicators {
+        let confidences: Vec<f64> = indicator
+            .contributing_markers
+            .iter()
+            .filter_map(|id| confidence_by_id.get(id.as_str()).copied())
+            .collect();
+        let (lower, upper) = confidence_band(indicator.value, &confidences);
+        indicator.lower = Some(lower);
+        indicator.upper = Some(upper);
+    }
+}
+
+/// Moving average applied to the rapport values before they are returned
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmoothingConfig {
+    /// Number of windows averaged; also sets the default EMA `alpha`
+    pub window: usize,
+    pub method: String, // "sma", "ema"
+    /// EMA weight of the newest value, defaults to `2 / (window + 1)`
+    pub alpha: Option<f64>,
+}
+
+impl SmoothingConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.window < 1 {
+            return Err("Smoothing window must be at least 1".to_string());
+        }
+        match self.method.as_str() {
+            "sma" => Ok(()),
+            "ema" => match self.alpha {
+                Some(alpha) if !(alpha > 0.0 && alpha <= 1.0) => {
+                    Err("EMA alpha must be in the range (0, 1]".to_string())
+                }
+                _ => Ok(()),
+            },
+            other => Err(format!("Unsupported smoothing method: {} (expected sma or ema)", other)),
+        }
+    }
+
+    /// Smooth `values` in place. SMA averages the trailing `window` values, fewer
+    /// at the start; EMA starts from the first value.
+    pub fn apply(&self, values: &mut [f64]) {
+        let raw = values.to_vec();
+        match self.method.as_str() {
+            "ema" => {
+                let alpha = self.alpha.unwrap_or(2.0 / (self.window as f64 + 1.0));
+                for index in 1..values.len() {
+                    values[index] = alpha * raw[index] + (1.0 - alpha) * values[index - 1];
+                }
+            }
+            _ => {
+                for (index, value) in values.iter_mut().enumerate() {
+                    let trailing = &raw[(index + 1).saturating_sub(self.window)..=index];
+                    *value = trailing.iter().sum::<f64>() / trailing.len() as f64;
+                }
+            }
+        }
+    }
+}
+
+/// Width of the time windows rapport is computed over
+pub const RAPPORT_WINDOW_SECS: f64 = 30.0;
+
+/// Change in value between consecutive windows below which the trend is "stable"
+const TREND_TOLERANCE: f64 = 0.05;
+
+/// Contribution of each marker type to rapport, per unit of marker confidence.
+/// Negative weights let a marker type count against rapport.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RapportWeights {
+    pub ato: f64,
+    pub sem: f64,
+    pub clu: f64,
+    pub mema: f64,
+}
+
+impl Default for RapportWeights {
+    /// Higher LD-3.4 levels aggregate more evidence and weigh more
+    fn default() -> Self {
+        Self {
+            ato: 0.1,
+            sem: 0.2,
+            clu: 0.3,
+            mema: 0.4,
+        }
+    }
+}
+
+impl RapportWeights {
+    pub fn validate(&self) -> Result<(), String> {
+        if [self.ato, self.sem, self.clu, self.mema].iter().any(|weight| !weight.is_finite()) {
+            return Err("Rapport weights must be finite numbers".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn weight(&self, marker_type: MarkerType) -> f64 {
+        match marker_type {
+            MarkerType::Ato => self.ato,
+            MarkerType::Sem => self.sem,
+            MarkerType::Clu => self.clu,
+            MarkerType::Mema => self.mema,
+        }
+    }
+}
+
+/// Rapport per `window_secs` window from the markers starting in it.
+/// A window's value is `tanh` of its summed weight × confidence, so it stays in
+/// -1.0..=1.0. Only windows holding markers are reported, so a stray late
+/// marker doesn't allocate every window before it; each is timestamped at its
+/// centre, and its trend compares it to the reported window before.
+pub fn rapport_indicators(
+    markers: &[MarkerEvent],
+    weights: &RapportWeights,
+    window_secs: f64
+) -> Vec<RapportIndicator> {
+    let mut windows: std::collections::BTreeMap<u64, (f64, Vec<&MarkerEvent>)> = std::collections::BTreeMap::new();
+    let mut ordered: Vec<&MarkerEvent> = markers.iter().collect();
+    ordered.sort_by(|a, b| a.start_time.total_cmp(&b.start_time).then_with(|| a.id.cmp(&b.id)));
+
+    for marker in ordered {
+        let window = (marker.start_time.max(0.0) / window_secs).floor() as u64;
+        let entry = windows.entry(window).or_insert_with(|| (0.0, Vec::new()));
+        let weight = weights.weight(marker.marker_type);
+        entry.0 += weight * marker.confidence.clamp(0.0, 1.0);
+        entry.1.push(marker);
+    }
+
+    let mut previous: Option<f64> = None;
+    windows
+        .into_iter()
+        .map(|(window, (score, contributing))| {
+            let value = score.tanh();
+            let trend = match previous {
+                Some(before) if value - before > TREND_TOLERANCE => "increasing",
+                Some(before) if before - value > TREND_TOLERANCE => "decreasing",
+                _ => "stable",
+            };
+            previous = Some(value);
+
+            RapportIndicator {
+                timestamp: (window as f64 + 0.5) * window_secs,
+                value,
+                trend: trend.to_string(),
+                contributing_markers: contributing.iter().map(|marker| marker.id.clone()).collect(),
+                lower: None,
+                upper: None,
+            }
+        })
+        .collect()
+}
+
+/// Width of the common timebase bins used to correlate markers with rapport
+pub const CORRELATION_BIN_SECS: f64 = 30.0;
+
+/// Load the persisted rapport curve of a session ordered by timestamp
+pub async fn load_rapport(
+    pool: &sqlx::SqlitePool,
+    session_id: &str
+) -> Result<Vec<RapportIndicator>, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT timestamp, value, trend, contributing_markers FROM rapport_indicators \
+         WHERE session_id = ? ORDER BY timestamp",
+    )
+    .bind(session_id)
+    .fetch_all(pool)
+    .await?;
+
+    rows.iter()
+        .map(|row| {
+            let contributing: String = row.try_get("contributing_markers")?;
+            Ok(RapportIndicator {
+                timestamp: row.try_get("timestamp")?,
+                value: row.try_get("value")?,
+                trend: row.try_get("trend")?,
+                contributing_markers: serde_json::from_str(&contributing)
+                    .map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+                lower: None,
+                upper: None,
+            })
+        })
+        .collect()
+}
+
+/// Rapport value at `time`, linearly interpolated and held constant past either end
+pub fn rapport_at(curve: &[RapportIndicator], time: f64) -> f64 {
+    let Some(first) = curve.first() else {
+        return 0.0;
+    };
+    if time <= first.timestamp {
+        return first.value;
+    }
+
+    for pair in curve.windows(2) {
+        let (before, after) = (&pair[0], &pair[1]);
+        if time <= after.timestamp {
+            let span = after.timestamp - before.timestamp;
+            if span <= 0.0 {
+                return after.value;
+            }
+            let fraction = (time - before.timestamp) / span;
+            return before.value + (after.value - before.value) * fraction;
+        }
+    }
+
+    curve.last().map(|last| last.value).unwrap_or(0.0)
+}
+
+/// Pearson correlation of two equally long series; 0.0 when either is constant
+pub fn pearson_correlation(xs: &[f64], ys: &[f64]) -> f64 {
+    let n = xs.len().min(ys.len());
+    if n < 2 {
+        return 0.0;
+    }
+
+    let mean_x = xs[..n].iter().sum::<f64>() / n as f64;
+    let mean_y = ys[..n].iter().sum::<f64>() / n as f64;
+    let (mut covariance, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+    for (x, y) in xs[..n].iter().zip(&ys[..n]) {
+        let (dx, dy) = (x - mean_x, y - mean_y);
+        covariance += dx * dy;
+        var_x += dx * dx;
+        var_y += dy * dy;
+    }
+
+    if var_x <= f64::EPSILON || var_y <= f64::EPSILON {
+        return 0.0;
+    }
+    covariance / (var_x.sqrt() * var_y.sqrt())
+}
+
+/// Correlate each marker type's density with the rapport curve.
+/// Both series are sampled on `bin_secs` bins: marker density counts markers
+/// overlapping a bin, rapport is interpolated at the bin centre. Results are
+/// sorted by descending correlation, then by marker type.
+pub fn marker_rapport_correlations(
+    markers: &[MarkerEvent],
+    curve: &[RapportIndicator],
+    bin_secs: f64
+) -> Vec<(String, f64)> {
+    if markers.is_empty() || curve.is_empty() || bin_secs <= 0.0 {
+        return Vec::new();
+    }
+
+    let end = markers
+        .iter()
+        .map(|marker| marker.end_time)
+        .chain(curve.iter().map(|point| point.timestamp))
+        .fold(0.0, f64::max);
+    let bin_count = ((end / bin_secs).ceil() as usize).max(1);
+
+    let rapport: Vec<f64> = (0..bin_count)
+        .map(|bin| rapport_at(curve, (bin as f64 + 0.5) * bin_secs))
+        .collect();
+
+    let mut densities: std::collections::BTreeMap<&str, Vec<f64>> = std::collections::BTreeMap::new();
+    for marker in markers {
+        let bins = densities
+            .entry(marker.marker_type.as_str())
+            .or_insert_with(|| vec![0.0; bin_count]);
+        let first = ((marker.start_time.max(0.0) / bin_secs).floor() as usize).min(bin_count - 1);
+        let last = ((marker.end_time.max(marker.start_time) / bin_secs).floor() as usize).min(bin_count - 1);
+        for bin in &mut bins[first..=last] {
+            *bin += 1.0;
+        }
+    }
+
+    let mut correlations: Vec<(String, f64)> = densities
+        .into_iter()
+        .map(|(marker_type, density)| {
+            (marker_type.to_string(), pearson_correlation(&density, &rapport))
+        })
+        .collect();
+    correlations.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    correlations
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RateBin {
+    pub start: f64,
+    /// Words per minute by speaker id; speakers silent in this bin report 0.0
+    pub speaker_rates: std::collections::BTreeMap<String, f64>,
+}
+
+/// Words per minute per speaker in consecutive `bin_secs` bins.
+/// Each word counts toward the bin containing its midpoint.
+pub fn speaking_rates(
+    segments: &[crate::transcription_commands::SpeakerSegment],
+    bin_secs: f64
+) -> Vec<RateBin> {
+    let end = segments.iter().map(|segment| segment.end_time).fold(0.0, f64::max);
+    if segments.is_empty() || bin_secs <= 0.0 || end <= 0.0 {
+        return Vec::new();
+    }
+
+    let bin_count = ((end / bin_secs).ceil() as usize).max(1);
+    let speakers: std::collections::BTreeSet<&str> =
+        segments.iter().map(|segment| segment.speaker_id.as_str()).collect();
+    let mut counts: Vec<std::collections::BTreeMap<&str, usize>> = (0..bin_count)
+        .map(|_| speakers.iter().map(|speaker| (*speaker, 0)).collect())
+        .collect();
+
+    for segment in segments {
+        for (_, start, end) in crate::transcription_commands::word_spans(segment) {
+            let bin = (((start + end) / 2.0 / bin_secs).floor().max(0.0) as usize).min(bin_count - 1);
+            *counts[bin].entry(segment.speaker_id.as_str()).or_default() += 1;
+        }
+    }
+
+    let minutes_per_bin = bin_secs / 60.0;
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(bin, words)| RateBin {
+            start: bin as f64 * bin_secs,
+            speaker_rates: words
+                .into_iter()
+                .map(|(speaker, count)| (speaker.to_string(), count as f64 / minutes_per_bin))
+                .collect(),
+        })
+        .collect()
+}
+
+/// Words that open a question when a turn has no question mark, English and German
+const QUESTION_OPENERS: &[&str] = &[
+    "what", "why", "how", "when", "where", "who", "which", "do", "does", "did", "can", "could",
+    "would", "is", "are", "was", "have", "has",
+    "warum", "wieso", "weshalb", "wie", "wann", "wo", "wer", "welche", "welcher", "welches",
+    "können", "kannst", "haben", "hast", "sind", "bist", "ist",
+];
+
+/// Weights of the clinician score; they sum to 1.0
+const ROLE_QUESTION_WEIGHT: f64 = 0.5;
+const ROLE_LISTENING_WEIGHT: f64 = 0.3;
+const ROLE_BREVITY_WEIGHT: f64 = 0.2;
+
+/// Per-speaker measurements the role suggestion is based on
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SpeakerRoleFeatures {
+    /// Share of all speaking time, 0.0..=1.0
+    pub talk_time_share: f64,
+    /// Runs of consecutive segments by this speaker
+    pub turn_count: usize,
+    pub average_turn_secs: f64,
+    /// Share of turns that ask a question, by question mark or opening word
+    pub question_ratio: f64,
+}
+
+/// Suggested role of one diarized speaker, to be accepted via `update_speaker_labels`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SpeakerRoleSuggestion {
+    pub speaker_id: String,
+    pub role: String, // "clinician", "client"
+    /// Clinician score: question ratio relative to the most questioning speaker
+    /// weighted 0.5, share of time spent listening 0.3, and turn brevity relative
+    /// to the longest-winded speaker 0.2. The top scorer is the clinician.
+    pub clinician_score: f64,
+    pub features: SpeakerRoleFeatures,
+    /// Plain-language reasons behind the suggestion
+    pub reasons: Vec<String>,
+}
+
+/// Per-speaker talk seconds, turn count and question turns
+type SpeakerTurnStats<'a> = std::collections::BTreeMap<&'a str, (f64, usize, usize)>;
+
+fn is_question(text: &str) -> bool {
+    if text.contains('?') {
+        return true;
+    }
+    text.split_whitespace()
+        .next()
+        .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+        .is_some_and(|word| QUESTION_OPENERS.contains(&word.as_str()))
+}
+
+/// Rank speakers by how clinician-like they talk: clinicians tend to ask more
+/// questions, talk less and take shorter turns. The best match is suggested as
+/// "clinician" and everyone else as "client"; results are sorted by score.
+pub fn speaker_role_suggestions(
+    segments: &[crate::transcription_commands::SpeakerSegment]
+) -> Vec<SpeakerRoleSuggestion> {
+    let mut ordered: Vec<_> = segments.iter().collect();
+    ordered.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+
+    fn close_turn<'a>(stats: &mut SpeakerTurnStats<'a>, speaker: Option<&'a str>, text: &str) {
+        if let Some(speaker) = speaker {
+            let entry = stats.entry(speaker).or_default();
+            entry.1 += 1;
+            if is_question(text) {
+                entry.2 += 1;
+            }
+        }
+    }
+
+    let mut stats = SpeakerTurnStats::new();
+    let mut turn_speaker: Option<&str> = None;
+    let mut turn_text = String::new();
+    for segment in &ordered {
+        let speaker = segment.speaker_id.as_str();
+        stats.entry(speaker).or_default().0 += (segment.end_time - segment.start_time).max(0.0);
+        if turn_speaker != Some(speaker) {
+            close_turn(&mut stats, turn_speaker, &turn_text);
+            turn_speaker = Some(speaker);
+            turn_text.clear();
+        }
+        turn_text.push(' ');
+        turn_text.push_str(&segment.text);
+    }
+    close_turn(&mut stats, turn_speaker, &turn_text);
+
+    let total_secs: f64 = stats.values().map(|(secs, _, _)| secs).sum();
+    let features: Vec<(&str, SpeakerRoleFeatures)> = stats
+        .into_iter()
+        .map(|(speaker, (secs, turns, questions))| {
+            (speaker, SpeakerRoleFeatures {
+                talk_time_share: if total_secs > 0.0 { secs / total_secs } else { 0.0 },
+                turn_count: turns,
+                average_turn_secs: secs / turns.max(1) as f64,
+                question_ratio: questions as f64 / turns.max(1) as f64,
+            })
+        })
+        .collect();
+
+    let max_question_ratio = features.iter().map(|(_, f)| f.question_ratio).fold(0.0, f64::max);
+    let max_turn_secs = features.iter().map(|(_, f)| f.average_turn_secs).fold(0.0, f64::max);
+    let mut suggestions: Vec<SpeakerRoleSuggestion> = features
+        .into_iter()
+        .map(|(speaker, features)| {
+            let questioning = if max_question_ratio > 0.0 { features.question_ratio / max_question_ratio } else { 0.0 };
+            let brevity = if max_turn_secs > 0.0 { 1.0 - features.average_turn_secs / max_turn_secs } else { 0.0 };
+            let score = ROLE_QUESTION_WEIGHT * questioning
+                + ROLE_LISTENING_WEIGHT * (1.0 - features.talk_time_share)
+                + ROLE_BREVITY_WEIGHT * brevity;
+            SpeakerRoleSuggestion {
+                speaker_id: speaker.to_string(),
+                role: "client".to_string(),
+                clinician_score: (score * 100.0).round() / 100.0,
+                reasons: vec![
+                    format!("asks questions in {:.0}% of turns", features.question_ratio * 100.0),
+                    format!("speaks {:.0}% of the time", features.talk_time_share * 100.0),
+                    format!("{} turns averaging {:.1}s", features.turn_count, features.average_turn_secs),
+                ],
+                features,
+            }
+        })
+        .collect();
+
+    suggestions.sort_by(|a, b| {
+        b.clinician_score
+            .total_cmp(&a.clinician_score)
+            .then_with(|| a.speaker_id.cmp(&b.speaker_id))
+    });
+    if let Some(clinician) = suggestions.first_mut() {
+        clinician.role = "clinician".to_string();
+    }
+    suggestions
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransitionMatrix {
+    /// Row and column labels, sorted
+    pub marker_types: Vec<String>,
+    /// `counts[from][to]`: how often `to` directly follows `from`
+    pub counts: Vec<Vec<u32>>,
+    /// Row-normalized counts; rows of types never followed by another marker are all 0.0
+    pub probabilities: Vec<Vec<f64>>,
+    pub total_transitions: u32,
+}
+
+/// First-order transitions between consecutive markers in time order, self-transitions included
+pub fn transition_matrix(markers: &[MarkerEvent]) -> TransitionMatrix {
+    let mut ordered: Vec<&MarkerEvent> = markers.iter().collect();
+    ordered.sort_by(|a, b| {
+        a.start_time
+            .total_cmp(&b.start_time)
+            .then_with(|| a.end_time.total_cmp(&b.end_time))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+
+    let marker_types: Vec<String> = ordered
+        .iter()
+        .map(|marker| marker.marker_type.to_string())
+        .collect::<std::collections::BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let index_of = |marker_type: &str| {
+        marker_types
+            .binary_search_by(|t| t.as_str().cmp(marker_type))
+            .expect("marker type collected above")
+    };
+
+    let size = marker_types.len();
+    let mut counts = vec![vec![0u32; size]; size];
+    for pair in ordered.windows(2) {
+        counts[index_of(pair[0].marker_type.as_str())][index_of(pair[1].marker_type.as_str())] += 1;
+    }
+
+    let probabilities = counts
+        .iter()
+        .map(|row| {
+            let total: u32 = row.iter().sum();
+            row.iter()
+                .map(|count| if total == 0 { 0.0 } else { *count as f64 / total as f64 })
+                .collect()
+        })
+        .collect();
+
+    TransitionMatrix {
+        total_transitions: ordered.len().saturating_sub(1) as u32,
+        marker_types,
+        counts,
+        probabilities,
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+pub struct MarkerCooccurrence {
+    /// The pair is unordered; `type_a` sorts first
+    pub type_a: String,
+    pub type_b: String,
+    pub count: u32,
+    pub avg_gap_secs: f64,
+}
+
+/// Count marker pairs whose start times lie within `window_secs` of each other.
+/// Each pair of markers is counted once, under its alphabetically ordered types.
+pub fn marker_cooccurrence(markers: &[MarkerEvent], window_secs: f64) -> Vec<MarkerCooccurrence> {
+    let mut ordered: Vec<&MarkerEvent> = markers.iter().collect();
+    ordered.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+
+    let mut pairs: std::collections::BTreeMap<(&str, &str), (u32, f64)> = std::collections::BTreeMap::new();
+    for (i, first) in ordered.iter().enumerate() {
+        // Sorted by start, so the first marker out of reach ends the scan
+        let within_window = ordered[i + 1..]
+            .iter()
+            .take_while(|second| second.start_time - first.start_time <= window_secs);
+        for second in within_window {
+            let (a, b) = (first.marker_type.as_str(), second.marker_type.as_str());
+            let key = if a <= b { (a, b) } else { (b, a) };
+            let entry = pairs.entry(key).or_insert((0, 0.0));
+            entry.0 += 1;
+            entry.1 += second.start_time - first.start_time;
+        }
+    }
+
+    pairs
+        .into_iter()
+        .map(|((type_a, type_b), (count, total_gap))| MarkerCooccurrence {
+            type_a: type_a.to_string(),
+            type_b: type_b.to_string(),
+            count,
+            avg_gap_secs: total_gap / count as f64,
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnalysisProgress {
     pub session_id: String,
     pub progress: f64,
-    pub current_stage: String, // "ATO", "SEM", "CLU", "MEMA", "Rapport"
+    pub current_stage: String, // "preparing", "detecting", "ATO", "SEM", "CLU", "MEMA", "Rapport", "completed", "failed"
     pub markers_detected: u32,
 }
 
+/// Stage reported once the rapport curve has been computed from the markers
+const RAPPORT_STAGE: &str = "Rapport";
+
+/// Share of an analysis run spent in the Python marker detection
+const DETECTION_SHARE: f64 = 0.5;
+
+/// Marker detection is text-only and quick; a run this long has wedged
+const MARKER_ANALYSIS_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10 * 60);
+
+/// Live progress of marker analysis runs keyed by session id. Every change is
+/// also emitted as an `analysis-progress` event carrying the same snapshot.
+/// Running analyses are registered with the `CancellationManager` under their session id.
+#[derive(Default)]
+pub struct AnalysisJobs {
+    jobs: std::sync::Mutex<std::collections::HashMap<String, AnalysisProgress>>,
+}
+
+impl AnalysisJobs {
+    pub fn set_stage(&self, app: &tauri::AppHandle, session_id: &str, stage: &str, progress: f64) {
+        self.update(app, session_id, |job| {
+            job.current_stage = stage.to_string();
+            job.progress = progress;
+        });
+    }
+
+    pub fn set_markers_detected(&self, app: &tauri::AppHandle, session_id: &str, count: u32) {
+        self.update(app, session_id, |job| job.markers_detected = count);
+    }
+
+    pub fn progress(&self, session_id: &str) -> Option<AnalysisProgress> {
+        self.jobs.lock().unwrap().get(session_id).cloned()
+    }
+
+    fn update(&self, app: &tauri::AppHandle, session_id: &str, apply: impl FnOnce(&mut AnalysisProgress)) {
+        let snapshot = {
+            let mut jobs = self.jobs.lock().unwrap();
+            let job = jobs.entry(session_id.to_string()).or_insert_with(|| AnalysisProgress {
+                session_id: session_id.to_string(),
+                progress: 0.0,
+                current_stage: "preparing".to_string(),
+                markers_detected: 0,
+            });
+            apply(job);
+            job.clone()
+        };
+
+        if let Err(e) = app.emit("analysis-progress", &snapshot) {
+            log::warn!("Failed to emit analysis progress: {}", e);
+        }
+    }
+}
+
+pub async fn insert_markers(
+    conn: &mut sqlx::SqliteConnection,
+    session_id: &str,
+    markers: &[&MarkerEvent]
+) -> Result<(), sqlx::Error> {
+    for marker in markers {
+        sqlx::query(
+            "INSERT INTO marker_events \
+             (id, session_id, marker_type, start_time, end_time, confidence, evidence, explanation, speaker) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&marker.id)
+        .bind(session_id)
+        .bind(marker.marker_type.as_str())
+        .bind(marker.start_time)
+        .bind(marker.end_time)
+        .bind(marker.confidence)
+        .bind(&marker.evidence)
+        .bind(&marker.explanation)
+        .bind(&marker.speaker)
+        .execute(&mut *conn)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Replace the persisted rapport curve of a session
+pub async fn replace_rapport(
+    conn: &mut sqlx::SqliteConnection,
+    session_id: &str,
+    indicators: &[RapportIndicator]
+) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM rapport_indicators WHERE session_id = ?")
+        .bind(session_id)
+        .execute(&mut *conn)
+        .await?;
+
+    for indicator in indicators {
+        let contributing = serde_json::to_string(&indicator.contributing_markers)
+            .map_err(|e| sqlx::Error::Protocol(format!("Failed to encode contributing markers: {}", e)))?;
+        sqlx::query(
+            "INSERT INTO rapport_indicators (session_id, timestamp, value, trend, contributing_markers) \
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(session_id)
+        .bind(indicator.timestamp)
+        .bind(indicator.value)
+        .bind(&indicator.trend)
+        .bind(contributing)
+        .execute(&mut *conn)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Run the LD-3.4 pipeline over a transcript, then store its markers level by
+/// level and the rapport curve they produce, reporting each stage as it completes
+async fn run_marker_analysis(
+    app: &tauri::AppHandle,
+    state: &AppState,
+    temp_files: &crate::secure_temp::SecureTempRegistry,
+    jobs: &AnalysisJobs,
+    session_id: &str,
+    transcript_segments: &[crate::transcription_commands::SpeakerSegment]
+) -> Result<u32, AppError> {
+    // The transcript is wiped from disk as soon as the pipeline is done with it
+    let temp_guard = temp_files.track(&format!("analysis-{}", session_id));
+    let transcript_path = std::env::temp_dir().join(format!("analysis_{}_transcript.json", session_id));
+    let contents = serde_json::to_string(transcript_segments)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize transcript: {}", e)))?;
+    tokio::fs::write(&transcript_path, contents)
+        .await
+        .map_err(|e| AppError::Io(format!("Failed to write transcript file: {}", e)))?;
+    temp_guard.register(transcript_path.clone());
+
+    jobs.set_stage(app, session_id, "detecting", 0.0);
+    let output = crate::python_integration::analyze_markers(
+        app,
+        &transcript_path.to_string_lossy(),
+        session_id,
+        Some(MARKER_ANALYSIS_TIMEOUT),
+    )
+    .await?;
+    drop(temp_guard);
+
+    let markers: Vec<MarkerEvent> = serde_json::from_str(&output)
+        .map_err(|e| AppError::Python(format!("Failed to parse marker analysis output: {}", e)))?;
+
+    // Each LD-3.4 level is a stage reported once its markers are stored
+    let stage_share = (1.0 - DETECTION_SHARE) / (MarkerType::ALL.len() + 1) as f64;
+    let mut tx = state.db.begin().await.map_err(AppError::from)?;
+    sqlx::query("DELETE FROM marker_events WHERE session_id = ?")
+        .bind(session_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to clear previous markers: {}", e)))?;
+
+    let mut detected = 0;
+    for (index, level) in MarkerType::ALL.into_iter().enumerate() {
+        let level_markers: Vec<&MarkerEvent> = markers
+            .iter()
+            .filter(|marker| marker.marker_type == level)
+            .collect();
+        insert_markers(&mut tx, session_id, &level_markers)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to save {} markers: {}", level, e)))?;
+
+        detected += level_markers.len() as u32;
+        jobs.set_markers_detected(app, session_id, detected);
+        jobs.set_stage(app, session_id, level.as_str(), DETECTION_SHARE + stage_share * (index + 1) as f64);
+    }
+
+    let curve = rapport_indicators(&markers, &RapportWeights::default(), RAPPORT_WINDOW_SECS);
+    replace_rapport(&mut tx, session_id, &curve)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to save rapport curve: {}", e)))?;
+    tx.commit().await.map_err(|e| AppError::Database(format!("Failed to save analysis: {}", e)))?;
+    jobs.set_stage(app, session_id, RAPPORT_STAGE, 1.0);
+
+    Ok(detected)
+}
+
+/// Reject transcripts the pipeline cannot analyze, listing every problem found:
+/// there must be a segment with text, and all segment times must be in order
+pub fn validate_analysis_input(segments: &[crate::transcription_commands::SpeakerSegment]) -> Result<(), AppError> {
+    let mut problems = Vec::new();
+    if segments.is_empty() {
+        problems.push("the transcript has no segments".to_string());
+    } else if segments.iter().all(|segment| segment.text.trim().is_empty()) {
+        problems.push("no segment has any text".to_string());
+    }
+    for (index, segment) in segments.iter().enumerate() {
+        if let Err(e) = crate::transcription_commands::validate_segment_times(segment) {
+            problems.push(format!("transcript_segments[{}]: {}", index, e));
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::Validation(format!("Cannot analyze transcript: {}", problems.join("; "))))
+    }
+}
+
 #[tauri::command]
 pub async fn analyze_transcript(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    temp_files: State<'_, crate::secure_temp::SecureTempRegistry>,
+    jobs: State<'_, AnalysisJobs>,
+    cancellations: State<'_, crate::cancellation::CancellationManager>,
     session_id: String,
     transcript_segments: Vec<crate::transcription_commands::SpeakerSegment>
-) -> Result<String, String> {
-    // TODO: Implement LD-3.4 marker analysis pipeline
+) -> Result<u32, AppError> {
     log::info!("Starting LD-3.4 analysis for session: {}", session_id);
     
-    // TODO: Call Python integration for marker analysis
-    // This will use the existing LD-3.4 pipeline via library reuse
+    validate_analysis_input(&transcript_segments)?;
     
-    Ok("Analysis started successfully".to_string())
+    let token = cancellations.try_register_with_id(&session_id).ok_or_else(|| {
+        AppError::Validation(format!("Analysis is already running for session: {}", session_id))
+    })?;
+    jobs.set_stage(&app, &session_id, "preparing", 0.0);
+    // Dropping the run kills the Python process, rolls back the unfinished
+    // marker transaction and wipes the transcript temp file
+    let outcome = tokio::select! {
+        outcome = run_marker_analysis(&app, &state, &temp_files, &jobs, &session_id, &transcript_segments) => outcome,
+        _ = token.cancelled() => Err(AppError::Cancelled(format!("Analysis cancelled for session: {}", session_id))),
+    };
+    cancellations.finish(&session_id);
+    match outcome {
+        Err(AppError::Cancelled(message)) => {
+            let progress = jobs.progress(&session_id).map(|job| job.progress).unwrap_or(0.0);
+            jobs.set_stage(&app, &session_id, "cancelled", progress);
+            log::info!("{}", message);
+            Err(AppError::Cancelled(message))
+        }
+        Ok(count) => {
+            jobs.set_stage(&app, &session_id, "completed", 1.0);
+            log::info!("Detected {} markers for session: {}", count, session_id);
+            Ok(count)
+        }
+        Err(e) => {
+            let progress = jobs.progress(&session_id).map(|job| job.progress).unwrap_or(0.0);
+            jobs.set_stage(&app, &session_id, "failed", progress);
+            Err(e)
+        }
+    }
 }
 
 #[tauri::command]
-pub async fn get_analysis_progress(session_id: String) -> Result<AnalysisProgress, String> {
-    // TODO: Implement analysis progress tracking
+pub async fn cancel_analysis(
+    cancellations: State<'_, crate::cancellation::CancellationManager>,
+    session_id: String
+) -> Result<String, AppError> {
+    log::info!("Cancelling analysis for session: {}", session_id);
+    
+    if !cancellations.cancel(&session_id) {
+        return Err(AppError::NotFound(format!("No running analysis for session: {}", session_id)));
+    }
+    Ok("Analysis cancelled".to_string())
+}
+
+#[tauri::command]
+pub async fn get_analysis_progress(
+    jobs: State<'_, AnalysisJobs>,
+    session_id: String
+) -> Result<AnalysisProgress, AppError> {
     log::info!("Getting analysis progress for session: {}", session_id);
     
-    Ok(AnalysisProgress {
-        session_id: session_id.clone(),
-        progress: 0.60,
-        current_stage: "CLU".to_string(),
-        markers_detected: 15,
-    })
+    jobs.progress(&session_id)
+        .ok_or_else(|| AppError::NotFound(format!("No analysis found for session: {}", session_id)))
 }
 
+/// Compute the rapport curve for `markers` and store it as the session's
+/// timeline, replacing any earlier one
 #[tauri::command]
 pub async fn calculate_rapport(
+    state: State<'_, AppState>,
     session_id: String,
-    markers: Vec<MarkerEvent>
-) -> Result<Vec<RapportIndicator>, String> {
-    // TODO: Implement rapport calculation from marker patterns
+    markers: Vec<MarkerEvent>,
+    weights: Option<RapportWeights>,
+    include_confidence_bands: Option<bool>,
+    smoothing: Option<SmoothingConfig>
+) -> Result<Vec<RapportIndicator>, AppError> {
     log::info!("Calculating rapport indicators for session: {}", session_id);
     
-    // Mock rapport calculation
-    Ok(vec![
-        RapportIndicator {
-            timestamp: 60.0,
-            value: 0.7,
-            trend: "increasing".to_string(),
-            contributing_markers: vec!["ATO_001".to_string(), "SEM_003".to_string()],
-        },
+    let weights = weights.unwrap_or_default();
+    weights.validate().map_err(AppError::Validation)?;
+    if let Some(smoothing) = &smoothing {
+        smoothing.validate().map_err(AppError::Validation)?;
+    }
+    for marker in &markers {
+        validate_marker(marker)?;
+    }
+    
+    let mut indicators = rapport_indicators(&markers, &weights, RAPPORT_WINDOW_SECS);
+    
+    // The stored timeline keeps the raw per-window values
+    let mut tx = state.db.begin().await.map_err(AppError::from)?;
+    replace_rapport(&mut tx, &session_id, &indicators)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to save rapport curve: {}", e)))?;
+    tx.commit().await.map_err(|e| AppError::Database(format!("Failed to save rapport curve: {}", e)))?;
+    
+    // Only values are smoothed; each window keeps its own contributing markers
+    if let Some(smoothing) = &smoothing {
+        let mut values: Vec<f64> = indicators.iter().map(|indicator| indicator.value).collect();
+        smoothing.apply(&mut values);
+        for (indicator, value) in indicators.iter_mut().zip(values) {
+            indicator.value = value;
+        }
+    }
+    
+    if include_confidence_bands.unwrap_or(false) {
+        apply_confidence_bands(&mut indicators, &markers);
+    }
+    
+    Ok(indicators)
+}
+
+/// Stored rapport curve of a session ordered by timestamp, empty when rapport
+/// has not been computed yet
+#[tauri::command]
+pub async fn get_rapport_timeline(
+    state: State<'_, AppState>,
+    session_id: String
+) -> Result<Vec<RapportIndicator>, AppError> {
+    log::info!("Loading rapport timeline for session: {}", session_id);
+
+    load_rapport(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load rapport curve: {}", e)))
+}
+
+/// Quiet period after the last marker edit before the session's rapport is recomputed
+const RAPPORT_RECALCULATION_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(1500);
+
+/// Payload of the `rapport-updated` event
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RapportUpdate {
+    pub session_id: String,
+    pub indicators: Vec<RapportIndicator>,
+}
+
+/// Recompute a session's stored rapport timeline from its persisted markers
+/// with the default weights, and announce it with a `rapport-updated` event
+pub async fn recompute_rapport(
+    app: &tauri::AppHandle,
+    db: &sqlx::SqlitePool,
+    session_id: &str
+) -> Result<Vec<RapportIndicator>, AppError> {
+    let markers = load_markers(db, session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load markers: {}", e)))?;
+    let indicators = rapport_indicators(&markers, &RapportWeights::default(), RAPPORT_WINDOW_SECS);
+
+    let mut tx = db.begin().await.map_err(AppError::from)?;
+    replace_rapport(&mut tx, session_id, &indicators)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to save rapport curve: {}", e)))?;
+    tx.commit().await.map_err(|e| AppError::Database(format!("Failed to save rapport curve: {}", e)))?;
+
+    let update = RapportUpdate {
+        session_id: session_id.to_string(),
+        indicators,
+    };
+    if let Err(e) = app.emit("rapport-updated", &update) {
+        log::warn!("Failed to emit rapport update: {}", e);
+    }
+    Ok(update.indicators)
+}
+
+/// Debounced rapport recomputation after marker edits. Each edit bumps the
+/// session's generation; a scheduled run only goes ahead if no later edit
+/// superseded it, so a batch of edits recomputes once.
+#[derive(Default)]
+pub struct RapportRecalculations {
+    generations: std::sync::Arc<std::sync::Mutex<std::collections::HashMap<String, u64>>>,
+}
+
+impl RapportRecalculations {
+    pub fn schedule(&self, app: &tauri::AppHandle, db: &sqlx::SqlitePool, session_id: &str) {
+        let generation = {
+            let mut generations = self.generations.lock().unwrap();
+            let generation = generations.entry(session_id.to_string()).or_insert(0);
+            *generation += 1;
+            *generation
+        };
+
+        let generations = self.generations.clone();
+        let app = app.clone();
+        let db = db.clone();
+        let session_id = session_id.to_string();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(RAPPORT_RECALCULATION_DEBOUNCE).await;
+            {
+                let mut generations = generations.lock().unwrap();
+                if generations.get(&session_id) != Some(&generation) {
+                    return;
+                }
+                generations.remove(&session_id);
+            }
+            if let Err(e) = recompute_rapport(&app, &db, &session_id).await {
+                log::error!("Failed to recalculate rapport for session {}: {}", session_id, e);
+            }
+        });
+    }
+}
+
+/// Recompute the stored rapport timeline from the session's current markers
+#[tauri::command]
+pub async fn recalculate_rapport(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    session_id: String
+) -> Result<Vec<RapportIndicator>, AppError> {
+    log::info!("Recalculating rapport for session: {}", session_id);
+
+    recompute_rapport(&app, &state.db, &session_id).await
+}
+
+#[tauri::command]
+pub async fn marker_rapport_correlation(
+    state: State<'_, AppState>,
+    session_id: String,
+    bin_secs: Option<f64>
+) -> Result<Vec<(String, f64)>, AppError> {
+    let bin_secs = bin_secs.unwrap_or(CORRELATION_BIN_SECS);
+    log::info!("Correlating markers with rapport for session: {} ({}s bins)", session_id, bin_secs);
+
+    if !bin_secs.is_finite() || bin_secs <= 0.0 {
+        return Err(AppError::Validation("bin_secs must be a positive number of seconds".to_string()));
+    }
+
+    let markers = load_markers(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load markers: {}", e)))?;
+    let curve = load_rapport(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load rapport curve: {}", e)))?;
+
+    if curve.is_empty() {
+        return Err(AppError::NotFound(format!("No rapport curve stored for session: {}", session_id)));
+    }
+
+    Ok(marker_rapport_correlations(&markers, &curve, bin_secs))
+}
+
+#[tauri::command]
+pub async fn speaking_rate(
+    state: State<'_, AppState>,
+    session_id: String,
+    bin_secs: f64
+) -> Result<Vec<RateBin>, AppError> {
+    log::info!("Computing speaking rate for session: {} ({}s bins)", session_id, bin_secs);
+
+    if !bin_secs.is_finite() || bin_secs <= 0.0 {
+        return Err(AppError::Validation("bin_secs must be a positive number of seconds".to_string()));
+    }
+
+    let segments = crate::storage_commands::load_segments(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load transcript: {}", e)))?;
+
+    Ok(speaking_rates(&segments, bin_secs))
+}
+
+#[tauri::command]
+pub async fn suggest_speaker_roles(
+    state: State<'_, AppState>,
+    session_id: String
+) -> Result<Vec<SpeakerRoleSuggestion>, AppError> {
+    log::info!("Suggesting speaker roles for session: {}", session_id);
+
+    let segments = crate::storage_commands::load_segments(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load transcript: {}", e)))?;
+    if segments.is_empty() {
+        return Err(AppError::Validation("Session has no transcript to analyze".to_string()));
+    }
+
+    Ok(speaker_role_suggestions(&segments))
+}
+
+#[tauri::command]
+pub async fn marker_transition_matrix(
+    state: State<'_, AppState>,
+    session_id: String
+) -> Result<TransitionMatrix, AppError> {
+    log::info!("Computing marker transition matrix for session: {}", session_id);
+
+    let markers = load_markers(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load markers: {}", e)))?;
+
+    Ok(transition_matrix(&markers))
+}
+
+#[tauri::command]
+pub async fn get_marker_cooccurrence(
+    state: State<'_, AppState>,
+    session_id: String,
+    window_secs: f64
+) -> Result<Vec<MarkerCooccurrence>, AppError> {
+    log::info!("Computing marker co-occurrence for session: {} ({}s window)", session_id, window_secs);
+
+    if !window_secs.is_finite() || window_secs <= 0.0 {
+        return Err(AppError::Validation("window_secs must be a positive number of seconds".to_string()));
+    }
+
+    let markers = load_markers(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load markers: {}", e)))?;
+
+    Ok(marker_cooccurrence(&markers, window_secs))
+}
+
+#[tauri::command]
+pub async fn get_markers(
+    state: State<'_, AppState>,
+    session_id: String,
+    min_confidence: Option<f64>,
+    marker_types: Option<Vec<String>>,
+    time_range: Option<(f64, f64)>
+) -> Result<Vec<MarkerEvent>, AppError> {
+    log::info!("Loading markers for session: {}", session_id);
+
+    if let Some(min) = min_confidence {
+        if !(0.0..=1.0).contains(&min) {
+            return Err(AppError::Validation("min_confidence must be between 0.0 and 1.0".to_string()));
+        }
+    }
+    if let Some((start, end)) = time_range {
+        if !start.is_finite() || !end.is_finite() || start > end {
+            return Err(AppError::Validation("time_range must be an ordered (start, end) pair".to_string()));
+        }
+    }
+
+    let mut builder = sqlx::QueryBuilder::new(format!(
+        "SELECT {} FROM marker_events WHERE session_id = ",
+        MARKER_COLUMNS
+    ));
+    builder.push_bind(session_id);
+    if let Some(types) = marker_types.filter(|types| !types.is_empty()) {
+        builder.push(" AND marker_type IN (");
+        let mut separated = builder.separated(", ");
+        for marker_type in types {
+            separated.push_bind(marker_type);
+        }
+        separated.push_unseparated(")");
+    }
+    if let Some(min) = min_confidence {
+        builder.push(" AND confidence >= ").push_bind(min);
+    }
+    // Markers overlapping the range, including ones straddling its edges
+    if let Some((start, end)) = time_range {
+        builder
+            .push(" AND end_time >= ")
+            .push_bind(start)
+            .push(" AND start_time <= ")
+            .push_bind(end);
+    }
+    builder.push(" ORDER BY start_time");
+
+    let rows = builder
+        .build()
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load markers: {}", e)))?;
+
+    rows.iter()
+        .map(marker_from_row)
+        .collect::<Result<Vec<_>, sqlx::Error>>()
+        .map_err(|e| AppError::Database(format!("Failed to decode marker: {}", e)))
+}
+
+/// One page of markers across all sessions matching `filters`, most confident
+/// first, each paired with its session id
+async fn find_markers(
+    pool: &sqlx::SqlitePool,
+    filters: &MarkerFilters
+) -> Result<Vec<(String, MarkerEvent)>, AppError> {
+    let limit = filters.limit.unwrap_or(DEFAULT_MARKER_QUERY_LIMIT);
+    if limit == 0 || limit > MAX_MARKER_QUERY_LIMIT {
+        return Err(AppError::Validation(format!("limit must be between 1 and {}", MAX_MARKER_QUERY_LIMIT)));
+    }
+
+    let mut builder = sqlx::QueryBuilder::new(format!(
+        "SELECT session_id, {} FROM marker_events WHERE 1 = 1",
+        MARKER_COLUMNS
+    ));
+    if !filters.marker_types.is_empty() {
+        builder.push(" AND marker_type IN (");
+        let mut separated = builder.separated(", ");
+        for marker_type in &filters.marker_types {
+            separated.push_bind(marker_type.clone());
+        }
+        separated.push_unseparated(")");
+    }
+    if let Some(min) = filters.min_confidence {
+        builder.push(" AND confidence >= ").push_bind(min);
+    }
+    if let Some(max) = filters.max_confidence {
+        builder.push(" AND confidence <= ").push_bind(max);
+    }
+    if let Some(speaker) = &filters.speaker {
+        builder.push(" AND speaker = ").push_bind(speaker.clone());
+    }
+    if let Some(text) = filters.text.as_deref().map(str::trim).filter(|t| !t.is_empty()) {
+        let pattern = crate::storage_commands::like_pattern(text);
+        builder
+            .push(" AND (evidence LIKE ")
+            .push_bind(pattern.clone())
+            .push(" ESCAPE '\\' OR explanation LIKE ")
+            .push_bind(pattern)
+            .push(" ESCAPE '\\')");
+    }
+    // Stable order so pages neither skip nor repeat markers
+    builder
+        .push(" ORDER BY confidence DESC, session_id, start_time, id LIMIT ")
+        .push_bind(limit as i64)
+        .push(" OFFSET ")
+        .push_bind(filters.offset.unwrap_or(0) as i64);
+
+    let rows = builder
+        .build()
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to query markers: {}", e)))?;
+
+    rows.iter()
+        .map(|row| {
+            let session_id: String = row.try_get("session_id")?;
+            Ok((session_id, marker_from_row(row)?))
+        })
+        .collect::<Result<Vec<_>, sqlx::Error>>()
+        .map_err(|e| AppError::Database(format!("Failed to decode marker: {}", e)))
+}
+
+#[tauri::command]
+pub async fn query_markers_global(
+    state: State<'_, AppState>,
+    filters: MarkerFilters
+) -> Result<Vec<(String, MarkerEvent)>, AppError> {
+    log::info!("Querying markers across all sessions");
+    log::debug!("Marker filters: {:?}", filters);
+
+    find_markers(&state.db, &filters).await
+}
+
+/// Fields `update_marker` changes; unset fields are left as they are
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MarkerPatch {
+    pub marker_type: Option<MarkerType>,
+    pub start_time: Option<f64>,
+    pub end_time: Option<f64>,
+    pub confidence: Option<f64>,
+    pub evidence: Option<String>,
+    pub explanation: Option<String>,
+}
+
+impl MarkerPatch {
+    fn apply(self, marker: &mut MarkerEvent) {
+        if let Some(marker_type) = self.marker_type {
+            marker.marker_type = marker_type;
+        }
+        if let Some(start_time) = self.start_time {
+            marker.start_time = start_time;
+        }
+        if let Some(end_time) = self.end_time {
+            marker.end_time = end_time;
+        }
+        if let Some(confidence) = self.confidence {
+            marker.confidence = confidence;
+        }
+        if let Some(evidence) = self.evidence {
+            marker.evidence = evidence;
+        }
+        if let Some(explanation) = self.explanation {
+            marker.explanation = explanation;
+        }
+    }
+}
+
+/// Check a marker an analyst wrote or edited before it is stored
+pub fn validate_marker(marker: &MarkerEvent) -> Result<(), AppError> {
+    if !marker.start_time.is_finite() || !marker.end_time.is_finite() || marker.start_time < 0.0 {
+        return Err(AppError::Validation("Marker times must be non-negative numbers".to_string()));
+    }
+    if marker.start_time > marker.end_time {
+        return Err(AppError::Validation("Marker start_time must not be after end_time".to_string()));
+    }
+    if !(0.0..=1.0).contains(&marker.confidence) {
+        return Err(AppError::Validation("Marker confidence must be between 0.0 and 1.0".to_string()));
+    }
+    Ok(())
+}
+
+async fn fetch_marker(
+    conn: &mut sqlx::SqliteConnection,
+    session_id: &str,
+    marker_id: &str
+) -> Result<Option<MarkerEvent>, sqlx::Error> {
+    let sql = format!("SELECT {} FROM marker_events WHERE session_id = ? AND id = ?", MARKER_COLUMNS);
+    let row = sqlx::query(&sql)
+        .bind(session_id)
+        .bind(marker_id)
+        .fetch_optional(&mut *conn)
+        .await?;
+    row.as_ref().map(marker_from_row).transpose()
+}
+
+#[tauri::command]
+pub async fn update_marker(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    recalculations: State<'_, RapportRecalculations>,
+    session_id: String,
+    marker_id: String,
+    patch: MarkerPatch
+) -> Result<MarkerEvent, AppError> {
+    log::info!("Updating marker {} of session: {}", marker_id, session_id);
+
+    let mut tx = state.db.begin().await.map_err(AppError::from)?;
+    let mut marker = fetch_marker(&mut tx, &session_id, &marker_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load marker: {}", e)))?
+        .ok_or_else(|| AppError::NotFound(format!("Marker not found: {}", marker_id)))?;
+    patch.apply(&mut marker);
+    validate_marker(&marker)?;
+
+    sqlx::query(
+        "UPDATE marker_events SET marker_type = ?, start_time = ?, end_time = ?, confidence = ?, \
+         evidence = ?, explanation = ? WHERE session_id = ? AND id = ?",
+    )
+    .bind(marker.marker_type.as_str())
+    .bind(marker.start_time)
+    .bind(marker.end_time)
+    .bind(marker.confidence)
+    .bind(&marker.evidence)
+    .bind(&marker.explanation)
+    .bind(&session_id)
+    .bind(&marker_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to update marker: {}", e)))?;
+    tx.commit().await.map_err(|e| AppError::Database(format!("Failed to update marker: {}", e)))?;
+    recalculations.schedule(&app, &state.db, &session_id);
+
+    Ok(marker)
+}
+
+#[tauri::command]
+pub async fn delete_marker(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    recalculations: State<'_, RapportRecalculations>,
+    session_id: String,
+    marker_id: String
+) -> Result<(), AppError> {
+    log::info!("Deleting marker {} of session: {}", marker_id, session_id);
+
+    let deleted = sqlx::query("DELETE FROM marker_events WHERE session_id = ? AND id = ?")
+        .bind(&session_id)
+        .bind(&marker_id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to delete marker: {}", e)))?;
+    if deleted.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!("Marker not found: {}", marker_id)));
+    }
+    recalculations.schedule(&app, &state.db, &session_id);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn add_marker(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    recalculations: State<'_, RapportRecalculations>,
+    session_id: String,
+    mut marker: MarkerEvent
+) -> Result<MarkerEvent, AppError> {
+    log::info!("Adding {} marker to session: {}", marker.marker_type, session_id);
+
+    validate_marker(&marker)?;
+    if marker.id.trim().is_empty() {
+        marker.id = uuid::Uuid::new_v4().to_string();
+    }
+
+    let mut tx = state.db.begin().await.map_err(AppError::from)?;
+    let session_exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM conversation_sessions WHERE id = ?)")
+        .bind(&session_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load session: {}", e)))?;
+    if !session_exists {
+        return Err(AppError::NotFound(format!("Session not found: {}", session_id)));
+    }
+    if fetch_marker(&mut tx, &session_id, &marker.id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load marker: {}", e)))?
+        .is_some()
+    {
+        return Err(AppError::Validation(format!("Marker already exists: {}", marker.id)));
+    }
+    insert_markers(&mut tx, &session_id, &[&marker])
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to add marker: {}", e)))?;
+    tx.commit().await.map_err(|e| AppError::Database(format!("Failed to add marker: {}", e)))?;
+    recalculations.schedule(&app, &state.db, &session_id);
+
+    Ok(marker)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{insert, marker, memory_pool, segment, session};
+
+    fn curve_point(timestamp: f64, value: f64) -> RapportIndicator {
         RapportIndicator {
-            timestamp: 120.0,
-            value: 0.8,
+            timestamp,
+            value,
             trend: "stable".to_string(),
-            contributing_markers: vec!["CLU_002".to_string()],
-        },
-    ])
-}
\ No newline at end of file
+            contributing_markers: Vec::new(),
+            lower: None,
+            upper: None,
+        }
+    }
+
+    #[test]
+    fn correlates_each_marker_type_with_rapport() {
+        let curve = vec![
+            curve_point(15.0, 0.1),
+            curve_point(45.0, 0.9),
+            curve_point(75.0, 0.2),
+            curve_point(105.0, 0.8),
+        ];
+        let markers = vec![
+            // ATO in the high-rapport bins, SEM in the low ones, CLU everywhere
+            marker("a1", MarkerType::Ato, 35.0, 40.0, 0.9, None),
+            marker("a2", MarkerType::Ato, 95.0, 100.0, 0.9, None),
+            marker("s1", MarkerType::Sem, 5.0, 10.0, 0.9, None),
+            marker("s2", MarkerType::Sem, 65.0, 70.0, 0.9, None),
+            marker("c1", MarkerType::Clu, 0.0, 110.0, 0.9, None),
+        ];
+
+        let correlations = marker_rapport_correlations(&markers, &curve, 30.0);
+
+        let types: Vec<&str> = correlations.iter().map(|(marker_type, _)| marker_type.as_str()).collect();
+        assert_eq!(types, ["ATO", "CLU", "SEM"]);
+        let expected = 0.7 / 0.5_f64.sqrt();
+        assert!((correlations[0].1 - expected).abs() < 1e-9);
+        assert_eq!(correlations[1].1, 0.0);
+        assert!((correlations[2].1 + expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn correlation_is_empty_without_a_curve_or_markers() {
+        let markers = vec![marker("a1", MarkerType::Ato, 0.0, 5.0, 0.9, None)];
+        assert!(marker_rapport_correlations(&markers, &[], 30.0).is_empty());
+        assert!(marker_rapport_correlations(&[], &[curve_point(15.0, 0.5)], 30.0).is_empty());
+    }
+
+    #[test]
+    fn pearson_of_a_constant_series_is_zero() {
+        assert_eq!(pearson_correlation(&[1.0, 1.0, 1.0], &[0.1, 0.5, 0.9]), 0.0);
+        assert!((pearson_correlation(&[1.0, 2.0, 3.0], &[2.0, 4.0, 6.0]) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn confidence_band_narrows_with_confident_and_numerous_markers() {
+        assert_eq!(confidence_band(0.3, &[]), (-1.0, 1.0));
+        assert_eq!(confidence_band(0.3, &[1.0]), (0.3, 0.3));
+
+        let (lower, upper) = confidence_band(0.0, &[0.5, 0.5, 0.5, 0.5]);
+        assert!((lower + 0.25).abs() < 1e-12 && (upper - 0.25).abs() < 1e-12);
+
+        // Same mean, but spread out confidences widen the band
+        let (lower, upper) = confidence_band(0.0, &[0.2, 0.8]);
+        let expected = 0.8 / 2.0_f64.sqrt();
+        assert!((lower + expected).abs() < 1e-12 && (upper - expected).abs() < 1e-12);
+
+        let width = |confidences: &[f64]| {
+            let (lower, upper) = confidence_band(0.0, confidences);
+            upper - lower
+        };
+        assert!(width(&[0.6; 9]) < width(&[0.6; 4]));
+        assert!(width(&[0.9; 4]) < width(&[0.6; 4]));
+    }
+
+    #[test]
+    fn confidence_band_stays_within_the_rapport_range() {
+        assert_eq!(confidence_band(0.9, &[0.5]), (0.4, 1.0));
+        assert_eq!(confidence_band(-0.9, &[0.5]), (-1.0, -0.4));
+        // Out of range confidences are clamped rather than inverting the band
+        assert_eq!(confidence_band(0.2, &[1.7]), (0.2, 0.2));
+    }
+
+    #[test]
+    fn bands_use_each_indicators_contributing_markers() {
+        let markers = vec![
+            marker("sure", MarkerType::Ato, 0.0, 5.0, 1.0, None),
+            marker("unsure", MarkerType::Sem, 40.0, 45.0, 0.5, None),
+        ];
+        let mut indicators = vec![curve_point(15.0, 0.5), curve_point(45.0, 0.2), curve_point(75.0, 0.0)];
+        indicators[0].contributing_markers = vec!["sure".to_string()];
+        indicators[1].contributing_markers = vec!["unsure".to_string()];
+        indicators[2].contributing_markers = vec!["deleted".to_string()];
+
+        apply_confidence_bands(&mut indicators, &markers);
+
+        let bands: Vec<(Option<f64>, Option<f64>)> =
+            indicators.iter().map(|indicator| (indicator.lower, indicator.upper)).collect();
+        assert_eq!(bands, [(Some(0.5), Some(0.5)), (Some(-0.3), Some(0.7)), (Some(-1.0), Some(1.0))]);
+    }
+
+    #[test]
+    fn speaking_rates_count_words_per_speaker_in_each_bin() {
+        let mut aligned = segment("SPEAKER_01", 28.0, 45.0, "yes I think");
+        aligned.words = Some(
+            [("yes", 28.0, 29.0), ("I", 29.5, 30.5), ("think", 31.0, 32.0)]
+                .into_iter()
+                .map(|(text, start, end)| crate::transcription_commands::WordTiming {
+                    text: text.to_string(),
+                    start,
+                    end,
+                    confidence: 0.9,
+                })
+                .collect(),
+        );
+        let segments = [segment("SPEAKER_00", 0.0, 10.0, "how did the week go then"), aligned];
+
+        let bins = speaking_rates(&segments, 30.0);
+
+        let rates: Vec<(f64, Vec<(&str, f64)>)> = bins
+            .iter()
+            .map(|bin| {
+                let rates = bin.speaker_rates.iter().map(|(speaker, rate)| (speaker.as_str(), *rate)).collect();
+                (bin.start, rates)
+            })
+            .collect();
+        // A word whose midpoint sits on a bin edge counts toward the later bin
+        assert_eq!(
+            rates,
+            [
+                (0.0, vec![("SPEAKER_00", 12.0), ("SPEAKER_01", 2.0)]),
+                (30.0, vec![("SPEAKER_00", 0.0), ("SPEAKER_01", 4.0)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn speaking_rates_are_empty_without_speech_or_bins() {
+        assert!(speaking_rates(&[], 30.0).is_empty());
+        assert!(speaking_rates(&[segment("SPEAKER_00", 0.0, 5.0, "hello")], 0.0).is_empty());
+        assert!(speaking_rates(&[segment("SPEAKER_00", 0.0, 0.0, "")], 30.0).is_empty());
+    }
+
+    async fn marker_corpus() -> sqlx::SqlitePool {
+        let pool = memory_pool().await;
+        for session_id in ["s1", "s2"] {
+            insert(&pool, &session(session_id, "Session")).await;
+        }
+        let mut conn = pool.acquire().await.unwrap();
+        let mut s1 = Vec::new();
+        let mut s2 = Vec::new();
+        for index in 0..12 {
+            // Confidences repeat so the tie-breakers decide the order
+            let confidence = [0.9, 0.7, 0.5][index % 3];
+            let marker_type = if index % 2 == 0 { MarkerType::Ato } else { MarkerType::Sem };
+            let speaker = if index < 6 { "SPEAKER_00" } else { "SPEAKER_01" };
+            let start = index as f64;
+            let event = marker(&format!("m{:02}", index), marker_type, start, start + 1.0, confidence, Some(speaker));
+            if index % 4 == 0 {
+                s2.push(event);
+            } else {
+                s1.push(event);
+            }
+        }
+        insert_markers(&mut conn, "s1", &s1.iter().collect::<Vec<_>>()).await.unwrap();
+        insert_markers(&mut conn, "s2", &s2.iter().collect::<Vec<_>>()).await.unwrap();
+        drop(conn);
+        pool
+    }
+
+    fn page(limit: u32, offset: u32) -> MarkerFilters {
+        MarkerFilters {
+            limit: Some(limit),
+            offset: Some(offset),
+            ..MarkerFilters::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn global_marker_pages_neither_skip_nor_repeat() {
+        let pool = marker_corpus().await;
+
+        let everything = find_markers(&pool, &page(100, 0)).await.unwrap();
+        assert_eq!(everything.len(), 12);
+        let confidences: Vec<f64> = everything.iter().map(|(_, marker)| marker.confidence).collect();
+        assert!(confidences.windows(2).all(|pair| pair[0] >= pair[1]));
+
+        let mut paged = Vec::new();
+        for offset in (0..15).step_by(5) {
+            let chunk = find_markers(&pool, &page(5, offset)).await.unwrap();
+            assert!(chunk.len() <= 5);
+            paged.extend(chunk);
+        }
+        let ids = |rows: &[(String, MarkerEvent)]| -> Vec<(String, String)> {
+            rows.iter().map(|(session_id, marker)| (session_id.clone(), marker.id.clone())).collect()
+        };
+        assert_eq!(ids(&paged), ids(&everything));
+        assert!(find_markers(&pool, &page(5, 12)).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn global_marker_filters_combine() {
+        let pool = marker_corpus().await;
+        let filters = MarkerFilters {
+            marker_types: vec!["ATO".to_string()],
+            min_confidence: Some(0.6),
+            speaker: Some("SPEAKER_00".to_string()),
+            ..MarkerFilters::default()
+        };
+
+        let found = find_markers(&pool, &filters).await.unwrap();
+
+        // SPEAKER_00's ATO markers are m00 (0.9), m02 (0.5) and m04 (0.7)
+        let ids: Vec<(&str, &str)> = found
+            .iter()
+            .map(|(session_id, marker)| (session_id.as_str(), marker.id.as_str()))
+            .collect();
+        assert_eq!(ids, [("s2", "m00"), ("s2", "m04")]);
+
+        let by_text = MarkerFilters {
+            text: Some("m1".to_string()),
+            max_confidence: Some(0.8),
+            ..MarkerFilters::default()
+        };
+        let found = find_markers(&pool, &by_text).await.unwrap();
+        let ids: Vec<&str> = found.iter().map(|(_, marker)| marker.id.as_str()).collect();
+        assert_eq!(ids, ["m10", "m11"]);
+    }
+
+    #[tokio::test]
+    async fn global_marker_limit_is_bounded() {
+        let pool = marker_corpus().await;
+        for limit in [0, MAX_MARKER_QUERY_LIMIT + 1] {
+            assert!(matches!(find_markers(&pool, &page(limit, 0)).await, Err(AppError::Validation(_))));
+        }
+        let default_page = find_markers(&pool, &MarkerFilters::default()).await.unwrap();
+        assert_eq!(default_page.len(), 12);
+    }
+
+    fn transition_fixture() -> Vec<MarkerEvent> {
+        // Out of time order on purpose; CLU and the last SEM start together
+        vec![
+            marker("f", MarkerType::Sem, 4.0, 5.0, 0.8, None),
+            marker("c", MarkerType::Ato, 2.0, 2.5, 0.8, None),
+            marker("a", MarkerType::Ato, 0.0, 0.5, 0.8, None),
+            marker("e", MarkerType::Clu, 4.0, 4.5, 0.8, None),
+            marker("b", MarkerType::Sem, 1.0, 1.5, 0.8, None),
+            marker("d", MarkerType::Ato, 3.0, 3.5, 0.8, None),
+        ]
+    }
+
+    #[test]
+    fn transitions_follow_time_order() {
+        let matrix = transition_matrix(&transition_fixture());
+
+        // ATO → SEM → ATO → ATO → CLU → SEM
+        assert_eq!(matrix.marker_types, ["ATO", "CLU", "SEM"]);
+        assert_eq!(matrix.counts, [vec![1, 1, 1], vec![0, 0, 1], vec![1, 0, 0]]);
+        assert_eq!(matrix.total_transitions, 5);
+        let third = 1.0 / 3.0;
+        assert_eq!(
+            matrix.probabilities,
+            [vec![third, third, third], vec![0.0, 0.0, 1.0], vec![1.0, 0.0, 0.0]]
+        );
+    }
+
+    #[test]
+    fn a_single_marker_has_no_transitions() {
+        let matrix = transition_matrix(&[marker("a", MarkerType::Mema, 0.0, 1.0, 0.8, None)]);
+        assert_eq!(matrix.marker_types, ["MEMA"]);
+        assert_eq!(matrix.counts, [vec![0]]);
+        assert_eq!(matrix.probabilities, [vec![0.0]]);
+        assert_eq!(matrix.total_transitions, 0);
+
+        let empty = transition_matrix(&[]);
+        assert!(empty.marker_types.is_empty() && empty.counts.is_empty());
+    }
+
+    #[test]
+    fn rapport_reports_only_windows_with_markers() {
+        let markers = vec![
+            marker("m1", MarkerType::Ato, 5.0, 6.0, 1.0, None),
+            marker("m2", MarkerType::Ato, 20.0, 21.0, 0.5, None),
+            marker("far", MarkerType::Sem, 1e12, 1e12 + 1.0, 1.0, None),
+        ];
+        let curve = rapport_indicators(&markers, &RapportWeights::default(), RAPPORT_WINDOW_SECS);
+
+        assert_eq!(curve.len(), 2);
+        assert_eq!(curve[0].timestamp, 0.5 * RAPPORT_WINDOW_SECS);
+        assert_eq!(curve[0].contributing_markers, vec!["m1", "m2"]);
+        assert_eq!(curve[1].contributing_markers, vec!["far"]);
+        assert!(curve[1].timestamp > 1e12);
+        assert_eq!(curve[1].trend, "increasing");
+    }
+}
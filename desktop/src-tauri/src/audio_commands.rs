@@ -1,74 +1,1401 @@
-use tauri::State;
+use tauri::{AppHandle, Emitter, Manager, State};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeSet, HashMap};
+use std::io::Read;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::Duration;
+use futures::StreamExt;
 
-#[derive(Debug, Serialize, Deserialize)]
+use crate::error::AppError;
+use crate::state::AppState;
+
+/// Extensions picked up when importing a whole directory
+const AUDIO_EXTENSIONS: &[&str] = &["wav", "mp3", "m4a", "flac", "ogg", "opus", "aac"];
+
+/// Files imported concurrently when no parallelism is requested
+const DEFAULT_IMPORT_PARALLELISM: usize = 4;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AudioDevice {
     pub id: String,
     pub name: String,
     pub is_default: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DirectoryImportEntry {
+    pub file_path: String,
+    pub success: bool,
+    pub message: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RecordingSession {
     pub id: String,
     pub is_recording: bool,
     pub duration: f64,
     pub file_path: Option<String>,
+    /// One mono file per input channel, channel 1 first, when the recording was split
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_files: Option<Vec<String>>,
 }
 
-#[tauri::command]
-pub async fn start_recording(device_id: Option<String>) -> Result<RecordingSession, String> {
-    // TODO: Implement audio recording start
-    log::info!("Starting audio recording with device: {:?}", device_id);
-    
-    Ok(RecordingSession {
-        id: uuid::Uuid::new_v4().to_string(),
-        is_recording: true,
-        duration: 0.0,
-        file_path: None,
+/// Hex-encoded SHA-256 of a file, read in chunks so large recordings stay out of memory
+pub fn file_sha256(path: &std::path::Path) -> std::io::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Minimum spacing of `audio-level` events, about 20 per second
+const AUDIO_LEVEL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Payload of the `audio-level` event emitted while a recording runs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AudioLevelEvent {
+    pub session_id: String,
+    pub rms: f32,
+    pub peak: f32,
+}
+
+/// Throttled `audio-level` emitter owned by the capture callback, so it is dropped with the stream
+struct LevelEmitter {
+    app: AppHandle,
+    session_id: String,
+    last_emit: Option<std::time::Instant>,
+}
+
+impl LevelEmitter {
+    fn emit(&mut self, level: crate::recording_sink::RecordingLevel) {
+        let now = std::time::Instant::now();
+        if self.last_emit.is_some_and(|last| now.duration_since(last) < AUDIO_LEVEL_INTERVAL) {
+            return;
+        }
+        self.last_emit = Some(now);
+
+        let event = AudioLevelEvent {
+            session_id: self.session_id.clone(),
+            rms: level.rms,
+            peak: level.peak,
+        };
+        if let Err(e) = self.app.emit("audio-level", &event) {
+            log::warn!("Failed to emit audio-level: {}", e);
+        }
+    }
+}
+
+/// Wall-clock and written-audio durations further apart than this suggest dropped buffers
+const DURATION_DRIFT_WARNING_SECS: f64 = 1.0;
+
+/// How often a capture thread checks whether a capped recording reached its limit
+const DURATION_LIMIT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A capture running on its own thread; cpal streams aren't `Send`, so the stream and
+/// its sink live there until `stop_recording` signals the thread to finalize them
+pub struct CaptureHandle {
+    stop_tx: mpsc::Sender<()>,
+    thread: std::thread::JoinHandle<Result<f64, String>>,
+}
+
+pub struct RecordingState {
+    /// Start of the current unpaused stretch; `None` while paused
+    active_since: Option<std::time::Instant>,
+    /// Active time accumulated before the last pause
+    active_before: Duration,
+    /// Shared with the capture callback, which drops samples while set
+    paused: Arc<AtomicBool>,
+    pub file_path: PathBuf,
+    /// Per-channel files of a split recording; `file_path` is the first of them
+    pub channel_files: Vec<PathBuf>,
+    /// `None` once the recording has been stopped and its file finalized
+    capture: Option<CaptureHandle>,
+}
+
+impl RecordingState {
+    /// Recorded wall-clock time, excluding paused intervals
+    pub fn active_duration(&self) -> Duration {
+        self.active_before + self.active_since.map(|since| since.elapsed()).unwrap_or_default()
+    }
+
+    fn session(&self, session_id: &str) -> RecordingSession {
+        RecordingSession {
+            id: session_id.to_string(),
+            is_recording: self.capture.is_some() && self.active_since.is_some(),
+            duration: self.active_duration().as_secs_f64(),
+            file_path: Some(self.file_path.to_string_lossy().to_string()),
+            channel_files: (!self.channel_files.is_empty()).then(|| {
+                self.channel_files
+                    .iter()
+                    .map(|path| path.to_string_lossy().to_string())
+                    .collect()
+            }),
+        }
+    }
+}
+
+/// Recordings started in this run, keyed by session id; held in `AppState`
+pub type Recordings = Mutex<HashMap<String, RecordingState>>;
+
+/// Resolve a device id from `get_audio_devices`, or the default input device for `None`
+fn find_input_device(device_id: Option<&str>) -> Result<cpal::Device, String> {
+    use cpal::traits::HostTrait;
+
+    match device_id {
+        Some(id) => input_devices_with_ids()?
+            .into_iter()
+            .find(|(candidate, _)| candidate == id)
+            .map(|(_, device)| device)
+            .ok_or_else(|| format!("Input device not found: {}", id)),
+        None => cpal::default_host()
+            .default_input_device()
+            .ok_or_else(|| "No default input device available".to_string()),
+    }
+}
+
+fn build_capture_stream<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    sink: Arc<Mutex<crate::recording_sink::MeteredSink>>,
+    failure: Arc<Mutex<Option<String>>>,
+    paused: Arc<AtomicBool>,
+    mut levels: LevelEmitter
+) -> Result<cpal::Stream, String>
+where
+    T: cpal::SizedSample,
+    f32: cpal::FromSample<T>,
+{
+    use cpal::traits::DeviceTrait;
+
+    device
+        .build_input_stream(
+            config,
+            move |data: &[T], _: &cpal::InputCallbackInfo| {
+                // Paused audio is discarded; the file simply continues after resume
+                if paused.load(Ordering::Relaxed) {
+                    return;
+                }
+                let mut failed = failure.lock().unwrap();
+                // After the first write error the file is unusable; drop further audio
+                if failed.is_some() {
+                    return;
+                }
+                let samples: Vec<f32> = data.iter().map(|sample| sample.to_sample::<f32>()).collect();
+                let mut sink = sink.lock().unwrap();
+                match sink.write_samples(&samples) {
+                    Ok(()) => levels.emit(sink.level()),
+                    Err(e) => {
+                        log::error!("Failed to write captured audio: {}", e);
+                        *failed = Some(e);
+                    }
+                }
+            },
+            |e| log::error!("Audio capture stream error: {}", e),
+            None,
+        )
+        .map_err(|e| format!("Failed to open input stream: {}", e))
+}
+
+/// Input configuration with `channels`, or the device's native one for `None`.
+/// A configuration at the device's default sample rate is preferred.
+fn select_input_config(device: &cpal::Device, channels: Option<u16>) -> Result<cpal::SupportedStreamConfig, String> {
+    use cpal::traits::DeviceTrait;
+
+    let default = device
+        .default_input_config()
+        .map_err(|e| format!("Failed to query input configuration: {}", e))?;
+    let Some(channels) = channels.filter(|channels| *channels != default.channels()) else {
+        return Ok(default);
+    };
+
+    let ranges: Vec<cpal::SupportedStreamConfigRange> = device
+        .supported_input_configs()
+        .map_err(|e| format!("Failed to query input configurations: {}", e))?
+        .collect();
+    let default_rate = default.sample_rate();
+    let matching: Vec<&cpal::SupportedStreamConfigRange> =
+        ranges.iter().filter(|range| range.channels() == channels).collect();
+    if let Some(range) = matching
+        .iter()
+        .find(|range| range.min_sample_rate() <= default_rate && default_rate <= range.max_sample_rate())
+    {
+        return Ok((*range).clone().with_sample_rate(default_rate));
+    }
+    if let Some(range) = matching.first() {
+        return Ok((*range).clone().with_max_sample_rate());
+    }
+
+    let supported: BTreeSet<u16> = ranges.iter().map(|range| range.channels()).collect();
+    Err(format!(
+        "Input device does not support {} channels (supported: {})",
+        channels,
+        supported.iter().map(|count| count.to_string()).collect::<Vec<_>>().join(", ")
+    ))
+}
+
+/// `<stem>_ch<n>.<ext>` next to `path` for each of `channels`, channel 1 first
+fn channel_file_paths(path: &std::path::Path, channels: u16) -> Vec<PathBuf> {
+    let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("recording");
+    let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("wav");
+    (1..=channels)
+        .map(|channel| path.with_file_name(format!("{}_ch{}.{}", stem, channel, extension)))
+        .collect()
+}
+
+/// File encoding and channel layout a recording was asked for
+#[derive(Debug, Clone)]
+struct CaptureFormat {
+    encoding: String,
+    /// Channels to capture; `None` uses the device's native count
+    channels: Option<u16>,
+    /// Write one mono file per channel instead of a single interleaved file
+    split: bool,
+    /// Audio after which the recording stops itself; `None` records until stopped
+    max_duration_secs: Option<f64>,
+    /// Where chunks for live captions go, when the recording has them
+    live: Option<crate::live_transcription::LiveFeed>,
+}
+
+/// Channels between a capture thread and the recording commands
+struct CaptureControl {
+    /// Reports the files being written, or why the capture could not start
+    ready: mpsc::SyncSender<Result<Vec<PathBuf>, String>>,
+    stop: mpsc::Receiver<()>,
+    /// Fired when the capture stopped itself at the maximum duration
+    limit_reached: tokio::sync::oneshot::Sender<()>,
+}
+
+type OpenCapture = (
+    cpal::Stream,
+    Arc<Mutex<crate::recording_sink::MeteredSink>>,
+    Arc<Mutex<Option<String>>>,
+    Vec<PathBuf>,
+);
+
+fn open_capture(
+    device_id: Option<&str>,
+    path: &std::path::Path,
+    format: &CaptureFormat,
+    paused: Arc<AtomicBool>,
+    levels: LevelEmitter
+) -> Result<OpenCapture, String> {
+    use cpal::traits::StreamTrait;
+
+    let device = find_input_device(device_id)?;
+    let supported = select_input_config(&device, format.channels)?;
+    let config = supported.config();
+
+    // Record at the device's native rate; conversion happens later
+    let (inner, files): (Box<dyn crate::recording_sink::RecordingSink>, Vec<PathBuf>) = if format.split {
+        if config.channels < 2 {
+            return Err("Splitting channels needs a multi-channel recording".to_string());
+        }
+        let files = channel_file_paths(path, config.channels);
+        let sink = crate::recording_sink::ChannelSplitSink::create(&files, &format.encoding, config.sample_rate.0)?;
+        (Box::new(sink), files)
+    } else {
+        let sink = crate::recording_sink::create_sink(path, &format.encoding, config.channels, config.sample_rate.0)?;
+        (sink, vec![path.to_path_buf()])
+    };
+    let inner: Box<dyn crate::recording_sink::RecordingSink> = match &format.live {
+        Some(feed) => Box::new(crate::recording_sink::LiveChunkTap::new(
+            inner,
+            feed.chunk_prefix.clone(),
+            crate::live_transcription::LIVE_WINDOW_SECS,
+            config.channels,
+            config.sample_rate.0,
+            feed.chunks.clone(),
+        )),
+        None => inner,
+    };
+    let remove_files = |files: &[PathBuf]| {
+        for file in files {
+            let _ = std::fs::remove_file(file);
+        }
+    };
+    let sink = Arc::new(Mutex::new(
+        crate::recording_sink::MeteredSink::new(inner, config.channels, config.sample_rate.0)
+            .with_max_duration(format.max_duration_secs),
+    ));
+    let failure = Arc::new(Mutex::new(None));
+
+    let stream = match supported.sample_format() {
+        cpal::SampleFormat::F32 => build_capture_stream::<f32>(&device, &config, sink.clone(), failure.clone(), paused, levels),
+        cpal::SampleFormat::I16 => build_capture_stream::<i16>(&device, &config, sink.clone(), failure.clone(), paused, levels),
+        cpal::SampleFormat::U16 => build_capture_stream::<u16>(&device, &config, sink.clone(), failure.clone(), paused, levels),
+        cpal::SampleFormat::I32 => build_capture_stream::<i32>(&device, &config, sink.clone(), failure.clone(), paused, levels),
+        other => Err(format!("Unsupported input sample format: {}", other)),
+    }
+    .and_then(|stream| {
+        stream
+            .play()
+            .map_err(|e| format!("Failed to start input stream: {}", e))?;
+        Ok(stream)
     })
+    .inspect_err(|_| remove_files(&files))?;
+
+    Ok((stream, sink, failure, files))
+}
+
+/// Body of the capture thread: report the files being written, record until
+/// stopped or until the maximum duration is written, then finalize them and
+/// return the recorded audio duration
+fn run_capture(
+    device_id: Option<String>,
+    path: PathBuf,
+    format: CaptureFormat,
+    paused: Arc<AtomicBool>,
+    levels: LevelEmitter,
+    control: CaptureControl
+) -> Result<f64, String> {
+    let opened = open_capture(device_id.as_deref(), &path, &format, paused, levels);
+    let (stream, sink, failure, files) = match opened {
+        Ok(capture) => capture,
+        Err(e) => {
+            let _ = control.ready.send(Err(e.clone()));
+            let _ = std::fs::remove_file(&path);
+            return Err(e);
+        }
+    };
+    let _ = control.ready.send(Ok(files));
+
+    // A dropped sender means the app is going away; finalize either way
+    loop {
+        match control.stop.recv_timeout(DURATION_LIMIT_POLL_INTERVAL) {
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                if sink.lock().unwrap().limit_reached() {
+                    let _ = control.limit_reached.send(());
+                    break;
+                }
+            }
+            _ => break,
+        }
+    }
+    drop(stream);
+
+    let sink = Arc::try_unwrap(sink)
+        .map_err(|_| "Capture stream still holds the recording".to_string())?
+        .into_inner()
+        .unwrap();
+    let duration = sink.finalize()?;
+
+    let write_error = failure.lock().unwrap().take();
+    match write_error {
+        Some(e) => Err(format!("Recording was interrupted by a write error: {}", e)),
+        None => Ok(duration),
+    }
+}
+
+/// Signal a capture thread to stop and wait for it to flush and finalize its file.
+/// Returns the duration of audio actually written.
+fn finish_capture(capture: CaptureHandle) -> Result<f64, String> {
+    let _ = capture.stop_tx.send(());
+    capture
+        .thread
+        .join()
+        .map_err(|_| "Recording thread panicked".to_string())?
+}
+
+/// Whether any recording still has a capture running, paused or not
+pub fn recording_in_progress(recordings: &Recordings) -> bool {
+    recordings.lock().unwrap().values().any(|recording| recording.capture.is_some())
+}
+
+/// Finalize every active recording so files stay readable when the app exits
+pub fn finalize_all_recordings(recordings: &Recordings) {
+    let active: Vec<(String, CaptureHandle)> = recordings
+        .lock()
+        .unwrap()
+        .iter_mut()
+        .filter_map(|(session_id, recording)| Some((session_id.clone(), recording.capture.take()?)))
+        .collect();
+    for (session_id, capture) in active {
+        if let Err(e) = finish_capture(capture) {
+            log::error!("Failed to finalize recording {}: {}", session_id, e);
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn start_recording(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    settings: State<'_, crate::settings::SettingsStore>,
+    device_id: Option<String>,
+    encoding: Option<String>, // "wav" (default) or "flac", encoded while capturing
+    channels: Option<u16>, // device native when unset
+    split_channels: Option<bool>, // one mono file per channel, e.g. per lapel mic
+    live_transcription: Option<bool> // emit best-effort `live-transcript` captions while recording
+) -> Result<RecordingSession, AppError> {
+    let encoding = encoding.unwrap_or_else(|| "wav".to_string());
+    log::info!("Starting audio recording with device: {:?} as {}", device_id, encoding);
+    
+    // Reject unknown encodings and channel layouts before any device is opened
+    let extension = crate::recording_sink::recording_extension(&encoding).map_err(AppError::Validation)?;
+    let split = split_channels.unwrap_or(false);
+    match channels {
+        Some(0) => return Err(AppError::Validation("channels must be at least 1".to_string())),
+        Some(1) if split => {
+            return Err(AppError::Validation("Splitting channels needs more than one channel".to_string()))
+        }
+        _ => {}
+    }
+    let settings = settings.get();
+    let live_transcription = live_transcription.unwrap_or(false);
+    if live_transcription && !settings.live_transcription {
+        return Err(AppError::Validation(
+            "Live transcription is disabled; enable the live_transcription setting first".to_string(),
+        ));
+    }
+    
+    let recordings_dir = app.state::<crate::paths::AppPaths>().recordings();
+    
+    let session_id = uuid::Uuid::new_v4().to_string();
+    let format = CaptureFormat {
+        encoding,
+        channels,
+        split,
+        max_duration_secs: settings.max_duration_secs.map(|secs| secs as f64),
+        live: live_transcription.then(|| crate::live_transcription::start(app.clone(), &session_id)),
+    };
+    let file_path = recordings_dir.join(format!("{}.{}", session_id, extension));
+    
+    let (ready_tx, ready_rx) = mpsc::sync_channel(1);
+    let (stop_tx, stop_rx) = mpsc::channel();
+    let (limit_tx, limit_rx) = tokio::sync::oneshot::channel();
+    let control = CaptureControl {
+        ready: ready_tx,
+        stop: stop_rx,
+        limit_reached: limit_tx,
+    };
+    let paused = Arc::new(AtomicBool::new(false));
+    let capture_path = file_path.clone();
+    let capture_paused = paused.clone();
+    let levels = LevelEmitter {
+        app: app.clone(),
+        session_id: session_id.clone(),
+        last_emit: None,
+    };
+    let thread = std::thread::Builder::new()
+        .name(format!("recording-{}", session_id))
+        .spawn(move || run_capture(device_id, capture_path, format, capture_paused, levels, control))
+        .map_err(|e| AppError::Internal(format!("Failed to start recording thread: {}", e)))?;
+    
+    let started = tauri::async_runtime::spawn_blocking(move || ready_rx.recv())
+        .await
+        .map_err(|e| AppError::Internal(format!("Recording startup task failed: {}", e)))?;
+    let files = match started {
+        Ok(Ok(files)) => files,
+        Ok(Err(e)) => return Err(AppError::Audio(e)),
+        Err(_) => {
+            // The thread exited without reporting; surface its own error if it has one
+            return Err(AppError::Audio(match thread.join() {
+                Ok(Err(e)) => e,
+                _ => "Recording thread exited during startup".to_string(),
+            }));
+        }
+    };
+    
+    let recording = RecordingState {
+        active_since: Some(std::time::Instant::now()),
+        active_before: Duration::ZERO,
+        paused,
+        file_path: if split { files[0].clone() } else { file_path },
+        channel_files: if split { files } else { Vec::new() },
+        capture: Some(CaptureHandle { stop_tx, thread }),
+    };
+    log::info!("Recording session {} writing to: {}", session_id, recording.file_path.display());
+    
+    let session = recording.session(&session_id);
+    state.recordings.lock().unwrap().insert(session_id.clone(), recording);
+    
+    // The sender is dropped without firing when the recording is stopped first
+    tauri::async_runtime::spawn(async move {
+        if limit_rx.await.is_ok() {
+            auto_stop_recording(&app, &session_id).await;
+        }
+    });
+    Ok(session)
+}
+
+/// Finish a recording whose capture stopped itself at `max_duration_secs`
+async fn auto_stop_recording(app: &AppHandle, session_id: &str) {
+    let state = app.state::<AppState>();
+    match end_recording(&state.recordings, session_id, true).await {
+        Ok(session) => {
+            log::info!("Recording {} reached its maximum duration after {:.1}s", session_id, session.duration);
+            if let Err(e) = app.emit("recording-auto-stopped", &session) {
+                log::warn!("Failed to emit recording-auto-stopped: {}", e);
+            }
+        }
+        // Stopped by the user in the meantime
+        Err(AppError::Validation(_)) => {}
+        Err(e) => log::error!("Failed to auto-stop recording {}: {}", session_id, e),
+    }
+}
+
+/// Look up a recording that hasn't been stopped yet
+fn running_recording<'a>(
+    recordings: &'a mut HashMap<String, RecordingState>,
+    session_id: &str
+) -> Result<&'a mut RecordingState, AppError> {
+    let recording = recordings
+        .get_mut(session_id)
+        .ok_or_else(|| AppError::NotFound(format!("Unknown recording session: {}", session_id)))?;
+    if recording.capture.is_none() {
+        return Err(AppError::Validation(format!("Recording session already stopped: {}", session_id)));
+    }
+    Ok(recording)
+}
+
+#[tauri::command]
+pub async fn pause_recording(
+    state: State<'_, AppState>,
+    session_id: String
+) -> Result<RecordingSession, AppError> {
+    log::info!("Pausing audio recording session: {}", session_id);
+    
+    let mut recordings = state.recordings.lock().unwrap();
+    let recording = running_recording(&mut recordings, &session_id)?;
+    let since = recording
+        .active_since
+        .take()
+        .ok_or_else(|| AppError::Validation(format!("Recording session already paused: {}", session_id)))?;
+    recording.paused.store(true, Ordering::Relaxed);
+    recording.active_before += since.elapsed();
+    
+    Ok(recording.session(&session_id))
+}
+
+#[tauri::command]
+pub async fn resume_recording(
+    state: State<'_, AppState>,
+    session_id: String
+) -> Result<RecordingSession, AppError> {
+    log::info!("Resuming audio recording session: {}", session_id);
+    
+    let mut recordings = state.recordings.lock().unwrap();
+    let recording = running_recording(&mut recordings, &session_id)?;
+    if recording.active_since.is_some() {
+        return Err(AppError::Validation(format!("Recording session is not paused: {}", session_id)));
+    }
+    recording.active_since = Some(std::time::Instant::now());
+    recording.paused.store(false, Ordering::Relaxed);
+    
+    Ok(recording.session(&session_id))
 }
 
 #[tauri::command]
-pub async fn stop_recording(session_id: String) -> Result<RecordingSession, String> {
-    // TODO: Implement audio recording stop
+pub async fn stop_recording(
+    state: State<'_, AppState>,
+    session_id: String
+) -> Result<RecordingSession, AppError> {
     log::info!("Stopping audio recording session: {}", session_id);
+    end_recording(&state.recordings, &session_id, false).await
+}
+
+/// Stop a running recording and finalize its files. A recording that stopped
+/// itself at its limit takes its duration from the audio written, since the
+/// wall clock ran on until the stop was noticed.
+async fn end_recording(
+    recordings: &Recordings,
+    session_id: &str,
+    auto_stopped: bool
+) -> Result<RecordingSession, AppError> {
+    // Take the capture under the lock so a concurrent stop sees the session as stopped
+    let (capture, duration) = {
+        let mut recordings = recordings.lock().unwrap();
+        let recording = running_recording(&mut recordings, session_id)?;
+        if let Some(since) = recording.active_since.take() {
+            recording.active_before += since.elapsed();
+        }
+        let capture = recording.capture.take().expect("running recording has a capture");
+        (capture, recording.active_duration().as_secs_f64())
+    };
     
-    Ok(RecordingSession {
-        id: session_id,
-        is_recording: false,
-        duration: 120.0, // Mock duration
-        file_path: Some("/tmp/recording.wav".to_string()),
-    })
+    let written = tauri::async_runtime::spawn_blocking(move || finish_capture(capture))
+        .await
+        .map_err(|e| AppError::Internal(format!("Recording finalize task failed: {}", e)))?
+        .map_err(AppError::Audio)?;
+    if !auto_stopped && (duration - written).abs() > DURATION_DRIFT_WARNING_SECS {
+        log::warn!(
+            "Recording {} ran {:.1}s but only {:.1}s of audio were written",
+            session_id, duration, written
+        );
+    }
+    
+    let mut recordings = recordings.lock().unwrap();
+    let recording = recordings
+        .get_mut(session_id)
+        .ok_or_else(|| AppError::NotFound(format!("Unknown recording session: {}", session_id)))?;
+    if auto_stopped {
+        recording.active_before = Duration::from_secs_f64(written);
+    }
+    Ok(recording.session(session_id))
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AudioFileInfo {
+    pub path: String,
+    /// 16 kHz mono WAV used for transcription; the original when it already qualifies
+    pub normalized_path: String,
+    pub format: String, // "wav", "flac", "ogg", "mp3", "aac", "m4a"
+    pub codec: String, // e.g. "pcm_s16le", "mp3", "aac"
+    /// Properties of the original file
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub duration_secs: f64,
+    /// Hex-encoded SHA-256 of the original file's bytes
+    pub sha256: String,
+}
+
+/// Import an audio file. With `session_id`, the file becomes that session's
+/// audio and its checksum is recorded for `verify_audio_integrity`.
 #[tauri::command]
-pub async fn import_audio_file(file_path: String) -> Result<String, String> {
-    // TODO: Implement audio file import validation
+pub async fn import_audio_file(
+    state: State<'_, AppState>,
+    settings: State<'_, crate::settings::SettingsStore>,
+    file_path: String,
+    session_id: Option<String>
+) -> Result<AudioFileInfo, AppError> {
     log::info!("Importing audio file: {}", file_path);
     
-    if !std::path::Path::new(&file_path).exists() {
+    let max_duration_secs = settings.get().max_import_duration_minutes as f64 * 60.0;
+    let info = tauri::async_runtime::spawn_blocking(move || {
+        import_audio(std::path::Path::new(&file_path), max_duration_secs)
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Import task failed: {}", e)))?
+    .map_err(AppError::Audio)?;
+    
+    if let Some(session_id) = session_id {
+        let updated = sqlx::query(
+            "UPDATE conversation_sessions SET file_path = ?, audio_sha256 = ?, duration = ?, updated_at = ? WHERE id = ?",
+        )
+        .bind(&info.path)
+        .bind(&info.sha256)
+        .bind(info.duration_secs)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .bind(&session_id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to attach audio to session: {}", e)))?;
+        if updated.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Session not found: {}", session_id)));
+        }
+        log::info!("Recorded SHA-256 {} for session {}", info.sha256, session_id);
+    }
+    
+    Ok(info)
+}
+
+fn check_import_duration(duration_secs: f64, max_duration_secs: f64) -> Result<(), String> {
+    if duration_secs > max_duration_secs {
+        return Err(format!(
+            "Audio is {:.1} hours long; imports are limited to {:.1} hours",
+            duration_secs / 3600.0,
+            max_duration_secs / 3600.0
+        ));
+    }
+    Ok(())
+}
+
+/// Validate and import a single audio file, writing a 16 kHz mono WAV copy next to it
+/// unless it already is one; blocking, so run it off the async runtime
+pub fn import_audio(path: &std::path::Path, max_duration_secs: f64) -> Result<AudioFileInfo, String> {
+    use crate::audio_processing::TRANSCRIPTION_SAMPLE_RATE;
+
+    if !path.exists() {
         return Err("File does not exist".to_string());
     }
+    let size = std::fs::metadata(path)
+        .map_err(|e| format!("Failed to read file metadata: {}", e))?
+        .len();
+    if size == 0 {
+        return Err("File is empty".to_string());
+    }
+    
+    // Hash the bytes as received, before anything reads them as audio
+    let sha256 = file_sha256(path).map_err(|e| format!("Failed to hash file: {}", e))?;
+    let format = crate::audio_processing::detect_audio_format(path)?;
+    
+    // Header-declared length rejects absurd files before anything is decoded
+    let probe = crate::audio_processing::probe_audio(path, Some(format))?;
+    if let Some(duration) = probe.duration_secs {
+        check_import_duration(duration, max_duration_secs)?;
+    }
+    
+    // A PCM WAV that already matches the transcription format is used as is
+    if format == "wav" {
+        if let Ok(reader) = hound::WavReader::open(path) {
+            let spec = reader.spec();
+            if spec.sample_rate == TRANSCRIPTION_SAMPLE_RATE
+                && spec.channels == 1
+                && spec.sample_format == hound::SampleFormat::Int
+                && spec.bits_per_sample == 16
+            {
+                return Ok(AudioFileInfo {
+                    path: path.to_string_lossy().to_string(),
+                    normalized_path: path.to_string_lossy().to_string(),
+                    format: format.to_string(),
+                    codec: probe.codec,
+                    sample_rate: spec.sample_rate,
+                    channels: spec.channels,
+                    duration_secs: reader.duration() as f64 / spec.sample_rate as f64,
+                    sha256,
+                });
+            }
+        }
+    }
     
-    Ok("File imported successfully".to_string())
+    let audio = crate::audio_processing::decode_audio(path, Some(format))?;
+    // Containers without a declared length are checked once decoded
+    check_import_duration(audio.duration_secs(), max_duration_secs)?;
+    let mono = crate::audio_processing::downmix_mono(&audio.samples, audio.spec.channels);
+    let normalized = crate::audio_processing::resample_linear(
+        &mono,
+        1,
+        audio.spec.sample_rate,
+        TRANSCRIPTION_SAMPLE_RATE,
+    );
+    
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("audio");
+    let normalized_path = path.with_file_name(format!("{}_16k.wav", stem));
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: TRANSCRIPTION_SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    crate::audio_processing::write_wav(&normalized_path, spec, &normalized)?;
+    log::info!("Normalized {} to {}", path.display(), normalized_path.display());
+    
+    Ok(AudioFileInfo {
+        path: path.to_string_lossy().to_string(),
+        normalized_path: normalized_path.to_string_lossy().to_string(),
+        format: format.to_string(),
+        codec: probe.codec,
+        sample_rate: audio.spec.sample_rate,
+        channels: audio.spec.channels,
+        duration_secs: audio.duration_secs(),
+        sha256,
+    })
+}
+
+/// Upper bound for concurrent imports: one file per core keeps decode buffers bounded
+fn import_parallelism(requested: Option<usize>) -> usize {
+    let cores = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    requested
+        .unwrap_or(DEFAULT_IMPORT_PARALLELISM)
+        .clamp(1, cores.max(1))
 }
 
 #[tauri::command]
-pub async fn get_audio_devices() -> Result<Vec<AudioDevice>, String> {
-    // TODO: Implement audio device enumeration
+pub async fn import_audio_directory(
+    settings: State<'_, crate::settings::SettingsStore>,
+    dir_path: String,
+    parallelism: Option<usize>
+) -> Result<Vec<DirectoryImportEntry>, AppError> {
+    let parallelism = import_parallelism(parallelism);
+    let max_duration_secs = settings.get().max_import_duration_minutes as f64 * 60.0;
+    log::info!("Importing audio directory: {} with parallelism: {}", dir_path, parallelism);
+    
+    import_directory(std::path::Path::new(&dir_path), parallelism, max_duration_secs).await
+}
+
+/// Import every audio file in `dir`, up to `parallelism` at once, reporting each
+/// file in file name order
+async fn import_directory(
+    dir: &std::path::Path,
+    parallelism: usize,
+    max_duration_secs: f64
+) -> Result<Vec<DirectoryImportEntry>, AppError> {
+    let entries = std::fs::read_dir(dir)
+        .map_err(|e| AppError::Io(format!("Failed to read directory: {}", e)))?;
+    
+    let mut files: Vec<std::path::PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .map(|ext| AUDIO_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+                .unwrap_or(false)
+        })
+        .collect();
+    // Results come back in this order regardless of which import finishes first
+    files.sort_by(|a, b| a.file_name().cmp(&b.file_name()));
+    
+    let results = futures::stream::iter(files)
+        .map(|path| async move {
+            let file_path = path.to_string_lossy().to_string();
+            let outcome = tauri::async_runtime::spawn_blocking(move || import_audio(&path, max_duration_secs))
+                .await
+                .map_err(|e| format!("Import task failed: {}", e))
+                .and_then(|result| result);
+            
+            // A failing file is reported in place instead of aborting the batch
+            match outcome {
+                Ok(info) => DirectoryImportEntry {
+                    file_path,
+                    success: true,
+                    message: format!("Imported as {}", info.normalized_path),
+                },
+                Err(message) => DirectoryImportEntry { file_path, success: false, message },
+            }
+        })
+        .buffered(parallelism)
+        .collect::<Vec<_>>()
+        .await;
+    
+    Ok(results)
+}
+
+#[tauri::command]
+pub async fn get_audio_devices() -> Result<Vec<AudioDevice>, AppError> {
     log::info!("Getting available audio devices");
     
-    Ok(vec![
-        AudioDevice {
-            id: "default".to_string(),
-            name: "Default Audio Device".to_string(),
-            is_default: true,
-        },
+    tauri::async_runtime::spawn_blocking(list_input_devices)
+        .await
+        .map_err(|e| AppError::Internal(format!("Device enumeration task failed: {}", e)))?
+        .map_err(AppError::Audio)
+}
+
+/// Stable device id derived from the device name, so it survives re-enumeration
+/// and reordering; `occurrence` distinguishes identically named devices.
+pub fn device_id_for_name(name: &str, occurrence: usize) -> String {
+    let digest = Sha256::digest(name.as_bytes());
+    let hex: String = digest.iter().take(8).map(|b| format!("{:02x}", b)).collect();
+    if occurrence == 0 {
+        format!("input-{}", hex)
+    } else {
+        format!("input-{}-{}", hex, occurrence + 1)
+    }
+}
+
+/// Input devices of the default host paired with their stable ids
+pub fn input_devices_with_ids() -> Result<Vec<(String, cpal::Device)>, String> {
+    use cpal::traits::{DeviceTrait, HostTrait};
+
+    let host = cpal::default_host();
+    let devices = host
+        .input_devices()
+        .map_err(|e| format!("Failed to initialize audio host {:?}: {}", host.id(), e))?;
+
+    let mut seen: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+    let mut identified = Vec::new();
+    for device in devices {
+        let name = match device.name() {
+            Ok(name) => name,
+            Err(e) => {
+                // A device that can't even report its name can't be selected reliably
+                log::warn!("Skipping input device without a readable name: {}", e);
+                continue;
+            }
+        };
+        let occurrence = seen.entry(name.clone()).or_default();
+        identified.push((device_id_for_name(&name, *occurrence), device));
+        *occurrence += 1;
+    }
+
+    Ok(identified)
+}
+
+/// Enumerate input devices; blocking, so call it off the async runtime
+pub fn list_input_devices() -> Result<Vec<AudioDevice>, String> {
+    use cpal::traits::{DeviceTrait, HostTrait};
+
+    let default_name = cpal::default_host()
+        .default_input_device()
+        .and_then(|device| device.name().ok());
+
+    let mut default_marked = false;
+    input_devices_with_ids().map(|devices| {
+        devices
+            .into_iter()
+            .filter_map(|(id, device)| {
+                let name = device.name().ok()?;
+                // Only the first device carrying the default's name is the default
+                let is_default = !default_marked && default_name.as_deref() == Some(name.as_str());
+                default_marked |= is_default;
+                Some(AudioDevice { id, name, is_default })
+            })
+            .collect()
+    })
+}
+
+/// Whether two device listings differ in membership or default device, ignoring order
+pub fn device_set_changed(previous: &[AudioDevice], current: &[AudioDevice]) -> bool {
+    let key = |device: &AudioDevice| (device.id.clone(), device.name.clone(), device.is_default);
+    let previous: BTreeSet<_> = previous.iter().map(key).collect();
+    let current: BTreeSet<_> = current.iter().map(key).collect();
+    previous != current
+}
+
+/// Background task that emits `audio-devices-changed` with the new listing when
+/// input devices are added or removed or the default input changes. It polls
+/// every `device_poll_interval_ms`, re-read each round so changes apply live.
+pub struct DeviceWatcher {
+    handle: Mutex<Option<tauri::async_runtime::JoinHandle<()>>>,
+}
+
+impl DeviceWatcher {
+    pub fn spawn(app: AppHandle) -> Self {
+        let handle = tauri::async_runtime::spawn(async move {
+            let mut known: Option<Vec<AudioDevice>> = None;
+            let mut first = true;
+
+            loop {
+                if !first {
+                    let interval_ms = app.state::<crate::settings::SettingsStore>().get().device_poll_interval_ms;
+                    tokio::time::sleep(Duration::from_millis(interval_ms)).await;
+                }
+                first = false;
+
+                let devices = match tauri::async_runtime::spawn_blocking(list_input_devices).await {
+                    Ok(Ok(devices)) => devices,
+                    Ok(Err(e)) => {
+                        log::warn!("Device watcher failed to enumerate devices: {}", e);
+                        continue;
+                    }
+                    Err(e) => {
+                        log::warn!("Device watcher task failed: {}", e);
+                        continue;
+                    }
+                };
+
+                // The first listing is the baseline; only later differences are events
+                let changed = known
+                    .as_ref()
+                    .map(|previous| device_set_changed(previous, &devices))
+                    .unwrap_or(false);
+                if changed {
+                    log::info!("Audio input devices changed; {} available", devices.len());
+                    if let Err(e) = app.emit("audio-devices-changed", &devices) {
+                        log::warn!("Failed to emit audio-devices-changed: {}", e);
+                    }
+                }
+                known = Some(devices);
+            }
+        });
+
+        Self {
+            handle: Mutex::new(Some(handle)),
+        }
+    }
+
+    pub fn stop(&self) {
+        if let Some(handle) = self.handle.lock().unwrap().take() {
+            handle.abort();
+            log::info!("Audio device watcher stopped");
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_waveform(file_path: String, bucket_count: usize) -> Result<Vec<f32>, AppError> {
+    log::info!("Computing {} waveform buckets for: {}", bucket_count, file_path);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::audio_processing::waveform_peaks(std::path::Path::new(&file_path), bucket_count)
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Waveform task failed: {}", e)))?
+    .map_err(AppError::Audio)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TrimmedAudio {
+    pub file_path: String,
+    pub duration: f64,
+}
+
+/// First `<stem>_<suffix>[_n].wav` next to `source` that does not exist yet
+fn derived_output_path(source: &std::path::Path, suffix: &str) -> PathBuf {
+    let stem = source.file_stem().and_then(|s| s.to_str()).unwrap_or("audio");
+    let base = format!("{}_{}", stem, suffix);
+
+    let mut candidate = source.with_file_name(format!("{}.wav", base));
+    let mut counter = 2;
+    while candidate.exists() {
+        candidate = source.with_file_name(format!("{}_{}.wav", base, counter));
+        counter += 1;
+    }
+    candidate
+}
+
+/// Write `[start_secs, end_secs)` of a WAV to a new file beside it, keeping the
+/// source's sample rate, channel count and sample format. The source is never touched.
+#[tauri::command]
+pub async fn trim_audio(
+    file_path: String,
+    start_secs: f64,
+    end_secs: f64
+) -> Result<TrimmedAudio, AppError> {
+    log::info!("Trimming {} to {}..{}", file_path, start_secs, end_secs);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let source = std::path::Path::new(&file_path);
+        let audio = crate::audio_processing::read_wav(source)?;
+
+        let duration = audio.duration_secs();
+        if !(0.0 <= start_secs && start_secs < end_secs && end_secs <= duration) {
+            return Err(format!(
+                "Trim range must satisfy 0 <= start < end <= {:.3} seconds",
+                duration
+            ));
+        }
+
+        let channels = audio.spec.channels as usize;
+        let rate = audio.spec.sample_rate as f64;
+        let start_frame = (start_secs * rate).round() as usize;
+        let end_frame = ((end_secs * rate).round() as usize).min(audio.frame_count());
+        let trimmed = &audio.samples[start_frame * channels..end_frame * channels];
+
+        let suffix = format!(
+            "trim_{}_{}",
+            (start_secs * 1000.0).round() as u64,
+            (end_secs * 1000.0).round() as u64
+        );
+        let output_path = derived_output_path(source, &suffix);
+        crate::audio_processing::write_wav(&output_path, audio.spec, trimmed)?;
+
+        Ok(TrimmedAudio {
+            file_path: output_path.to_string_lossy().to_string(),
+            duration: (end_frame - start_frame) as f64 / rate,
+        })
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Trim task failed: {}", e)))?
+    .map_err(AppError::Audio)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NormalizedAudio {
+    pub file_path: String,
+    /// Gain applied to every sample
+    pub gain_db: f64,
+    /// Loudest sample of the normalized copy
+    pub peak_dbfs: f64,
+}
+
+/// Write a copy of a WAV with its RMS level brought to `target_dbfs`, without
+/// clipping, beside the original. Useful before transcribing quiet recordings;
+/// the source is never touched.
+#[tauri::command]
+pub async fn normalize_audio(file_path: String, target_dbfs: f64) -> Result<NormalizedAudio, AppError> {
+    log::info!("Normalizing {} to {} dBFS", file_path, target_dbfs);
+
+    if !target_dbfs.is_finite() || target_dbfs >= 0.0 {
+        return Err(AppError::Validation("target_dbfs must be a negative number".to_string()));
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let source = std::path::Path::new(&file_path);
+        let mut audio = crate::audio_processing::read_wav(source)?;
+        let gain_db = crate::audio_processing::normalization_gain_db(&audio.samples, target_dbfs)
+            .ok_or_else(|| "Audio is silent; there is nothing to normalize".to_string())?;
+
+        let gain = 10f64.powf(gain_db / 20.0) as f32;
+        let mut peak = 0.0f32;
+        for sample in &mut audio.samples {
+            *sample *= gain;
+            peak = peak.max(sample.abs());
+        }
+
+        let output_path = derived_output_path(source, "norm");
+        crate::audio_processing::write_wav(&output_path, audio.spec, &audio.samples)?;
+
+        Ok(NormalizedAudio {
+            file_path: output_path.to_string_lossy().to_string(),
+            gain_db: (gain_db * 100.0).round() / 100.0,
+            peak_dbfs: (crate::audio_processing::amplitude_dbfs(peak as f64) * 100.0).round() / 100.0,
+        })
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Normalize task failed: {}", e)))?
+    .map_err(AppError::Audio)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SilenceRegion {
+    pub start: f64,
+    pub end: f64,
+}
+
+#[tauri::command]
+pub async fn detect_silence(
+    file_path: String,
+    threshold_db: f64,
+    min_silence_ms: u64
+) -> Result<Vec<SilenceRegion>, AppError> {
+    log::info!("Detecting silence below {} dBFS lasting {}ms in: {}", threshold_db, min_silence_ms, file_path);
+
+    if !threshold_db.is_finite() || threshold_db >= 0.0 {
+        return Err(AppError::Validation("threshold_db must be a negative dBFS level".to_string()));
+    }
+
+    let regions = tauri::async_runtime::spawn_blocking(move || {
+        crate::audio_processing::stream_silent_regions(
+            std::path::Path::new(&file_path),
+            threshold_db,
+            min_silence_ms as f64 / 1000.0,
+        )
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Silence detection task failed: {}", e)))?
+    .map_err(AppError::Audio)?;
+
+    Ok(regions.into_iter().map(|(start, end)| SilenceRegion { start, end }).collect())
+}
+
+#[tauri::command]
+pub async fn export_audio_clip(
+    file_path: String,
+    start_secs: f64,
+    end_secs: f64,
+    output_sample_rate: Option<u32>
+) -> Result<String, AppError> {
+    log::info!("Exporting audio clip {}..{} from: {}", start_secs, end_secs, file_path);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        export_clip(std::path::Path::new(&file_path), start_secs, end_secs, output_sample_rate)
+            .map(|output_path| output_path.to_string_lossy().to_string())
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Clip export task failed: {}", e)))?
+    .map_err(AppError::Audio)
+}
+
+/// Write `start_secs..end_secs` of a WAV file next to it, resampled to
+/// `output_sample_rate` when given; returns the clip's path
+fn export_clip(
+    source: &std::path::Path,
+    start_secs: f64,
+    end_secs: f64,
+    output_sample_rate: Option<u32>
+) -> Result<std::path::PathBuf, String> {
+    let audio = crate::audio_processing::read_wav(source)?;
+    let target_rate = crate::audio_processing::resolve_output_sample_rate(
+        output_sample_rate,
+        audio.spec.sample_rate,
+    )?;
+
+    let duration = audio.duration_secs();
+    if !(0.0 <= start_secs && start_secs < end_secs && end_secs <= duration) {
+        return Err(format!(
+            "Clip range must satisfy 0 <= start < end <= {:.3} seconds",
+            duration
+        ));
+    }
+
+    let channels = audio.spec.channels as usize;
+    let start_frame = (start_secs * audio.spec.sample_rate as f64).floor() as usize;
+    let end_frame = ((end_secs * audio.spec.sample_rate as f64).ceil() as usize)
+        .min(audio.frame_count());
+    let clip = &audio.samples[start_frame * channels..end_frame * channels];
+
+    let resampled = crate::audio_processing::resample_linear(
+        clip,
+        audio.spec.channels,
+        audio.spec.sample_rate,
+        target_rate,
+    );
+
+    let stem = source.file_stem().and_then(|s| s.to_str()).unwrap_or("audio");
+    let output_path = source.with_file_name(format!(
+        "{}_clip_{}_{}.wav",
+        stem,
+        (start_secs * 1000.0).round() as u64,
+        (end_secs * 1000.0).round() as u64
+    ));
+
+    let spec = hound::WavSpec {
+        sample_rate: target_rate,
+        ..audio.spec
+    };
+    crate::audio_processing::write_wav(&output_path, spec, &resampled)?;
+
+    Ok(output_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{temp_dir, write_tone_wav};
+
+    #[test]
+    fn clip_export_at_44_1_khz_writes_a_matching_wav_header() {
+        let dir = temp_dir();
+        let source = dir.join("session.wav");
+        write_tone_wav(&source, 48_000, 2, 2.0);
+
+        let clip = export_clip(&source, 0.5, 1.5, Some(44_100)).unwrap();
+
+        // Canonical 44-byte header: format chunk fields at fixed offsets
+        let bytes = std::fs::read(&clip).unwrap();
+        let u16_at = |offset: usize| u16::from_le_bytes([bytes[offset], bytes[offset + 1]]);
+        let u32_at = |offset: usize| u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap());
+        assert_eq!(&bytes[0..4], b"RIFF");
+        assert_eq!(&bytes[8..16], b"WAVEfmt ");
+        assert_eq!(u16_at(22), 2, "channels");
+        assert_eq!(u32_at(24), 44_100, "sample rate");
+        assert_eq!(u32_at(28), 44_100 * 2 * 2, "byte rate");
+        assert_eq!(u16_at(32), 4, "block align");
+        assert_eq!(u16_at(34), 16, "bits per sample");
+
+        let reader = hound::WavReader::open(&clip).unwrap();
+        assert_eq!(reader.duration(), 44_100);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn clip_export_keeps_the_source_rate_by_default() {
+        let dir = temp_dir();
+        let source = dir.join("session.wav");
+        write_tone_wav(&source, 16_000, 1, 1.0);
+
+        let clip = export_clip(&source, 0.0, 0.5, None).unwrap();
+
+        let spec = hound::WavReader::open(&clip).unwrap().spec();
+        assert_eq!(spec.sample_rate, 16_000);
+        assert_eq!(spec.channels, 1);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn clip_export_rejects_out_of_range_rates() {
+        let dir = temp_dir();
+        let source = dir.join("session.wav");
+        write_tone_wav(&source, 16_000, 1, 1.0);
+
+        assert!(export_clip(&source, 0.0, 0.5, Some(4_000)).is_err());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn parallel_directory_import_reports_files_by_name() {
+        let dir = temp_dir();
+        // The first file takes longest to normalize, so finishing order differs from name order
+        write_tone_wav(&dir.join("a.wav"), 48_000, 2, 20.0);
+        write_tone_wav(&dir.join("c.wav"), 16_000, 1, 0.5);
+        write_tone_wav(&dir.join("b.wav"), 44_100, 1, 0.5);
+        std::fs::write(dir.join("bad.wav"), b"").unwrap();
+        std::fs::write(dir.join("notes.txt"), b"not audio").unwrap();
+
+        let entries = import_directory(&dir, 4, 600.0).await.unwrap();
+
+        let names: Vec<String> = entries
+            .iter()
+            .map(|entry| std::path::Path::new(&entry.file_path).file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names, ["a.wav", "b.wav", "bad.wav", "c.wav"]);
+        let succeeded: Vec<bool> = entries.iter().map(|entry| entry.success).collect();
+        assert_eq!(succeeded, [true, true, false, true]);
+        assert_eq!(entries[2].message, "File is empty");
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn directory_import_order_does_not_depend_on_parallelism() {
+        let dir = temp_dir();
+        // Already in the transcription format, so imports add no normalized copies
+        for (name, secs) in [("3.wav", 2.0), ("1.wav", 0.2), ("2.wav", 1.0)] {
+            write_tone_wav(&dir.join(name), 16_000, 1, secs);
+        }
+
+        let serial = import_directory(&dir, 1, 600.0).await.unwrap();
+        let parallel = import_directory(&dir, 3, 600.0).await.unwrap();
+
+        let paths = |entries: &[DirectoryImportEntry]| entries.iter().map(|e| e.file_path.clone()).collect::<Vec<_>>();
+        assert_eq!(paths(&serial), paths(&parallel));
+        assert_eq!(parallel.len(), 3);
+        assert!(parallel.iter().all(|entry| entry.success));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    fn device(name: &str, is_default: bool) -> AudioDevice {
         AudioDevice {
-            id: "mic1".to_string(),
-            name: "Built-in Microphone".to_string(),
-            is_default: false,
-        },
-    ])
-}
\ No newline at end of file
+            id: device_id_for_name(name, 0),
+            name: name.to_string(),
+            is_default,
+        }
+    }
+
+    #[test]
+    fn reordered_device_listings_are_unchanged() {
+        let before = [device("Built-in Microphone", true), device("USB Headset", false)];
+        let after = [device("USB Headset", false), device("Built-in Microphone", true)];
+
+        assert!(!device_set_changed(&before, &after));
+        assert!(!device_set_changed(&before, &before));
+    }
+
+    #[test]
+    fn added_or_removed_devices_are_changes() {
+        let before = [device("Built-in Microphone", true)];
+        let plugged_in = [device("Built-in Microphone", true), device("USB Headset", false)];
+
+        assert!(device_set_changed(&before, &plugged_in));
+        assert!(device_set_changed(&plugged_in, &before));
+        assert!(device_set_changed(&before, &[]));
+    }
+
+    #[test]
+    fn a_new_default_device_is_a_change() {
+        let before = [device("Built-in Microphone", true), device("USB Headset", false)];
+        let after = [device("Built-in Microphone", false), device("USB Headset", true)];
+
+        assert!(device_set_changed(&before, &after));
+    }
+
+    #[test]
+    fn device_ids_are_stable_and_tell_duplicates_apart() {
+        assert_eq!(device_id_for_name("USB Headset", 0), device_id_for_name("USB Headset", 0));
+        assert_ne!(device_id_for_name("USB Headset", 0), device_id_for_name("USB Headset", 1));
+        assert!(device_id_for_name("USB Headset", 1).ends_with("-2"));
+    }
+
+    fn recording(capture: Option<CaptureHandle>) -> RecordingState {
+        RecordingState {
+            active_since: None,
+            active_before: Duration::ZERO,
+            paused: Arc::new(AtomicBool::new(true)),
+            file_path: PathBuf::from("recording.wav"),
+            channel_files: Vec::new(),
+            capture,
+        }
+    }
+
+    #[test]
+    fn only_running_captures_count_as_recording_in_progress() {
+        let recordings = Recordings::default();
+        assert!(!recording_in_progress(&recordings));
+
+        recordings.lock().unwrap().insert("stopped".to_string(), recording(None));
+        assert!(!recording_in_progress(&recordings));
+
+        // A paused recording still holds its capture and files open
+        let (stop_tx, _stop_rx) = mpsc::channel();
+        let thread = std::thread::spawn(|| Ok(0.0));
+        recordings
+            .lock()
+            .unwrap()
+            .insert("paused".to_string(), recording(Some(CaptureHandle { stop_tx, thread })));
+        assert!(recording_in_progress(&recordings));
+    }
+}
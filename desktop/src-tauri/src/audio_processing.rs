@@ -0,0 +1,493 @@
+use hound::{SampleFormat, WavReader, WavSpec, WavWriter};
+use std::path::Path;
+
+/// Lowest output rate accepted for exported audio
+pub const MIN_OUTPUT_SAMPLE_RATE: u32 = 8_000;
+
+/// Highest output rate accepted for exported audio
+pub const MAX_OUTPUT_SAMPLE_RATE: u32 = 192_000;
+
+/// Sample rate WhisperX and the VAD expect
+pub const TRANSCRIPTION_SAMPLE_RATE: u32 = 16_000;
+
+/// Decoded WAV audio as interleaved `f32` samples in -1.0..=1.0
+pub struct DecodedAudio {
+    pub spec: WavSpec,
+    pub samples: Vec<f32>,
+}
+
+impl DecodedAudio {
+    pub fn frame_count(&self) -> usize {
+        self.samples.len() / self.spec.channels.max(1) as usize
+    }
+
+    pub fn duration_secs(&self) -> f64 {
+        self.frame_count() as f64 / self.spec.sample_rate as f64
+    }
+}
+
+/// Resolve the requested output rate, defaulting to the source rate
+pub fn resolve_output_sample_rate(requested: Option<u32>, source_rate: u32) -> Result<u32, String> {
+    match requested {
+        None => Ok(source_rate),
+        Some(rate) if (MIN_OUTPUT_SAMPLE_RATE..=MAX_OUTPUT_SAMPLE_RATE).contains(&rate) => Ok(rate),
+        Some(rate) => Err(format!(
+            "Invalid output sample rate {} Hz; expected {}..={} Hz",
+            rate, MIN_OUTPUT_SAMPLE_RATE, MAX_OUTPUT_SAMPLE_RATE
+        )),
+    }
+}
+
+/// Read a whole WAV file into normalized `f32` samples
+pub fn read_wav(path: &Path) -> Result<DecodedAudio, String> {
+    let mut reader = WavReader::open(path)
+        .map_err(|e| format!("Failed to open WAV file {}: {}", path.display(), e))?;
+    let spec = reader.spec();
+
+    let samples = match spec.sample_format {
+        SampleFormat::Float => reader
+            .samples::<f32>()
+            .collect::<Result<Vec<_>, _>>(),
+        SampleFormat::Int => {
+            let scale = int_scale(spec.bits_per_sample);
+            reader
+                .samples::<i32>()
+                .map(|sample| sample.map(|s| s as f32 / scale))
+                .collect::<Result<Vec<_>, _>>()
+        }
+    }
+    .map_err(|e| format!("Failed to decode WAV file {}: {}", path.display(), e))?;
+
+    Ok(DecodedAudio { spec, samples })
+}
+
+/// Read at most the first `max_secs` of a WAV file, leaving the rest unread
+pub fn read_wav_head(path: &Path, max_secs: f64) -> Result<DecodedAudio, String> {
+    let mut reader = WavReader::open(path)
+        .map_err(|e| format!("Failed to open WAV file {}: {}", path.display(), e))?;
+    let spec = reader.spec();
+    let limit = (max_secs.max(0.0) * spec.sample_rate as f64) as usize * spec.channels.max(1) as usize;
+
+    let samples = match spec.sample_format {
+        SampleFormat::Float => reader
+            .samples::<f32>()
+            .take(limit)
+            .collect::<Result<Vec<_>, _>>(),
+        SampleFormat::Int => {
+            let scale = int_scale(spec.bits_per_sample);
+            reader
+                .samples::<i32>()
+                .take(limit)
+                .map(|sample| sample.map(|s| s as f32 / scale))
+                .collect::<Result<Vec<_>, _>>()
+        }
+    }
+    .map_err(|e| format!("Failed to decode WAV file {}: {}", path.display(), e))?;
+
+    Ok(DecodedAudio { spec, samples })
+}
+
+/// Write interleaved `f32` samples using `spec`'s channel count, rate and sample format
+pub fn write_wav(path: &Path, spec: WavSpec, samples: &[f32]) -> Result<(), String> {
+    let mut writer = WavWriter::create(path, spec)
+        .map_err(|e| format!("Failed to create WAV file {}: {}", path.display(), e))?;
+
+    let result = match spec.sample_format {
+        SampleFormat::Float => samples
+            .iter()
+            .try_for_each(|sample| writer.write_sample(sample.clamp(-1.0, 1.0))),
+        SampleFormat::Int => {
+            let scale = int_scale(spec.bits_per_sample);
+            samples.iter().try_for_each(|sample| {
+                let value = (sample.clamp(-1.0, 1.0) * scale).round() as i32;
+                let value = value.clamp(-(scale as i32), scale as i32 - 1);
+                writer.write_sample(value)
+            })
+        }
+    };
+
+    result
+        .and_then(|_| writer.finalize())
+        .map_err(|e| format!("Failed to write WAV file {}: {}", path.display(), e))
+}
+
+/// Full-scale magnitude of a signed integer sample of the given width
+fn int_scale(bits_per_sample: u16) -> f32 {
+    (1u64 << (bits_per_sample.clamp(8, 32) - 1)) as f32
+}
+
+/// Bucket counts accepted by `waveform_peaks`; requests outside are clamped
+pub const MIN_WAVEFORM_BUCKETS: usize = 100;
+pub const MAX_WAVEFORM_BUCKETS: usize = 10_000;
+
+/// Min/max peak pairs `[min0, max0, min1, max1, ...]` over all channels of a WAV,
+/// streamed sample by sample so long recordings never sit in memory.
+/// Files shorter than `bucket_count` frames get one bucket per frame.
+pub fn waveform_peaks(path: &Path, bucket_count: usize) -> Result<Vec<f32>, String> {
+    let mut reader = WavReader::open(path)
+        .map_err(|e| format!("Failed to open WAV file {}: {}", path.display(), e))?;
+    let spec = reader.spec();
+    let channels = spec.channels.max(1) as u64;
+    let frames = reader.duration() as u64;
+    if frames == 0 {
+        return Err(format!("{} contains no audio", path.display()));
+    }
+
+    let buckets = (bucket_count.clamp(MIN_WAVEFORM_BUCKETS, MAX_WAVEFORM_BUCKETS) as u64).min(frames);
+    let mut peaks = vec![(f32::MAX, f32::MIN); buckets as usize];
+    let mut accumulate = |index: u64, sample: f32| {
+        let bucket = &mut peaks[((index / channels) * buckets / frames) as usize];
+        bucket.0 = bucket.0.min(sample);
+        bucket.1 = bucket.1.max(sample);
+    };
+
+    let decode_error = |e: hound::Error| format!("Failed to decode WAV file {}: {}", path.display(), e);
+    match spec.sample_format {
+        SampleFormat::Float => {
+            for (index, sample) in reader.samples::<f32>().enumerate() {
+                accumulate(index as u64, sample.map_err(decode_error)?);
+            }
+        }
+        SampleFormat::Int => {
+            let scale = int_scale(spec.bits_per_sample);
+            for (index, sample) in reader.samples::<i32>().enumerate() {
+                accumulate(index as u64, sample.map_err(decode_error)? as f32 / scale);
+            }
+        }
+    }
+
+    Ok(peaks.into_iter().flat_map(|(min, max)| [min, max]).collect())
+}
+
+/// Linearly resample interleaved audio from `from_rate` to `to_rate`
+pub fn resample_linear(samples: &[f32], channels: u16, from_rate: u32, to_rate: u32) -> Vec<f32> {
+    let channels = channels.max(1) as usize;
+    if from_rate == to_rate || samples.is_empty() {
+        return samples.to_vec();
+    }
+
+    let input_frames = samples.len() / channels;
+    let output_frames = ((input_frames as u64 * to_rate as u64) / from_rate as u64) as usize;
+    let step = from_rate as f64 / to_rate as f64;
+    let mut output = Vec::with_capacity(output_frames * channels);
+
+    for frame in 0..output_frames {
+        let position = frame as f64 * step;
+        let index = position.floor() as usize;
+        let fraction = (position - index as f64) as f32;
+        let next = (index + 1).min(input_frames - 1);
+
+        for channel in 0..channels {
+            let current = samples[index * channels + channel];
+            let following = samples[next * channels + channel];
+            output.push(current + (following - current) * fraction);
+        }
+    }
+
+    output
+}
+
+/// Highest peak a loudness normalization may produce, leaving headroom for resampling
+pub const NORMALIZE_PEAK_CEILING_DBFS: f64 = -1.0;
+
+/// Level of a linear amplitude in dBFS; silence reads as negative infinity
+pub fn amplitude_dbfs(amplitude: f64) -> f64 {
+    20.0 * amplitude.log10()
+}
+
+/// Gain in dB that brings `samples` from their RMS level to `target_dbfs`,
+/// lowered where needed so the loudest sample stays at or below
+/// `NORMALIZE_PEAK_CEILING_DBFS`. `None` for silent audio, which no gain can fix.
+pub fn normalization_gain_db(samples: &[f32], target_dbfs: f64) -> Option<f64> {
+    let peak = samples.iter().fold(0.0f32, |peak, s| peak.max(s.abs())) as f64;
+    if samples.is_empty() || peak == 0.0 {
+        return None;
+    }
+    let rms = (samples.iter().map(|s| (*s as f64).powi(2)).sum::<f64>() / samples.len() as f64).sqrt();
+
+    let gain_db = target_dbfs - amplitude_dbfs(rms);
+    let max_gain_db = NORMALIZE_PEAK_CEILING_DBFS - amplitude_dbfs(peak);
+    Some(gain_db.min(max_gain_db))
+}
+
+/// Frame length used for energy-based voice activity detection
+pub const VAD_FRAME_SECS: f64 = 0.03;
+
+/// Frames quieter than this (RMS, dBFS) count as silence
+pub const VAD_SILENCE_DBFS: f64 = -45.0;
+
+/// `(start, end)` spans in seconds where every frame's RMS level is below `threshold_dbfs`
+pub fn silent_regions(audio: &DecodedAudio, threshold_dbfs: f64) -> Vec<(f64, f64)> {
+    let channels = audio.spec.channels.max(1) as usize;
+    let frame_len = ((audio.spec.sample_rate as f64 * VAD_FRAME_SECS) as usize).max(1);
+    let threshold = 10f64.powf(threshold_dbfs / 20.0);
+
+    let mut regions: Vec<(f64, f64)> = Vec::new();
+    for (index, frame) in audio.samples.chunks(frame_len * channels).enumerate() {
+        let energy = frame.iter().map(|s| (*s as f64).powi(2)).sum::<f64>() / frame.len() as f64;
+        if energy.sqrt() >= threshold {
+            continue;
+        }
+
+        let start = (index * frame_len) as f64 / audio.spec.sample_rate as f64;
+        let end = start + (frame.len() / channels) as f64 / audio.spec.sample_rate as f64;
+        match regions.last_mut() {
+            Some(last) if (last.1 - start).abs() < 1e-9 => last.1 = end,
+            _ => regions.push((start, end)),
+        }
+    }
+
+    regions
+}
+
+/// Silent spans of a WAV lasting at least `min_silence_secs`, streamed frame by
+/// frame so long recordings never sit in memory. A frame is silent when its RMS
+/// level over all channels is below `threshold_dbfs`.
+pub fn stream_silent_regions(path: &Path, threshold_dbfs: f64, min_silence_secs: f64) -> Result<Vec<(f64, f64)>, String> {
+    let mut reader = WavReader::open(path)
+        .map_err(|e| format!("Failed to open WAV file {}: {}", path.display(), e))?;
+    let spec = reader.spec();
+    let channels = spec.channels.max(1) as usize;
+    let rate = spec.sample_rate as f64;
+    let frame_samples = ((rate * VAD_FRAME_SECS) as usize).max(1) * channels;
+    let threshold = 10f64.powf(threshold_dbfs / 20.0);
+
+    let samples: Box<dyn Iterator<Item = Result<f32, hound::Error>> + '_> = match spec.sample_format {
+        SampleFormat::Float => Box::new(reader.samples::<f32>()),
+        SampleFormat::Int => {
+            let scale = int_scale(spec.bits_per_sample);
+            Box::new(reader.samples::<i32>().map(move |sample| sample.map(|s| s as f32 / scale)))
+        }
+    };
+
+    let mut regions: Vec<(f64, f64)> = Vec::new();
+    let mut close_frame = |first_sample: usize, len: usize, energy: f64| {
+        if (energy / len as f64).sqrt() >= threshold {
+            return;
+        }
+        let start = (first_sample / channels) as f64 / rate;
+        let end = start + (len / channels) as f64 / rate;
+        match regions.last_mut() {
+            Some(last) if (last.1 - start).abs() < 1e-9 => last.1 = end,
+            _ => regions.push((start, end)),
+        }
+    };
+
+    let mut frame_start = 0;
+    let mut frame_len = 0;
+    let mut energy = 0.0;
+    for sample in samples {
+        let sample = sample.map_err(|e| format!("Failed to decode WAV file {}: {}", path.display(), e))?;
+        energy += (sample as f64).powi(2);
+        frame_len += 1;
+        if frame_len == frame_samples {
+            close_frame(frame_start, frame_len, energy);
+            frame_start += frame_len;
+            frame_len = 0;
+            energy = 0.0;
+        }
+    }
+    if frame_len > 0 {
+        close_frame(frame_start, frame_len, energy);
+    }
+
+    regions.retain(|(start, end)| end - start >= min_silence_secs);
+    Ok(regions)
+}
+
+/// Fraction of `start..end` covered by `regions`
+pub fn overlap_fraction(start: f64, end: f64, regions: &[(f64, f64)]) -> f64 {
+    let span = end - start;
+    if span <= 0.0 {
+        return 0.0;
+    }
+
+    let covered: f64 = regions
+        .iter()
+        .map(|(region_start, region_end)| (end.min(*region_end) - start.max(*region_start)).max(0.0))
+        .sum();
+    (covered / span).min(1.0)
+}
+
+/// Identify the container from its leading bytes: "wav", "flac", "ogg", "mp3", "aac" or "m4a".
+/// `None` means the file is not audio we can import.
+pub fn sniff_audio_format(header: &[u8]) -> Option<&'static str> {
+    match header {
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'A', b'V', b'E', ..] => Some("wav"),
+        [b'f', b'L', b'a', b'C', ..] => Some("flac"),
+        [b'O', b'g', b'g', b'S', ..] => Some("ogg"),
+        [_, _, _, _, b'f', b't', b'y', b'p', ..] => Some("m4a"),
+        [b'I', b'D', b'3', ..] => Some("mp3"),
+        // ADTS and MPEG audio share the 12-bit sync word; layer bits 00 mean AAC
+        [0xFF, second, ..] if second & 0xF6 == 0xF0 => Some("aac"),
+        [0xFF, second, ..] if second & 0xE0 == 0xE0 => Some("mp3"),
+        _ => None,
+    }
+}
+
+/// Detect an audio file's format by magic bytes, using the extension only to report disagreement
+pub fn detect_audio_format(path: &Path) -> Result<&'static str, String> {
+    use std::io::Read;
+
+    let mut header = [0u8; 12];
+    let mut file = std::fs::File::open(path)
+        .map_err(|e| format!("Failed to open audio file {}: {}", path.display(), e))?;
+    let read = file
+        .read(&mut header)
+        .map_err(|e| format!("Failed to read audio file {}: {}", path.display(), e))?;
+
+    let format = sniff_audio_format(&header[..read])
+        .ok_or_else(|| format!("{} is not a supported audio file", path.display()))?;
+
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase());
+    if let Some(extension) = extension {
+        let matches = extension == format || (format == "m4a" && ["mp4", "m4b", "aac"].contains(&extension.as_str()));
+        if !matches {
+            log::warn!("{} has extension .{} but contains {} audio", path.display(), extension, format);
+        }
+    }
+
+    Ok(format)
+}
+
+/// Container reader for `path` and the id of its first audio track
+fn open_audio_track(
+    path: &Path,
+    format_hint: Option<&str>
+) -> Result<(Box<dyn symphonia::core::formats::FormatReader>, u32), String> {
+    use symphonia::core::codecs::CODEC_TYPE_NULL;
+    use symphonia::core::formats::FormatOptions;
+    use symphonia::core::io::MediaSourceStream;
+    use symphonia::core::meta::MetadataOptions;
+    use symphonia::core::probe::Hint;
+
+    let file = std::fs::File::open(path)
+        .map_err(|e| format!("Failed to open audio file {}: {}", path.display(), e))?;
+    let stream = MediaSourceStream::new(Box::new(file), Default::default());
+
+    let mut hint = Hint::new();
+    if let Some(format) = format_hint {
+        hint.with_extension(format);
+    }
+    let probed = symphonia::default::get_probe()
+        .format(&hint, stream, &FormatOptions::default(), &MetadataOptions::default())
+        .map_err(|e| format!("Unrecognized audio in {}: {}", path.display(), e))?;
+
+    let track_id = probed
+        .format
+        .tracks()
+        .iter()
+        .find(|track| track.codec_params.codec != CODEC_TYPE_NULL)
+        .map(|track| track.id)
+        .ok_or_else(|| format!("{} contains no audio track", path.display()))?;
+
+    Ok((probed.format, track_id))
+}
+
+/// Stream properties read from container headers, without decoding any audio
+pub struct AudioProbe {
+    pub codec: String,
+    /// `None` when the container doesn't declare its length
+    pub duration_secs: Option<f64>,
+}
+
+pub fn probe_audio(path: &Path, format_hint: Option<&str>) -> Result<AudioProbe, String> {
+    let (reader, track_id) = open_audio_track(path, format_hint)?;
+    let params = &reader
+        .tracks()
+        .iter()
+        .find(|track| track.id == track_id)
+        .expect("track found while opening")
+        .codec_params;
+
+    let codec = symphonia::default::get_codecs()
+        .get_codec(params.codec)
+        .map(|descriptor| descriptor.short_name.to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    let duration_secs = match (params.n_frames, params.sample_rate) {
+        (Some(frames), Some(rate)) if rate > 0 => Some(frames as f64 / rate as f64),
+        _ => None,
+    };
+
+    Ok(AudioProbe {
+        codec,
+        duration_secs,
+    })
+}
+
+/// Decode any container/codec symphonia supports into interleaved `f32` samples
+pub fn decode_audio(path: &Path, format_hint: Option<&str>) -> Result<DecodedAudio, String> {
+    use symphonia::core::audio::SampleBuffer;
+    use symphonia::core::codecs::DecoderOptions;
+    use symphonia::core::errors::Error as SymphoniaError;
+
+    let (mut reader, track_id) = open_audio_track(path, format_hint)?;
+    let track = reader
+        .tracks()
+        .iter()
+        .find(|track| track.id == track_id)
+        .expect("track found while opening");
+    let mut sample_rate = track.codec_params.sample_rate;
+    let mut channels = track.codec_params.channels.map(|c| c.count() as u16);
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(|e| format!("Unsupported codec in {}: {}", path.display(), e))?;
+
+    let mut samples = Vec::new();
+    loop {
+        let packet = match reader.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+            Err(e) => return Err(format!("Failed to read audio from {}: {}", path.display(), e)),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        match decoder.decode(&packet) {
+            Ok(decoded) => {
+                let spec = *decoded.spec();
+                sample_rate = Some(spec.rate);
+                channels = Some(spec.channels.count() as u16);
+                let mut buffer = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+                buffer.copy_interleaved_ref(decoded);
+                samples.extend_from_slice(buffer.samples());
+            }
+            // A corrupt packet costs a few milliseconds of audio, not the whole import
+            Err(SymphoniaError::DecodeError(e)) => {
+                log::warn!("Skipping undecodable packet in {}: {}", path.display(), e);
+            }
+            Err(e) => return Err(format!("Failed to decode audio from {}: {}", path.display(), e)),
+        }
+    }
+
+    let (Some(sample_rate), Some(channels)) = (sample_rate, channels) else {
+        return Err(format!("{} does not declare a sample rate and channel layout", path.display()));
+    };
+
+    Ok(DecodedAudio {
+        spec: WavSpec {
+            channels,
+            sample_rate,
+            bits_per_sample: 32,
+            sample_format: SampleFormat::Float,
+        },
+        samples,
+    })
+}
+
+/// Average interleaved channels into a single channel
+pub fn downmix_mono(samples: &[f32], channels: u16) -> Vec<f32> {
+    let channels = channels.max(1) as usize;
+    if channels == 1 {
+        return samples.to_vec();
+    }
+
+    samples
+        .chunks_exact(channels)
+        .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+        .collect()
+}
@@ -0,0 +1,370 @@
+use tauri::{AppHandle, Emitter, Manager, State};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use crate::error::AppError;
+use crate::paths::AppPaths;
+use crate::settings::{AppSettings, SettingsStore};
+use crate::state::AppState;
+
+/// How often the scheduler re-reads settings to decide whether a backup is due
+const BACKUP_TICK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// File name prefix shared by scheduled and manual backups
+const BACKUP_PREFIX: &str = "transrapport-backup-";
+
+const BACKUP_EXTENSION: &str = "db";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupInfo {
+    pub file_path: String,
+    pub size_bytes: u64,
+    pub created_at: String,
+}
+
+/// Configured backup directory, defaulting to `backups/` in the app data directory
+pub fn backup_directory(app: &AppHandle, settings: &AppSettings) -> Result<PathBuf, AppError> {
+    match &settings.backup_directory {
+        Some(dir) => Ok(PathBuf::from(dir)),
+        None => app
+            .path()
+            .app_data_dir()
+            .map(|dir| dir.join("backups"))
+            .map_err(|e| AppError::Internal(format!("Failed to resolve app data directory: {}", e))),
+    }
+}
+
+/// Backup files in `dir`, newest first. Names embed a sortable UTC timestamp.
+pub fn list_backup_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut backups: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            let is_backup_name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .map(|name| name.starts_with(BACKUP_PREFIX))
+                .unwrap_or(false);
+            let is_db = path.extension().and_then(|ext| ext.to_str()) == Some(BACKUP_EXTENSION);
+            is_backup_name && is_db && path.is_file()
+        })
+        .collect();
+    backups.sort_by(|a, b| b.file_name().cmp(&a.file_name()));
+    backups
+}
+
+/// Backups beyond the newest `keep`, which rotation deletes
+pub fn backups_to_prune(mut backups: Vec<PathBuf>, keep: usize) -> Vec<PathBuf> {
+    backups.sort_by(|a, b| b.file_name().cmp(&a.file_name()));
+    backups.into_iter().skip(keep).collect()
+}
+
+/// Delete all but the newest `keep` backups in `dir`
+fn rotate_backups(dir: &Path, keep: usize) {
+    for stale in backups_to_prune(list_backup_files(dir), keep) {
+        if let Err(e) = std::fs::remove_file(&stale) {
+            log::warn!("Failed to remove old backup {}: {}", stale.display(), e);
+        }
+    }
+}
+
+fn backup_info(path: &Path) -> Result<BackupInfo, AppError> {
+    let metadata = std::fs::metadata(path)
+        .map_err(|e| AppError::Io(format!("Failed to read backup {}: {}", path.display(), e)))?;
+    let created_at = metadata
+        .modified()
+        .map(|modified| chrono::DateTime::<chrono::Utc>::from(modified).to_rfc3339())
+        .unwrap_or_default();
+
+    Ok(BackupInfo {
+        file_path: path.to_string_lossy().to_string(),
+        size_bytes: metadata.len(),
+        created_at,
+    })
+}
+
+/// Write a consistent copy of the live database to `destination`
+pub async fn write_backup(pool: &SqlitePool, destination: &Path) -> Result<BackupInfo, AppError> {
+    if destination.exists() {
+        return Err(AppError::Validation(format!("Backup destination already exists: {}", destination.display())));
+    }
+
+    // VACUUM INTO produces a compacted snapshot without blocking readers
+    sqlx::query("VACUUM INTO ?")
+        .bind(destination.to_string_lossy().to_string())
+        .execute(pool)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to back up database: {}", e)))?;
+
+    backup_info(destination)
+}
+
+/// Back up into the configured directory, rotate old backups and notify the UI
+pub async fn run_backup(
+    app: &AppHandle,
+    pool: &SqlitePool,
+    settings: &AppSettings
+) -> Result<BackupInfo, AppError> {
+    let result = async {
+        let dir = backup_directory(app, settings)?;
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| AppError::Io(format!("Failed to create backup directory: {}", e)))?;
+
+        let file_name = format!(
+            "{}{}.{}",
+            BACKUP_PREFIX,
+            chrono::Utc::now().format("%Y%m%d-%H%M%S%3f"),
+            BACKUP_EXTENSION
+        );
+        let info = write_backup(pool, &dir.join(file_name)).await?;
+
+        rotate_backups(&dir, settings.backup_keep_count);
+
+        Ok(info)
+    }
+    .await;
+
+    match &result {
+        Ok(info) => {
+            log::info!("Database backed up to: {} ({} bytes)", info.file_path, info.size_bytes);
+            if let Err(e) = app.emit("backup-complete", info) {
+                log::warn!("Failed to emit backup-complete: {}", e);
+            }
+        }
+        Err(error) => {
+            log::error!("Database backup failed: {}", error);
+            if let Err(e) = app.emit("backup-failed", error) {
+                log::warn!("Failed to emit backup-failed: {}", e);
+            }
+        }
+    }
+
+    result
+}
+
+/// Age of the newest backup, which decides when the next scheduled one is due
+fn newest_backup_age(dir: &Path) -> Option<Duration> {
+    let newest = list_backup_files(dir).into_iter().next()?;
+    let modified = std::fs::metadata(newest).and_then(|m| m.modified()).ok()?;
+    SystemTime::now().duration_since(modified).ok()
+}
+
+/// Background task running scheduled backups per `auto_backup_interval_minutes`
+pub struct BackupScheduler {
+    handle: Mutex<Option<tauri::async_runtime::JoinHandle<()>>>,
+}
+
+impl BackupScheduler {
+    pub fn spawn(app: AppHandle) -> Self {
+        let handle = tauri::async_runtime::spawn(async move {
+            let mut ticker = tokio::time::interval(BACKUP_TICK_INTERVAL);
+
+            loop {
+                ticker.tick().await;
+
+                let settings = app.state::<SettingsStore>().get();
+                let Some(interval_minutes) = settings.auto_backup_interval_minutes else {
+                    continue;
+                };
+
+                let dir = match backup_directory(&app, &settings) {
+                    Ok(dir) => dir,
+                    Err(e) => {
+                        log::warn!("Backup scheduler cannot resolve backup directory: {}", e);
+                        continue;
+                    }
+                };
+                let interval = Duration::from_secs(interval_minutes * 60);
+                let due = newest_backup_age(&dir).map(|age| age >= interval).unwrap_or(true);
+                if !due {
+                    continue;
+                }
+
+                // Nothing to back up until the database is unlocked
+                let Some(state) = app.try_state::<AppState>() else {
+                    continue;
+                };
+                // Avoid contending with a live recording; the next tick retries
+                if crate::audio_commands::recording_in_progress(&state.recordings) {
+                    log::info!("Skipping scheduled backup while a recording is active");
+                    continue;
+                }
+
+                // Failures are reported through backup-failed by run_backup
+                let _ = run_backup(&app, &state.db, &settings).await;
+            }
+        });
+
+        Self {
+            handle: Mutex::new(Some(handle)),
+        }
+    }
+
+    pub fn stop(&self) {
+        if let Some(handle) = self.handle.lock().unwrap().take() {
+            handle.abort();
+            log::info!("Backup scheduler stopped");
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn trigger_backup_now(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    settings: State<'_, SettingsStore>
+) -> Result<BackupInfo, AppError> {
+    log::info!("Manual database backup requested");
+
+    run_backup(&app, &state.db, &settings.get()).await
+}
+
+#[tauri::command]
+pub async fn list_backups(
+    app: AppHandle,
+    settings: State<'_, SettingsStore>
+) -> Result<Vec<BackupInfo>, AppError> {
+    let dir = backup_directory(&app, &settings.get())?;
+
+    list_backup_files(&dir)
+        .iter()
+        .map(|path| backup_info(path))
+        .collect()
+}
+
+/// Absolute form of a path that may not exist yet, resolving its parent
+fn resolve_destination(path: &Path) -> Result<PathBuf, AppError> {
+    let parent = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| AppError::Validation(format!("Not a file path: {}", path.display())))?;
+    let parent = parent
+        .canonicalize()
+        .map_err(|e| AppError::Io(format!("Cannot use {}: {}", parent.display(), e)))?;
+    Ok(parent.join(file_name))
+}
+
+/// Back up the live database to a path of the user's choosing, e.g. removable
+/// media. The copy is consistent even while the app keeps writing.
+#[tauri::command]
+pub async fn backup_database(
+    state: State<'_, AppState>,
+    paths: State<'_, AppPaths>,
+    destination_path: String
+) -> Result<BackupInfo, AppError> {
+    log::info!("Backing up database to: {}", destination_path);
+
+    let destination = resolve_destination(Path::new(&destination_path))?;
+    // A copy next to the live database is lost with it
+    if paths.is_in_data_dir(&destination) {
+        return Err(AppError::Validation(
+            "Choose a backup location outside the app's data directory".to_string(),
+        ));
+    }
+
+    let info = write_backup(&state.db, &destination).await?;
+    log::info!("Database backed up to: {} ({} bytes)", info.file_path, info.size_bytes);
+    Ok(info)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DatabaseRestore {
+    pub source_path: String,
+    pub session_count: i64,
+    /// The restored database is used once the app restarts
+    pub restart_required: bool,
+}
+
+/// Stage a backup to replace the live database. The file is checked to be a
+/// readable database under the live database's key first; the swap happens at
+/// the next start, so the UI should offer a restart.
+#[tauri::command]
+pub async fn restore_database(
+    state: State<'_, AppState>,
+    paths: State<'_, AppPaths>,
+    source_path: String
+) -> Result<DatabaseRestore, AppError> {
+    log::info!("Restoring database from: {}", source_path);
+
+    let source = Path::new(&source_path);
+    if !source.is_file() {
+        return Err(AppError::NotFound(format!("Backup not found: {}", source_path)));
+    }
+    let session_count = crate::storage_commands::check_database_file(source, &state.database_key)
+        .await
+        .map_err(|problem| AppError::Validation(format!("{} {}", source_path, problem)))?;
+
+    // Copy under a temporary name so a half-written file is never picked up at startup
+    let pending = paths.pending_restore();
+    let staging = pending.with_extension("restore.partial");
+    tokio::fs::copy(source, &staging)
+        .await
+        .map_err(|e| AppError::Io(format!("Failed to stage restored database: {}", e)))?;
+    tokio::fs::rename(&staging, &pending)
+        .await
+        .map_err(|e| AppError::Io(format!("Failed to stage restored database: {}", e)))?;
+
+    log::info!("Staged {} with {} sessions; it replaces the database at the next start", source_path, session_count);
+    Ok(DatabaseRestore {
+        source_path,
+        session_count,
+        restart_required: true,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{memory_pool, temp_dir};
+
+    fn backup_name(stamp: &str) -> String {
+        format!("{}{}.{}", BACKUP_PREFIX, stamp, BACKUP_EXTENSION)
+    }
+
+    #[tokio::test]
+    async fn rotation_keeps_exactly_the_newest_backups() {
+        let pool = memory_pool().await;
+        let dir = temp_dir();
+        let stamps = [
+            "20260101-080000000",
+            "20260102-080000000",
+            "20260103-080000000",
+            "20260104-080000000",
+            "20260105-080000000",
+        ];
+        for stamp in stamps {
+            write_backup(&pool, &dir.join(backup_name(stamp))).await.unwrap();
+        }
+        std::fs::write(dir.join("notes.txt"), "not a backup").unwrap();
+        std::fs::write(dir.join(format!("{}manual.txt", BACKUP_PREFIX)), "not a backup").unwrap();
+
+        rotate_backups(&dir, 3);
+
+        let kept: Vec<PathBuf> = list_backup_files(&dir);
+        let expected: Vec<PathBuf> = stamps[2..].iter().rev().map(|stamp| dir.join(backup_name(stamp))).collect();
+        assert_eq!(kept, expected);
+        assert!(dir.join("notes.txt").exists());
+        assert!(dir.join(format!("{}manual.txt", BACKUP_PREFIX)).exists());
+
+        // Already at the limit: nothing more goes
+        rotate_backups(&dir, 3);
+        assert_eq!(list_backup_files(&dir).len(), 3);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn prune_keeps_everything_below_the_limit() {
+        let backups = vec![PathBuf::from(backup_name("20260101-080000000"))];
+        assert!(backups_to_prune(backups, 3).is_empty());
+    }
+}
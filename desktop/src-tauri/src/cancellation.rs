@@ -0,0 +1,217 @@
+use tauri::State;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::error::AppError;
+
+#[derive(Debug, Default)]
+struct TokenState {
+    cancelled: AtomicBool,
+    notify: tokio::sync::Notify,
+}
+
+/// Flag a long-running command polls, or awaits, to learn that the user cancelled it
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<TokenState>);
+
+impl CancellationToken {
+    pub fn cancel(&self) {
+        self.0.cancelled.store(true, Ordering::SeqCst);
+        self.0.notify.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Resolve once the token is cancelled, for racing work in `tokio::select!`
+    pub async fn cancelled(&self) {
+        loop {
+            // Registered before the check, so a cancel in between still wakes it
+            let notified = self.0.notify.notified();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// Return an error once cancelled so work loops can bail out with `?`
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.is_cancelled() {
+            Err(AppError::Cancelled("Operation cancelled".to_string()))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Registry of cancellable operations keyed by the operation id handed to the UI
+#[derive(Default)]
+pub struct CancellationManager {
+    operations: Mutex<HashMap<String, CancellationToken>>,
+}
+
+impl CancellationManager {
+    /// Register a new operation under a fresh id
+    pub fn register(&self) -> (String, CancellationToken) {
+        let operation_id = uuid::Uuid::new_v4().to_string();
+        let token = self.register_with_id(&operation_id);
+        (operation_id, token)
+    }
+
+    /// Register an operation under a caller-chosen id, e.g. a session id.
+    /// Re-registering an id that is still active returns its existing token.
+    pub fn register_with_id(&self, operation_id: &str) -> CancellationToken {
+        self.operations
+            .lock()
+            .unwrap()
+            .entry(operation_id.to_string())
+            .or_default()
+            .clone()
+    }
+
+    /// Register an operation that must not run twice at once under the same id;
+    /// `None` while an operation with that id is still registered
+    pub fn try_register_with_id(&self, operation_id: &str) -> Option<CancellationToken> {
+        match self.operations.lock().unwrap().entry(operation_id.to_string()) {
+            Entry::Occupied(_) => None,
+            Entry::Vacant(entry) => Some(entry.insert(CancellationToken::default()).clone()),
+        }
+    }
+
+    /// Signal cancellation; returns false when the operation is unknown or finished
+    pub fn cancel(&self, operation_id: &str) -> bool {
+        match self.operations.lock().unwrap().get(operation_id) {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Forget an operation once it has completed, successfully or not
+    pub fn finish(&self, operation_id: &str) {
+        self.operations.lock().unwrap().remove(operation_id);
+    }
+
+    pub fn active_operations(&self) -> Vec<String> {
+        self.operations.lock().unwrap().keys().cloned().collect()
+    }
+}
+
+#[tauri::command]
+pub async fn cancel(
+    manager: State<'_, CancellationManager>,
+    operation_id: String
+) -> Result<String, AppError> {
+    log::info!("Cancelling operation: {}", operation_id);
+
+    if manager.cancel(&operation_id) {
+        Ok("Cancellation requested".to_string())
+    } else {
+        Err(AppError::NotFound(format!("No active operation with id: {}", operation_id)))
+    }
+}
+
+#[tauri::command]
+pub async fn list_active_operations(
+    manager: State<'_, CancellationManager>
+) -> Result<Vec<String>, AppError> {
+    Ok(manager.active_operations())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn running_operation_observes_cancel() {
+        let manager = CancellationManager::default();
+        let (operation_id, token) = manager.register();
+
+        // Stands in for a command's work loop polling its token between steps
+        let worker = tokio::spawn(async move {
+            while !token.is_cancelled() {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+            token.check()
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!worker.is_finished());
+
+        assert!(manager.cancel(&operation_id));
+        let result = tokio::time::timeout(Duration::from_secs(5), worker)
+            .await
+            .expect("worker stops after cancel")
+            .unwrap();
+        assert!(matches!(result, Err(AppError::Cancelled(_))));
+    }
+
+    #[test]
+    fn reregistering_an_active_id_shares_its_token() {
+        let manager = CancellationManager::default();
+        let first = manager.register_with_id("session-1");
+        let second = manager.register_with_id("session-1");
+
+        manager.cancel("session-1");
+
+        assert!(first.is_cancelled());
+        assert!(second.is_cancelled());
+    }
+
+    #[test]
+    fn finished_operations_cannot_be_cancelled() {
+        let manager = CancellationManager::default();
+        let (operation_id, token) = manager.register();
+
+        manager.finish(&operation_id);
+
+        assert!(!manager.cancel(&operation_id));
+        assert!(!token.is_cancelled());
+        assert!(manager.active_operations().is_empty());
+    }
+
+    #[tokio::test]
+    async fn cancelled_resolves_for_waiters_and_after_the_fact() {
+        let manager = CancellationManager::default();
+        let token = manager.register_with_id("run-1");
+
+        let waiter = tokio::spawn({
+            let token = token.clone();
+            async move { token.cancelled().await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!waiter.is_finished());
+
+        assert!(manager.cancel("run-1"));
+        tokio::time::timeout(Duration::from_secs(5), waiter)
+            .await
+            .expect("waiter wakes on cancel")
+            .unwrap();
+        // Already cancelled tokens resolve immediately
+        tokio::time::timeout(Duration::from_secs(5), token.cancelled())
+            .await
+            .expect("cancelled token resolves");
+    }
+
+    #[test]
+    fn exclusive_registration_rejects_an_active_id() {
+        let manager = CancellationManager::default();
+        let first = manager.try_register_with_id("session-1").expect("first registration");
+        assert!(manager.try_register_with_id("session-1").is_none());
+
+        // A cancelled operation stays registered until it finishes
+        manager.cancel("session-1");
+        assert!(first.is_cancelled());
+        assert!(manager.try_register_with_id("session-1").is_none());
+
+        manager.finish("session-1");
+        let second = manager.try_register_with_id("session-1").expect("registration after finish");
+        assert!(!second.is_cancelled());
+    }
+}
@@ -0,0 +1,123 @@
+use serde::Serialize;
+use tauri::Manager;
+
+use crate::audio_commands::AudioDevice;
+use crate::error::AppError;
+use crate::state::AppState;
+
+/// Details of one subsystem, or why they could not be gathered
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Subsystem<T> {
+    Available { details: T },
+    Unavailable { reason: String },
+}
+
+impl<T> From<Result<T, String>> for Subsystem<T> {
+    fn from(result: Result<T, String>) -> Self {
+        match result {
+            Ok(details) => Subsystem::Available { details },
+            Err(reason) => Subsystem::Unavailable { reason },
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PythonDiagnostics {
+    pub interpreter_path: String,
+    pub version: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DatabaseDiagnostics {
+    pub path: String,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecordCounts {
+    pub sessions: i64,
+    /// Sessions with at least one transcript segment
+    pub transcripts: i64,
+    pub markers: i64,
+}
+
+/// Environment details for bug reports. Every subsystem is probed on its own,
+/// so one that is missing or broken is reported rather than failing the rest.
+#[derive(Debug, Serialize)]
+pub struct AppDiagnostics {
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    pub python: Subsystem<PythonDiagnostics>,
+    pub audio_input_devices: Subsystem<Vec<AudioDevice>>,
+    pub database: Subsystem<DatabaseDiagnostics>,
+    pub data_dir_free_bytes: Subsystem<u64>,
+    pub record_counts: Subsystem<RecordCounts>,
+}
+
+async fn python_diagnostics(app: &tauri::AppHandle) -> Result<PythonDiagnostics, String> {
+    let state = app.try_state::<AppState>().ok_or("App state is not initialized")?;
+    let interpreter = state.python_interpreter(app).await.map_err(|e| e.to_string())?;
+    let version = crate::python_integration::interpreter_version(interpreter).await?;
+    Ok(PythonDiagnostics {
+        interpreter_path: interpreter.display().to_string(),
+        version,
+    })
+}
+
+async fn audio_input_devices() -> Result<Vec<AudioDevice>, String> {
+    tauri::async_runtime::spawn_blocking(crate::audio_commands::list_input_devices)
+        .await
+        .map_err(|e| format!("Device enumeration task failed: {}", e))?
+}
+
+fn database_diagnostics(paths: &crate::paths::AppPaths) -> Result<DatabaseDiagnostics, String> {
+    let path = paths.database();
+    let metadata = std::fs::metadata(&path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    Ok(DatabaseDiagnostics {
+        path: path.display().to_string(),
+        size_bytes: metadata.len(),
+    })
+}
+
+fn data_dir_free_bytes(paths: &crate::paths::AppPaths) -> Result<u64, String> {
+    let data_dir = paths.data_dir();
+    fs2::available_space(data_dir).map_err(|e| format!("Cannot query free space on {}: {}", data_dir.display(), e))
+}
+
+async fn record_counts(app: &tauri::AppHandle) -> Result<RecordCounts, String> {
+    let state = app.try_state::<AppState>().ok_or("App state is not initialized")?;
+    let (sessions, transcripts, markers): (i64, i64, i64) = sqlx::query_as(
+        "SELECT (SELECT COUNT(*) FROM conversation_sessions), \
+                (SELECT COUNT(DISTINCT session_id) FROM transcript_segments), \
+                (SELECT COUNT(*) FROM marker_events)",
+    )
+    .fetch_one(&state.db)
+    .await
+    .map_err(|e| format!("Failed to count records: {}", e))?;
+    Ok(RecordCounts {
+        sessions,
+        transcripts,
+        markers,
+    })
+}
+
+#[tauri::command]
+pub async fn get_app_diagnostics(app: tauri::AppHandle) -> Result<AppDiagnostics, AppError> {
+    log::info!("Collecting app diagnostics");
+
+    let paths = app.try_state::<crate::paths::AppPaths>().map(|paths| paths.inner().clone());
+    let paths_unavailable = || "App data directory is not resolved".to_string();
+
+    Ok(AppDiagnostics {
+        app_version: app.package_info().version.to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        python: python_diagnostics(&app).await.into(),
+        audio_input_devices: audio_input_devices().await.into(),
+        database: paths.as_ref().ok_or_else(paths_unavailable).and_then(database_diagnostics).into(),
+        data_dir_free_bytes: paths.as_ref().ok_or_else(paths_unavailable).and_then(data_dir_free_bytes).into(),
+        record_counts: record_counts(&app).await.into(),
+    })
+}
@@ -0,0 +1,350 @@
+use tauri::State;
+use sqlx::Row;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::error::AppError;
+use crate::state::AppState;
+
+/// Jaccard similarity of transcript word shingles above which sessions count as duplicates
+const DEFAULT_TRANSCRIPT_SIMILARITY: f64 = 0.9;
+
+/// Words per shingle used for near-duplicate transcript comparison
+const SHINGLE_SIZE: usize = 3;
+
+/// What duplicate detection knows about one session
+pub struct SessionFingerprint {
+    pub session_id: String,
+    pub audio_checksum: Option<String>,
+    pub shingles: HashSet<String>,
+}
+
+/// Overlapping word n-grams of a transcript, case- and punctuation-insensitive
+pub fn transcript_shingles(text: &str) -> HashSet<String> {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect();
+
+    if words.len() < SHINGLE_SIZE {
+        return words.into_iter().collect();
+    }
+
+    words.windows(SHINGLE_SIZE).map(|window| window.join(" ")).collect()
+}
+
+pub fn jaccard_similarity(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let intersection = a.intersection(b).count() as f64;
+    let union = a.union(b).count() as f64;
+    intersection / union
+}
+
+fn find_root(parents: &mut [usize], index: usize) -> usize {
+    let mut root = index;
+    while parents[root] != root {
+        root = parents[root];
+    }
+    // Path compression keeps later lookups flat
+    let mut current = index;
+    while parents[current] != root {
+        let next = parents[current];
+        parents[current] = root;
+        current = next;
+    }
+    root
+}
+
+fn union(parents: &mut [usize], a: usize, b: usize) {
+    let root_a = find_root(parents, a);
+    let root_b = find_root(parents, b);
+    if root_a != root_b {
+        parents[root_b] = root_a;
+    }
+}
+
+/// Group sessions sharing an audio checksum or a near-identical transcript.
+/// Only groups with at least two members are returned, each sorted by session id.
+pub fn group_duplicates(fingerprints: &[SessionFingerprint], similarity_threshold: f64) -> Vec<Vec<String>> {
+    let mut parents: Vec<usize> = (0..fingerprints.len()).collect();
+
+    let mut by_checksum: HashMap<&str, usize> = HashMap::new();
+    for (index, fingerprint) in fingerprints.iter().enumerate() {
+        if let Some(checksum) = fingerprint.audio_checksum.as_deref() {
+            match by_checksum.get(checksum) {
+                Some(&first) => union(&mut parents, first, index),
+                None => {
+                    by_checksum.insert(checksum, index);
+                }
+            }
+        }
+    }
+
+    for i in 0..fingerprints.len() {
+        for j in (i + 1)..fingerprints.len() {
+            let similarity = jaccard_similarity(&fingerprints[i].shingles, &fingerprints[j].shingles);
+            if similarity >= similarity_threshold {
+                union(&mut parents, i, j);
+            }
+        }
+    }
+
+    let mut groups: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+    for (index, fingerprint) in fingerprints.iter().enumerate() {
+        let root = find_root(&mut parents, index);
+        groups
+            .entry(root)
+            .or_default()
+            .push(fingerprint.session_id.clone());
+    }
+
+    let mut duplicates: Vec<Vec<String>> = groups
+        .into_values()
+        .filter(|group| group.len() > 1)
+        .map(|mut group| {
+            group.sort();
+            group
+        })
+        .collect();
+    duplicates.sort();
+    duplicates
+}
+
+#[tauri::command]
+pub async fn find_duplicate_sessions(
+    state: State<'_, AppState>,
+    similarity_threshold: Option<f64>
+) -> Result<Vec<Vec<String>>, AppError> {
+    let threshold = similarity_threshold.unwrap_or(DEFAULT_TRANSCRIPT_SIMILARITY);
+    log::info!("Searching for duplicate sessions with similarity threshold: {}", threshold);
+
+    if !(0.0..=1.0).contains(&threshold) || threshold == 0.0 {
+        return Err(AppError::Validation("similarity_threshold must be within (0.0, 1.0]".to_string()));
+    }
+
+    let rows = sqlx::query("SELECT id, file_path FROM conversation_sessions")
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load sessions: {}", e)))?;
+
+    let mut fingerprints = Vec::with_capacity(rows.len());
+    for row in rows {
+        let session_id: String = row.try_get("id").map_err(AppError::from)?;
+        let file_path: Option<String> = row.try_get("file_path").map_err(AppError::from)?;
+
+        let audio_checksum = match file_path {
+            Some(path) => {
+                let checksum = tauri::async_runtime::spawn_blocking(move || {
+                    crate::audio_commands::file_sha256(std::path::Path::new(&path))
+                })
+                .await
+                .map_err(|e| AppError::Internal(format!("Checksum task failed: {}", e)))?;
+                // Missing audio simply can't match by checksum
+                checksum.ok()
+            }
+            None => None,
+        };
+
+        let segments = crate::storage_commands::load_segments(&state.db, &session_id)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to load transcript: {}", e)))?;
+        let text = segments
+            .iter()
+            .map(|segment| segment.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        fingerprints.push(SessionFingerprint {
+            session_id,
+            audio_checksum,
+            shingles: transcript_shingles(&text),
+        });
+    }
+
+    Ok(group_duplicates(&fingerprints, threshold))
+}
+
+#[tauri::command]
+pub async fn merge_duplicates(
+    state: State<'_, AppState>,
+    group: Vec<String>
+) -> Result<String, AppError> {
+    log::info!("Merging duplicate group of {} sessions", group.len());
+
+    let keep_id = merge_group(&state.db, &group).await?;
+
+    log::info!("Merged duplicates into session: {}", keep_id);
+    Ok(keep_id)
+}
+
+/// Fold a duplicate group into its richest session and delete the others;
+/// returns the id of the session kept
+async fn merge_group(pool: &sqlx::SqlitePool, group: &[String]) -> Result<String, AppError> {
+    let unique: HashSet<&String> = group.iter().collect();
+    if unique.len() < 2 {
+        return Err(AppError::Validation("A duplicate group needs at least two distinct sessions".to_string()));
+    }
+
+    // Keep the session with the most transcript and analysis content, counting
+    // segments and markers alike; ties go to the oldest session
+    let mut richest: Option<(String, i64, String)> = None;
+    for session_id in &unique {
+        let row = sqlx::query(
+            "SELECT s.created_at, \
+             (SELECT COUNT(*) FROM transcript_segments t WHERE t.session_id = s.id) \
+             + (SELECT COUNT(*) FROM marker_events m WHERE m.session_id = s.id) AS content_count \
+             FROM conversation_sessions s WHERE s.id = ?",
+        )
+        .bind(session_id.as_str())
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to inspect session: {}", e)))?
+        .ok_or_else(|| AppError::NotFound(format!("Session not found: {}", session_id)))?;
+
+        let created_at: String = row.try_get("created_at").map_err(AppError::from)?;
+        let content_count: i64 = row.try_get("content_count").map_err(AppError::from)?;
+
+        let is_richer = match &richest {
+            None => true,
+            Some((_, best_count, best_created)) => {
+                content_count > *best_count
+                    || (content_count == *best_count && created_at < *best_created)
+            }
+        };
+        if is_richer {
+            richest = Some(((*session_id).clone(), content_count, created_at));
+        }
+    }
+
+    let (keep_id, _, _) = richest.expect("group has at least two sessions");
+
+    let mut tx = pool.begin().await.map_err(AppError::from)?;
+    for session_id in unique.iter().filter(|id| ***id != keep_id) {
+        // Adopt the duplicate's audio if the kept session has none
+        sqlx::query(
+            "UPDATE conversation_sessions \
+             SET file_path = (SELECT file_path FROM conversation_sessions WHERE id = ?) \
+             WHERE id = ? AND file_path IS NULL",
+        )
+        .bind(session_id.as_str())
+        .bind(&keep_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to consolidate session audio: {}", e)))?;
+
+        crate::storage_commands::delete_session_rows(&mut tx, session_id)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to remove duplicate session: {}", e)))?;
+    }
+    tx.commit().await.map_err(|e| AppError::Database(format!("Failed to merge duplicates: {}", e)))?;
+
+    Ok(keep_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis_commands::MarkerType;
+    use crate::test_support::{insert, marker, memory_pool, segment, session};
+
+    fn fingerprint(session_id: &str, audio_checksum: Option<&str>, text: &str) -> SessionFingerprint {
+        SessionFingerprint {
+            session_id: session_id.to_string(),
+            audio_checksum: audio_checksum.map(str::to_string),
+            shingles: transcript_shingles(text),
+        }
+    }
+
+    #[test]
+    fn sessions_group_by_audio_checksum() {
+        let fingerprints = [
+            fingerprint("c", Some("aaaa"), "we talked about work"),
+            fingerprint("a", Some("aaaa"), "a completely different conversation"),
+            fingerprint("b", Some("bbbb"), "nothing in common here at all"),
+            fingerprint("d", None, "no audio for this one"),
+        ];
+
+        assert_eq!(group_duplicates(&fingerprints, 0.9), vec![vec!["a".to_string(), "c".to_string()]]);
+    }
+
+    #[test]
+    fn near_identical_transcripts_group_transitively() {
+        let text = "we talked about the move and how the new job is going so far";
+        let fingerprints = [
+            fingerprint("a", Some("aaaa"), text),
+            fingerprint("b", None, &format!("{}.", text.to_uppercase())),
+            fingerprint("c", Some("aaaa"), "unrelated words only"),
+            fingerprint("d", None, "something else entirely, said by someone else"),
+        ];
+
+        let groups = group_duplicates(&fingerprints, 0.9);
+
+        assert_eq!(groups, vec![vec!["a".to_string(), "b".to_string(), "c".to_string()]]);
+    }
+
+    #[tokio::test]
+    async fn merge_keeps_the_richest_session_and_its_audio() {
+        let pool = memory_pool().await;
+        let mut sparse = session("sparse", "Copy");
+        sparse.file_path = Some("/recordings/original.wav".to_string());
+        insert(&pool, &sparse).await;
+        insert(&pool, &session("rich", "Original")).await;
+        crate::storage_commands::write_segments(
+            &mut pool.acquire().await.unwrap(),
+            "rich",
+            &[segment("SPEAKER_00", 0.0, 1.0, "hello"), segment("SPEAKER_01", 1.0, 2.0, "hi")],
+        )
+        .await
+        .unwrap();
+
+        let kept = merge_group(&pool, &["sparse".to_string(), "rich".to_string()]).await.unwrap();
+
+        assert_eq!(kept, "rich");
+        let kept = crate::storage_commands::fetch_session(&pool, "rich").await.unwrap().unwrap();
+        assert_eq!(kept.file_path.as_deref(), Some("/recordings/original.wav"));
+        assert!(crate::storage_commands::fetch_session(&pool, "sparse").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn merge_needs_two_distinct_sessions() {
+        let pool = memory_pool().await;
+        let result = merge_group(&pool, &["a".to_string(), "a".to_string()]).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn merge_counts_markers_towards_richness() {
+        let pool = memory_pool().await;
+        insert(&pool, &session("transcribed", "Copy")).await;
+        insert(&pool, &session("analyzed", "Original")).await;
+        let mut conn = pool.acquire().await.unwrap();
+        crate::storage_commands::write_segments(
+            &mut conn,
+            "transcribed",
+            &[segment("SPEAKER_00", 0.0, 1.0, "hello"), segment("SPEAKER_01", 1.0, 2.0, "hi")],
+        )
+        .await
+        .unwrap();
+        crate::storage_commands::write_segments(&mut conn, "analyzed", &[segment("SPEAKER_00", 0.0, 2.0, "hello hi")])
+            .await
+            .unwrap();
+        let markers = [
+            marker("m1", MarkerType::Ato, 0.0, 1.0, 0.9, Some("SPEAKER_00")),
+            marker("m2", MarkerType::Sem, 0.5, 1.5, 0.8, Some("SPEAKER_00")),
+            marker("m3", MarkerType::Clu, 0.0, 2.0, 0.7, None),
+        ];
+        crate::analysis_commands::insert_markers(&mut conn, "analyzed", &markers.iter().collect::<Vec<_>>())
+            .await
+            .unwrap();
+        drop(conn);
+
+        let kept = merge_group(&pool, &["transcribed".to_string(), "analyzed".to_string()]).await.unwrap();
+
+        assert_eq!(kept, "analyzed");
+        assert_eq!(crate::analysis_commands::load_markers(&pool, "analyzed").await.unwrap().len(), 3);
+        assert!(crate::storage_commands::fetch_session(&pool, "transcribed").await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,219 @@
+use tauri::State;
+use sqlx::Row;
+use chrono::Utc;
+
+use crate::error::AppError;
+use crate::state::AppState;
+
+/// Dimensionality of the local hashed bag-of-words embedding
+const EMBEDDING_DIMENSIONS: usize = 512;
+
+const MAX_TOP_K: usize = 100;
+
+/// Words too common to carry topical meaning
+const EMBEDDING_STOP_WORDS: &[&str] = &[
+    "the", "and", "that", "this", "with", "for", "are", "was", "but", "not", "you", "have",
+    "what", "there", "they", "just", "about", "like", "der", "die", "das", "und", "ich",
+    "nicht", "ist", "mit", "sie", "ein", "eine", "auch", "aber",
+];
+
+/// FNV-1a; stable across builds, unlike `DefaultHasher`, so stored vectors stay comparable
+fn stable_hash(token: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in token.as_bytes() {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+/// Compute an L2-normalized document embedding locally via feature hashing.
+/// This is a lexical model: it captures shared vocabulary, not paraphrase.
+pub fn embed_text(text: &str) -> Vec<f32> {
+    let mut counts = vec![0f32; EMBEDDING_DIMENSIONS];
+
+    let tokens = text
+        .split(|c: char| !c.is_alphanumeric())
+        .map(|token| token.to_lowercase())
+        .filter(|token| token.chars().count() > 2)
+        .filter(|token| !EMBEDDING_STOP_WORDS.contains(&token.as_str()));
+
+    for token in tokens {
+        let hash = stable_hash(&token);
+        let bucket = (hash % EMBEDDING_DIMENSIONS as u64) as usize;
+        // The sign bit spreads collisions so they cancel out instead of piling up
+        let sign = if (hash >> 63) == 0 { 1.0 } else { -1.0 };
+        counts[bucket] += sign;
+    }
+
+    // Sublinear term frequency keeps one repeated word from dominating a session
+    let mut vector: Vec<f32> = counts
+        .iter()
+        .map(|c| c.signum() * (1.0 + c.abs()).ln())
+        .collect();
+
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|v| *v /= norm);
+    }
+    vector
+}
+
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f64 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+
+    let dot: f64 = a.iter().zip(b).map(|(x, y)| (*x as f64) * (*y as f64)).sum();
+    let norm_a = a.iter().map(|x| (*x as f64).powi(2)).sum::<f64>().sqrt();
+    let norm_b = b.iter().map(|x| (*x as f64).powi(2)).sum::<f64>().sqrt();
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+/// Rank stored `(session_id, vector)` pairs by similarity to `query`, best first
+pub fn rank_by_similarity(
+    query: &[f32],
+    candidates: Vec<(String, Vec<f32>)>,
+    top_k: usize
+) -> Vec<(String, f64)> {
+    let mut scored: Vec<(String, f64)> = candidates
+        .into_iter()
+        .map(|(session_id, vector)| {
+            let score = cosine_similarity(query, &vector);
+            (session_id, score)
+        })
+        .collect();
+
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+    scored.truncate(top_k);
+    scored
+}
+
+#[tauri::command]
+pub async fn index_session_embedding(
+    state: State<'_, AppState>,
+    session_id: String
+) -> Result<String, AppError> {
+    log::info!("Indexing transcript embedding for session: {}", session_id);
+
+    let segments = crate::storage_commands::load_segments(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load transcript: {}", e)))?;
+
+    if segments.is_empty() {
+        return Err(AppError::Validation("Session has no transcript to index".to_string()));
+    }
+
+    let document = segments
+        .iter()
+        .map(|segment| segment.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let vector = embed_text(&document);
+    let encoded = serde_json::to_string(&vector)
+        .map_err(|e| AppError::Internal(format!("Failed to encode embedding: {}", e)))?;
+
+    // Re-indexing replaces the previous vector for the session
+    sqlx::query(
+        "INSERT INTO session_embeddings (session_id, dimensions, vector, updated_at) \
+         VALUES (?, ?, ?, ?) \
+         ON CONFLICT(session_id) DO UPDATE SET \
+         dimensions = excluded.dimensions, vector = excluded.vector, updated_at = excluded.updated_at",
+    )
+    .bind(&session_id)
+    .bind(vector.len() as i64)
+    .bind(encoded)
+    .bind(Utc::now().to_rfc3339())
+    .execute(&state.db)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to store embedding: {}", e)))?;
+
+    Ok("Session embedding indexed successfully".to_string())
+}
+
+#[tauri::command]
+pub async fn semantic_search(
+    state: State<'_, AppState>,
+    query: String,
+    top_k: usize
+) -> Result<Vec<(String, f64)>, AppError> {
+    log::info!("Running semantic search with top_k: {}", top_k);
+
+    if query.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let rows = sqlx::query("SELECT session_id, vector FROM session_embeddings")
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load embeddings: {}", e)))?;
+
+    let mut candidates = Vec::with_capacity(rows.len());
+    for row in rows {
+        let session_id: String = row.try_get("session_id").map_err(AppError::from)?;
+        let encoded: String = row.try_get("vector").map_err(AppError::from)?;
+        match serde_json::from_str::<Vec<f32>>(&encoded) {
+            Ok(vector) => candidates.push((session_id, vector)),
+            Err(e) => log::warn!("Skipping unreadable embedding for session {}: {}", session_id, e),
+        }
+    }
+
+    let query_vector = embed_text(&query);
+    Ok(rank_by_similarity(&query_vector, candidates, top_k.clamp(1, MAX_TOP_K)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn candidates_rank_by_cosine_similarity() {
+        // Stub vectors instead of embeddings, so the expected order is plain geometry
+        let query = [1.0, 0.0, 0.0];
+        let candidates = vec![
+            ("orthogonal".to_string(), vec![0.0, 1.0, 0.0]),
+            ("same".to_string(), vec![2.0, 0.0, 0.0]),
+            ("opposite".to_string(), vec![-1.0, 0.0, 0.0]),
+            ("close".to_string(), vec![1.0, 1.0, 0.0]),
+        ];
+
+        let ranked = rank_by_similarity(&query, candidates, 10);
+
+        let order: Vec<&str> = ranked.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(order, ["same", "close", "orthogonal", "opposite"]);
+        assert!((ranked[0].1 - 1.0).abs() < 1e-9);
+        assert!((ranked[1].1 - std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-9);
+        assert!((ranked[3].1 + 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn ranking_keeps_top_k() {
+        let candidates = (0..5).map(|i| (i.to_string(), vec![1.0, i as f32])).collect();
+        assert_eq!(rank_by_similarity(&[1.0, 0.0], candidates, 2).len(), 2);
+    }
+
+    #[test]
+    fn mismatched_or_empty_vectors_score_zero() {
+        assert_eq!(cosine_similarity(&[1.0, 0.0], &[1.0, 0.0, 0.0]), 0.0);
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 0.0]), 0.0);
+    }
+
+    #[test]
+    fn shared_vocabulary_ranks_first() {
+        let query = embed_text("sleep problems and insomnia");
+        let candidates = vec![
+            ("budget".to_string(), embed_text("quarterly budget forecast for the sales team")),
+            ("sleep".to_string(), embed_text("her insomnia got worse, sleep problems every night")),
+        ];
+
+        let ranked = rank_by_similarity(&query, candidates, 10);
+
+        assert_eq!(ranked[0].0, "sleep");
+        assert!(ranked[0].1 > ranked[1].1);
+    }
+}
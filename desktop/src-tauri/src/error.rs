@@ -0,0 +1,149 @@
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+
+/// Error returned by commands; reaches the frontend as `{ kind, code, message }`
+#[derive(Debug)]
+pub enum AppError {
+    /// The requested session, segment, file or run doesn't exist
+    NotFound(String),
+    Database(String),
+    Io(String),
+    /// A Python pipeline script failed to start or exited unsuccessfully
+    Python(String),
+    /// No working Python interpreter was found; the user needs to install Python
+    PythonMissing(String),
+    /// A Python pipeline script ran past its time limit and was killed
+    Timeout(String),
+    /// The request itself was invalid; retrying it unchanged will fail again
+    Validation(String),
+    Audio(String),
+    /// The operation was cancelled before it finished
+    Cancelled(String),
+    /// The passphrase given to unlock the database doesn't open it
+    WrongPassphrase(String),
+    Internal(String),
+}
+
+impl AppError {
+    /// Stable identifier the frontend branches on
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AppError::NotFound(_) => "not_found",
+            AppError::Database(_) => "database",
+            AppError::Io(_) => "io",
+            AppError::Python(_) => "python",
+            AppError::PythonMissing(_) => "python_missing",
+            AppError::Timeout(_) => "timeout",
+            AppError::Validation(_) => "validation",
+            AppError::Audio(_) => "audio",
+            AppError::Cancelled(_) => "cancelled",
+            AppError::WrongPassphrase(_) => "wrong_passphrase",
+            AppError::Internal(_) => "internal",
+        }
+    }
+
+    /// Machine-readable code sent alongside `kind`. Codes are never renamed or
+    /// reused, so the frontend can switch on them across releases.
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::NotFound(_) => "E_NOT_FOUND",
+            AppError::Database(_) => "E_DATABASE",
+            AppError::Io(_) => "E_IO",
+            AppError::Python(_) => "E_PYTHON",
+            AppError::PythonMissing(_) => "E_PYTHON_MISSING",
+            AppError::Timeout(_) => "E_TIMEOUT",
+            AppError::Validation(_) => "E_VALIDATION",
+            AppError::Audio(_) => "E_AUDIO",
+            AppError::Cancelled(_) => "E_CANCELLED",
+            AppError::WrongPassphrase(_) => "E_WRONG_PASSPHRASE",
+            AppError::Internal(_) => "E_INTERNAL",
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            AppError::NotFound(message)
+            | AppError::Database(message)
+            | AppError::Io(message)
+            | AppError::Python(message)
+            | AppError::PythonMissing(message)
+            | AppError::Timeout(message)
+            | AppError::Validation(message)
+            | AppError::Audio(message)
+            | AppError::Cancelled(message)
+            | AppError::WrongPassphrase(message)
+            | AppError::Internal(message) => message,
+        }
+    }
+}
+
+impl std::fmt::Display for AppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("AppError", 3)?;
+        state.serialize_field("kind", self.kind())?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", self.message())?;
+        state.end()
+    }
+}
+
+impl From<sqlx::Error> for AppError {
+    fn from(error: sqlx::Error) -> Self {
+        match error {
+            sqlx::Error::RowNotFound => AppError::NotFound(error.to_string()),
+            other => AppError::Database(other.to_string()),
+        }
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(error: std::io::Error) -> Self {
+        match error.kind() {
+            std::io::ErrorKind::NotFound => AppError::NotFound(error.to_string()),
+            _ => AppError::Io(error.to_string()),
+        }
+    }
+}
+
+/// Error for a Python process that could not be spawned. A missing interpreter
+/// file means Python went away after it was resolved.
+pub fn python_spawn_error(error: std::io::Error) -> AppError {
+    let message = format!("Failed to spawn Python process: {}", error);
+    match error.kind() {
+        std::io::ErrorKind::NotFound => AppError::PythonMissing(message),
+        _ => AppError::Python(message),
+    }
+}
+
+impl From<crate::python_integration::PythonResult> for AppError {
+    /// A finished but unsuccessful run, described by its exit code and stderr
+    fn from(result: crate::python_integration::PythonResult) -> Self {
+        let exit = result
+            .exit_code
+            .map(|code| format!("exit code {}", code))
+            .unwrap_or_else(|| "terminated by signal".to_string());
+        AppError::Python(format!("Python script failed ({}): {}", exit, result.stderr.trim()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors_serialize_with_kind_code_and_message() {
+        let json = serde_json::to_value(AppError::NotFound("session not found".to_string())).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "kind": "not_found", "code": "E_NOT_FOUND", "message": "session not found" })
+        );
+    }
+}
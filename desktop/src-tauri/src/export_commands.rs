@@ -1,5 +1,22 @@
-use tauri::State;
+use tauri::{Emitter, State};
 use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use futures::TryStreamExt;
+use tokio::io::{AsyncWriteExt, BufWriter};
+use base64::Engine;
+use std::path::PathBuf;
+
+use crate::error::AppError;
+use crate::state::AppState;
+
+/// Rows between progress log lines while streaming large exports
+const EXPORT_LOG_INTERVAL: u64 = 10_000;
+
+/// Output buffered before it is written out while streaming large exports
+const EXPORT_BUFFER_BYTES: usize = 64 * 1024;
+
+/// Code system for TransRapport-specific FHIR codes and identifiers
+const FHIR_SYSTEM: &str = "urn:transrapport";
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ReportTemplate {
@@ -7,6 +24,9 @@ pub struct ReportTemplate {
     pub name: String,
     pub template_type: String, // "therapy", "legal", "business"
     pub description: String,
+    /// Section order; falls back to the order for `template_type` when empty
+    #[serde(default)]
+    pub sections: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -15,52 +35,1924 @@ pub struct ExportOptions {
     pub include_markers: bool,
     pub include_rapport: bool,
     pub include_transcript: bool,
-    pub confidentiality_level: String,
+    /// Falls back to the `default_confidentiality_level` setting
+    #[serde(default)]
+    pub confidentiality_level: Option<String>,
+    /// Scrub client names from the exported copy, see `Redactor`
+    #[serde(default)]
+    pub redact: bool,
+}
+
+/// Order in which a template lays out the sections of a report
+fn report_section_order(template: &ReportTemplate) -> Vec<&str> {
+    if !template.sections.is_empty() {
+        return template.sections.iter().map(String::as_str).collect();
+    }
+    match template.template_type.as_str() {
+        "therapy" => vec!["rapport", "markers", "transcript"],
+        "legal" => vec!["transcript", "markers", "rapport"],
+        _ => vec!["markers", "rapport", "transcript"],
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GeneratedReport {
+    pub file_path: String,
+    pub size_bytes: u64,
+}
+
+/// Progress of a report export, emitted as an `export-progress` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportProgress {
+    pub session_id: String,
+    pub stage: String, // "loading", "transcript", "markers", "rapport", "rendering", "writing", "completed", "failed"
+    pub fraction: f64,
+}
+
+/// Share of a report export spent loading and laying out its sections
+const SECTIONS_SHARE: f64 = 0.6;
+
+pub fn emit_export_progress(app: &tauri::AppHandle, session_id: &str, stage: &str, fraction: f64) {
+    let progress = ExportProgress {
+        session_id: session_id.to_string(),
+        stage: stage.to_string(),
+        fraction,
+    };
+    if let Err(e) = app.emit("export-progress", &progress) {
+        log::warn!("Failed to emit export progress: {}", e);
+    }
+}
+
+/// Build a session's report in `export_options.format` and return the file bytes,
+/// emitting `export-progress` up to the "rendering" stage
+pub async fn render_session_report(
+    app: &tauri::AppHandle,
+    pool: &SqlitePool,
+    settings: &crate::settings::AppSettings,
+    session_id: &str,
+    template: &ReportTemplate,
+    export_options: &ExportOptions
+) -> Result<Vec<u8>, AppError> {
+    use crate::report_pdf::ReportBlock;
+
+    if !matches!(export_options.format.as_str(), "pdf" | "docx") {
+        return Err(AppError::Validation(format!("Unsupported report format: {}", export_options.format)));
+    }
+    emit_export_progress(app, session_id, "loading", 0.0);
+    let session = crate::storage_commands::fetch_session(pool, session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load session: {}", e)))?
+        .ok_or_else(|| AppError::NotFound(format!("Session not found: {}", session_id)))?;
+    
+    let mut blocks = vec![
+        ReportBlock::Title(template.name.clone()),
+        ReportBlock::Paragraph(format!("Session: {}", session.name)),
+        ReportBlock::Paragraph(format!("Type: {}", session.session_type)),
+        ReportBlock::Paragraph(format!("Recorded: {}", session.created_at.format("%Y-%m-%d %H:%M UTC"))),
+    ];
+    // DOCX reports are edited afterwards, so a section without data is left out;
+    // a PDF is final and must contain everything that was asked for
+    let omit_missing = export_options.format == "docx";
+    if let Some(duration) = session.duration {
+        blocks.push(ReportBlock::Paragraph(format!("Duration: {}", format_clock(duration))));
+    }
+    let confidentiality = export_options
+        .confidentiality_level
+        .clone()
+        .unwrap_or_else(|| settings.default_confidentiality_level.clone());
+    blocks.push(ReportBlock::Paragraph(format!("Confidentiality: {}", confidentiality)));
+    blocks.push(ReportBlock::Paragraph(format!("Generated: {}", chrono::Utc::now().format("%Y-%m-%d %H:%M UTC"))));
+    
+    let sections = report_section_order(template);
+    for (index, section) in sections.iter().copied().enumerate() {
+        emit_export_progress(app, session_id, section, 0.1 + SECTIONS_SHARE * index as f64 / sections.len() as f64);
+        match section {
+            "transcript" if export_options.include_transcript => {
+                let segments = crate::storage_commands::load_segments(pool, session_id)
+                    .await
+                    .map_err(|e| AppError::Database(format!("Failed to load transcript: {}", e)))?;
+                if segments.is_empty() {
+                    if omit_missing {
+                        log::warn!("Omitting transcript section, session {} has no transcript", session_id);
+                        continue;
+                    }
+                    return Err(AppError::Validation("Session has no transcript to include in the report".to_string()));
+                }
+                blocks.push(ReportBlock::Heading("Transcript".to_string()));
+                blocks.extend(segments.iter().map(|segment| {
+                    ReportBlock::Paragraph(format!(
+                        "[{}] {}: {}",
+                        format_clock(segment.start_time),
+                        segment.speaker_label,
+                        segment.text
+                    ))
+                }));
+            }
+            "markers" if export_options.include_markers => {
+                let markers = crate::analysis_commands::load_markers(pool, session_id)
+                    .await
+                    .map_err(|e| AppError::Database(format!("Failed to load markers: {}", e)))?;
+                if markers.is_empty() {
+                    if omit_missing {
+                        log::warn!("Omitting markers section, session {} has no markers", session_id);
+                        continue;
+                    }
+                    return Err(AppError::Validation("Session has no markers to include in the report".to_string()));
+                }
+                blocks.push(ReportBlock::Heading("Markers".to_string()));
+                blocks.push(ReportBlock::Table {
+                    columns: ["Time", "Marker", "Confidence", "Speaker", "Explanation"]
+                        .map(String::from)
+                        .to_vec(),
+                    rows: markers
+                        .iter()
+                        .map(|marker| {
+                            vec![
+                                format_clock(marker.start_time),
+                                marker.marker_type.to_string(),
+                                format!("{:.0}%", marker.confidence * 100.0),
+                                marker.speaker.clone().unwrap_or_default(),
+                                marker.explanation.clone(),
+                            ]
+                        })
+                        .collect(),
+                });
+            }
+            "rapport" if export_options.include_rapport => {
+                let indicators = crate::analysis_commands::load_rapport(pool, session_id)
+                    .await
+                    .map_err(|e| AppError::Database(format!("Failed to load rapport: {}", e)))?;
+                if indicators.is_empty() {
+                    if omit_missing {
+                        log::warn!("Omitting rapport section, session {} has no rapport data", session_id);
+                        continue;
+                    }
+                    return Err(AppError::Validation("Session has no rapport data to include in the report".to_string()));
+                }
+                let average = indicators.iter().map(|indicator| indicator.value).sum::<f64>() / indicators.len() as f64;
+                blocks.push(ReportBlock::Heading("Rapport".to_string()));
+                blocks.push(ReportBlock::Paragraph(format!("Average rapport: {:+.2}", average)));
+                blocks.push(ReportBlock::Table {
+                    columns: ["Time", "Rapport", "Trend"].map(String::from).to_vec(),
+                    rows: indicators
+                        .iter()
+                        .map(|indicator| {
+                            vec![
+                                format_clock(indicator.timestamp),
+                                format!("{:+.2}", indicator.value),
+                                indicator.trend.clone(),
+                            ]
+                        })
+                        .collect(),
+                });
+            }
+            _ => {}
+        }
+    }
+    
+    if export_options.redact {
+        let redactor = Redactor::new(session.client_reference.as_deref(), &settings.redaction_terms);
+        for block in &mut blocks {
+            block.redact(&redactor);
+        }
+    }
+    
+    emit_export_progress(app, session_id, "rendering", 0.1 + SECTIONS_SHARE);
+    let report = match export_options.format.as_str() {
+        "docx" => crate::report_docx::render_report(&confidentiality, &blocks),
+        _ => crate::report_pdf::render_report(&template.name, &confidentiality, &blocks),
+    };
+    report.map_err(AppError::Internal)
 }
 
 #[tauri::command]
 pub async fn generate_report(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    settings: State<'_, crate::settings::SettingsStore>,
     session_id: String,
     template_id: String,
     export_options: ExportOptions
-) -> Result<String, String> {
-    // TODO: Implement professional report generation
+) -> Result<GeneratedReport, AppError> {
     log::info!("Generating report for session: {} with template: {}", 
                session_id, template_id);
     
-    // TODO: Use template engine to generate report
-    let output_path = format!("/tmp/report_{}_{}.{}", 
-                             session_id, template_id, export_options.format);
+    let result = async {
+        let template = crate::report_templates::find_template(&app, &template_id)?;
+        let report =
+            render_session_report(&app, &state.db, &settings.get(), &session_id, &template, &export_options).await?;
+        emit_export_progress(&app, &session_id, "writing", 0.9);
+        let output_path = export_directory(&app, &settings.get())
+            .await?
+            .join(format!("report_{}_{}.{}", session_id, template_id, export_options.format))
+            .to_string_lossy()
+            .to_string();
+        tokio::fs::write(&output_path, &report)
+            .await
+            .map_err(|e| AppError::Io(format!("Failed to write report: {}", e)))?;
+        
+        log::info!("Wrote {} byte report to: {}", report.len(), output_path);
+        Ok::<_, AppError>(GeneratedReport {
+            file_path: output_path,
+            size_bytes: report.len() as u64,
+        })
+    }
+    .await;
     
-    Ok(output_path)
+    match &result {
+        Ok(_) => emit_export_progress(&app, &session_id, "completed", 1.0),
+        Err(_) => emit_export_progress(&app, &session_id, "failed", 1.0),
+    }
+    result
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BundleSessionStatus {
+    pub session_id: String,
+    pub status: String, // "exported", "failed"
+    /// Archive entries written for the session
+    pub files: Vec<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionBundle {
+    pub file_path: String,
+    pub sessions: Vec<BundleSessionStatus>,
+}
+
+/// Archive entries for one session, `<session_id>/report.<format>` and
+/// `<session_id>/transcript.txt` when the session has a transcript
+async fn bundle_session_files(
+    app: &tauri::AppHandle,
+    pool: &SqlitePool,
+    settings: &crate::settings::AppSettings,
+    templates: &[ReportTemplate],
+    session_id: &str,
+    export_options: &ExportOptions
+) -> Result<Vec<(String, Vec<u8>)>, AppError> {
+    let session = crate::storage_commands::fetch_session(pool, session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load session: {}", e)))?
+        .ok_or_else(|| AppError::NotFound(format!("Session not found: {}", session_id)))?;
+
+    // Each session is laid out with the template named after its type
+    let template = templates
+        .iter()
+        .find(|template| template.id == session.session_type)
+        .ok_or_else(|| AppError::NotFound(format!("Report template not found: {}", session.session_type)))?;
+    let report = render_session_report(app, pool, settings, session_id, template, export_options).await?;
+    let mut files = vec![(format!("{}/report.{}", session_id, export_options.format), report)];
+
+    let segments = crate::storage_commands::load_segments(pool, session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load transcript: {}", e)))?;
+    if !segments.is_empty() {
+        let mut transcript = render_transcript_text(&segments, true);
+        if export_options.redact {
+            transcript = Redactor::new(session.client_reference.as_deref(), &settings.redaction_terms).redact(&transcript);
+        }
+        files.push((format!("{}/transcript.txt", session_id), transcript.into_bytes()));
+    }
+
+    Ok(files)
+}
+
+/// Zip the report and transcript of several sessions into one archive with a
+/// folder per session and a top-level `manifest.json`. Entries are written as
+/// each session finishes, so only one session is held in memory at a time, and
+/// a session that fails is recorded in the manifest instead of aborting the bundle.
+/// The bundle is cancellable through `cancel` under `operation_id` when one is
+/// given; a cancelled bundle is deleted.
+#[tauri::command]
+pub async fn export_sessions_bundle(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    settings: State<'_, crate::settings::SettingsStore>,
+    cancellations: State<'_, crate::cancellation::CancellationManager>,
+    session_ids: Vec<String>,
+    export_options: ExportOptions,
+    operation_id: Option<String>
+) -> Result<SessionBundle, AppError> {
+    log::info!("Exporting bundle of {} sessions as {}", session_ids.len(), export_options.format);
+    
+    if session_ids.is_empty() {
+        return Err(AppError::Validation("No sessions selected for the bundle".to_string()));
+    }
+    
+    let (operation_id, token) = match operation_id {
+        Some(operation_id) => {
+            let token = cancellations
+                .try_register_with_id(&operation_id)
+                .ok_or_else(|| AppError::Validation(format!("Operation already running: {}", operation_id)))?;
+            (operation_id, token)
+        }
+        None => cancellations.register(),
+    };
+    let settings = settings.get();
+    let result = write_sessions_bundle(&app, &state.db, &settings, session_ids, &export_options, &token).await;
+    cancellations.finish(&operation_id);
+    result
+}
+
+/// Write the bundle of `export_sessions_bundle`, checking `token` before each session
+async fn write_sessions_bundle(
+    app: &tauri::AppHandle,
+    pool: &SqlitePool,
+    settings: &crate::settings::AppSettings,
+    session_ids: Vec<String>,
+    export_options: &ExportOptions,
+    token: &crate::cancellation::CancellationToken
+) -> Result<SessionBundle, AppError> {
+    use std::io::Write;
+
+    let templates = crate::report_templates::load_templates(&crate::report_templates::template_directories(app));
+    let created_at = chrono::Utc::now();
+    let output_path = export_directory(app, settings)
+        .await?
+        .join(format!("sessions_{}.zip", created_at.format("%Y%m%dT%H%M%SZ")));
+    let file = std::fs::File::create(&output_path)
+        .map_err(|e| AppError::Io(format!("Failed to create bundle: {}", e)))?;
+    let mut archive = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    
+    let mut statuses = Vec::with_capacity(session_ids.len());
+    for session_id in session_ids {
+        if let Err(e) = token.check() {
+            drop(archive);
+            if let Err(remove_error) = std::fs::remove_file(&output_path) {
+                log::warn!("Failed to remove cancelled bundle {}: {}", output_path.display(), remove_error);
+            }
+            return Err(e);
+        }
+        match bundle_session_files(app, pool, settings, &templates, &session_id, export_options).await {
+            Ok(files) => {
+                emit_export_progress(app, &session_id, "writing", 0.9);
+                let mut names = Vec::with_capacity(files.len());
+                for (name, contents) in files {
+                    archive
+                        .start_file(name.as_str(), options)
+                        .and_then(|_| archive.write_all(&contents).map_err(zip::result::ZipError::from))
+                        .map_err(|e| AppError::Io(format!("Failed to write bundle entry {}: {}", name, e)))?;
+                    names.push(name);
+                }
+                emit_export_progress(app, &session_id, "completed", 1.0);
+                statuses.push(BundleSessionStatus {
+                    session_id,
+                    status: "exported".to_string(),
+                    files: names,
+                    error: None,
+                });
+            }
+            Err(e) => {
+                log::warn!("Leaving session {} out of the bundle: {}", session_id, e);
+                emit_export_progress(app, &session_id, "failed", 1.0);
+                statuses.push(BundleSessionStatus {
+                    session_id,
+                    status: "failed".to_string(),
+                    files: Vec::new(),
+                    error: Some(e.to_string()),
+                });
+            }
+        }
+    }
+    
+    let manifest = serde_json::json!({
+        "created_at": created_at.to_rfc3339(),
+        "format": export_options.format,
+        "redacted": export_options.redact,
+        "sessions": &statuses,
+    });
+    let manifest = serde_json::to_vec_pretty(&manifest)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize bundle manifest: {}", e)))?;
+    archive
+        .start_file("manifest.json", options)
+        .and_then(|_| archive.write_all(&manifest).map_err(zip::result::ZipError::from))
+        .and_then(|_| archive.finish().map(|_| ()))
+        .map_err(|e| AppError::Io(format!("Failed to write bundle: {}", e)))?;
+    
+    let file_path = output_path.to_string_lossy().to_string();
+    log::info!("Wrote session bundle to: {}", file_path);
+    Ok(SessionBundle {
+        file_path,
+        sessions: statuses,
+    })
+}
+
+/// Format seconds as `HH:MM:SS<separator>mmm`, `,` for SRT and `.` for WebVTT
+pub fn subtitle_timestamp(seconds: f64, separator: char) -> String {
+    let total_ms = (seconds.max(0.0) * 1000.0).round() as u64;
+    let total_secs = total_ms / 1000;
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        total_secs / 3600,
+        (total_secs % 3600) / 60,
+        total_secs % 60,
+        separator,
+        total_ms % 1000
+    )
+}
+
+/// SubRip cues numbered from 1, optionally prefixed with `Speaker:`
+pub fn render_srt(segments: &[crate::transcription_commands::SpeakerSegment], include_speakers: bool) -> String {
+    let mut output = String::new();
+    for (index, segment) in segments.iter().enumerate() {
+        let text = if include_speakers {
+            format!("{}: {}", segment.speaker_label, segment.text.trim())
+        } else {
+            segment.text.trim().to_string()
+        };
+        output.push_str(&format!(
+            "{}\n{} --> {}\n{}\n\n",
+            index + 1,
+            subtitle_timestamp(segment.start_time, ','),
+            subtitle_timestamp(segment.end_time, ','),
+            text
+        ));
+    }
+    output
+}
+
+/// Escape the characters WebVTT cue text treats as markup
+fn vtt_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// WebVTT cues, optionally tagged with a `<v Speaker>` voice span
+pub fn render_vtt(segments: &[crate::transcription_commands::SpeakerSegment], include_speakers: bool) -> String {
+    let mut output = String::from("WEBVTT\n\n");
+    for segment in segments {
+        let text = vtt_escape(segment.text.trim());
+        let text = if include_speakers {
+            format!("<v {}>{}", vtt_escape(&segment.speaker_label), text)
+        } else {
+            text
+        };
+        output.push_str(&format!(
+            "{} --> {}\n{}\n\n",
+            subtitle_timestamp(segment.start_time, '.'),
+            subtitle_timestamp(segment.end_time, '.'),
+            text
+        ));
+    }
+    output
+}
+
+/// One `[start–end] Speaker: text` line per segment
+pub fn render_transcript_text(
+    segments: &[crate::transcription_commands::SpeakerSegment],
+    include_speakers: bool
+) -> String {
+    segments
+        .iter()
+        .map(|segment| {
+            let span = format!("[{}–{}]", format_clock(segment.start_time), format_clock(segment.end_time));
+            if include_speakers {
+                format!("{} {}: {}\n", span, segment.speaker_label, segment.text.trim())
+            } else {
+                format!("{} {}\n", span, segment.text.trim())
+            }
+        })
+        .collect()
+}
+
+/// Configured export directory, defaulting to `AppPaths::exports`, created on
+/// first use since a configured one may not exist yet
+pub async fn export_directory(
+    app: &tauri::AppHandle,
+    settings: &crate::settings::AppSettings
+) -> Result<PathBuf, AppError> {
+    use tauri::Manager;
+
+    let dir = match &settings.export_directory {
+        Some(dir) => PathBuf::from(dir),
+        None => app.state::<crate::paths::AppPaths>().exports(),
+    };
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .map_err(|e| AppError::Io(format!("Failed to create export directory: {}", e)))?;
+    Ok(dir)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TranscriptExport {
+    pub file_path: String,
+    pub segment_count: usize,
 }
 
 #[tauri::command]
 pub async fn export_transcript(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    settings: State<'_, crate::settings::SettingsStore>,
     session_id: String,
     format: String, // "txt", "srt", "vtt", "json"
     include_speakers: bool
-) -> Result<String, String> {
-    // TODO: Implement transcript export in various formats
+) -> Result<TranscriptExport, AppError> {
     log::info!("Exporting transcript for session: {} in format: {}", 
                session_id, format);
     
-    let output_path = format!("/tmp/transcript_{}.{}", session_id, format);
+    if !matches!(format.as_str(), "txt" | "srt" | "vtt" | "json") {
+        return Err(AppError::Validation(format!("Unsupported transcript export format: {}", format)));
+    }
     
-    Ok(output_path)
+    let segments = crate::storage_commands::load_segments(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load transcript: {}", e)))?;
+    if segments.is_empty() {
+        return Err(AppError::Validation("Session has no transcript to export".to_string()));
+    }
+    
+    let contents = match format.as_str() {
+        "txt" => render_transcript_text(&segments, include_speakers),
+        "srt" => render_srt(&segments, include_speakers),
+        "vtt" => render_vtt(&segments, include_speakers),
+        // Word timings travel with each segment
+        _ => serde_json::to_string_pretty(&segments)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize transcript: {}", e)))?,
+    };
+    let output_path = export_directory(&app, &settings.get())
+        .await?
+        .join(format!("transcript_{}.{}", session_id, format))
+        .to_string_lossy()
+        .to_string();
+    tokio::fs::write(&output_path, contents)
+        .await
+        .map_err(|e| AppError::Io(format!("Failed to write transcript export: {}", e)))?;
+    verify_export(&settings, &output_path, &format).await?;
+    
+    Ok(TranscriptExport {
+        file_path: output_path,
+        segment_count: segments.len(),
+    })
+}
+
+/// Quote a CSV field when it contains a delimiter, quote or line break
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn marker_csv_row(marker: &crate::analysis_commands::MarkerEvent) -> String {
+    format!(
+        "{},{},{},{},{},{},{},{}\n",
+        csv_field(&marker.id),
+        csv_field(marker.marker_type.as_str()),
+        marker.start_time,
+        marker.end_time,
+        marker.confidence,
+        csv_field(marker.speaker.as_deref().unwrap_or("")),
+        csv_field(&marker.evidence),
+        csv_field(&marker.explanation)
+    )
+}
+
+/// Stream a session's markers to `output_path` row by row, never holding the full set
+pub async fn stream_markers_to_file(
+    pool: &SqlitePool,
+    session_id: &str,
+    format: &str,
+    marker_types: &[String],
+    output_path: &str
+) -> Result<u64, AppError> {
+    let file = tokio::fs::File::create(output_path)
+        .await
+        .map_err(|e| AppError::Io(format!("Failed to create export file: {}", e)))?;
+    write_markers(pool, session_id, format, marker_types, file).await
+}
+
+/// Stream a session's markers into `output`, holding at most one buffer of
+/// encoded rows at a time
+async fn write_markers<W: tokio::io::AsyncWrite + Unpin>(
+    pool: &SqlitePool,
+    session_id: &str,
+    format: &str,
+    marker_types: &[String],
+    output: W
+) -> Result<u64, AppError> {
+    let mut sql = format!(
+        "SELECT {} FROM marker_events WHERE session_id = ?",
+        crate::analysis_commands::MARKER_COLUMNS
+    );
+    if !marker_types.is_empty() {
+        let placeholders = vec!["?"; marker_types.len()].join(", ");
+        sql.push_str(&format!(" AND marker_type IN ({})", placeholders));
+    }
+    sql.push_str(" ORDER BY start_time");
+
+    let mut query = sqlx::query(&sql).bind(session_id);
+    for marker_type in marker_types {
+        query = query.bind(marker_type);
+    }
+
+    let mut writer = BufWriter::with_capacity(EXPORT_BUFFER_BYTES, output);
+
+    let header = match format {
+        "csv" => "id,marker_type,start_time,end_time,confidence,speaker,evidence,explanation\n",
+        "json" => "[",
+        _ => "",
+    };
+    writer.write_all(header.as_bytes()).await.map_err(AppError::from)?;
+
+    let mut rows = query.fetch(pool);
+    let mut written: u64 = 0;
+    while let Some(row) = rows
+        .try_next()
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to read markers: {}", e)))?
+    {
+        let marker = crate::analysis_commands::marker_from_row(&row)
+            .map_err(|e| AppError::Database(format!("Failed to decode marker: {}", e)))?;
+
+        let to_json = || serde_json::to_string(&marker).map_err(|e| AppError::Internal(e.to_string()));
+        let line = match format {
+            "csv" => marker_csv_row(&marker),
+            "json" => {
+                let prefix = if written == 0 { "" } else { "," };
+                format!("{}{}", prefix, to_json()?)
+            }
+            _ => format!("{}\n", to_json()?),
+        };
+        writer.write_all(line.as_bytes()).await.map_err(AppError::from)?;
+
+        written += 1;
+        if written % EXPORT_LOG_INTERVAL == 0 {
+            log::info!("Exported {} markers so far for session: {}", written, session_id);
+        }
+    }
+
+    if format == "json" {
+        writer.write_all(b"]").await.map_err(AppError::from)?;
+    }
+    writer.flush().await.map_err(|e| AppError::Io(format!("Failed to write export file: {}", e)))?;
+
+    Ok(written)
+}
+
+/// Number of fields in each CSV record, `None` when a quoted field never closes
+fn csv_record_widths(contents: &str) -> Option<Vec<usize>> {
+    let mut widths = Vec::new();
+    let mut fields = 1;
+    let mut in_quotes = false;
+
+    for c in contents.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields += 1,
+            '\n' if !in_quotes => {
+                widths.push(fields);
+                fields = 1;
+            }
+            _ => {}
+        }
+    }
+
+    (!in_quotes).then_some(widths)
+}
+
+/// Check a freshly written export is complete and parses as `format`.
+/// Every text format written here ends its last record with a newline, so a
+/// missing final newline also marks a truncated write.
+pub fn validate_export_contents(contents: &str, format: &str) -> Result<(), String> {
+    // A JSON Lines export of zero records is legitimately empty
+    if contents.is_empty() && format != "jsonl" {
+        return Err("export file is empty".to_string());
+    }
+
+    match format {
+        "json" => serde_json::from_str::<serde_json::Value>(contents)
+            .map(|_| ())
+            .map_err(|e| format!("export is not valid JSON: {}", e)),
+        "jsonl" => {
+            if !contents.is_empty() && !contents.ends_with('\n') {
+                return Err("export ends mid-record".to_string());
+            }
+            for (index, line) in contents.lines().enumerate() {
+                serde_json::from_str::<serde_json::Value>(line)
+                    .map_err(|e| format!("line {} is not valid JSON: {}", index + 1, e))?;
+            }
+            Ok(())
+        }
+        "csv" => {
+            if !contents.ends_with('\n') {
+                return Err("export ends mid-record".to_string());
+            }
+            let widths = csv_record_widths(contents)
+                .ok_or_else(|| "export ends inside a quoted field".to_string())?;
+            match widths.iter().position(|width| *width != widths[0]) {
+                Some(index) => Err(format!(
+                    "record {} has {} fields, header has {}",
+                    index + 1,
+                    widths[index],
+                    widths[0]
+                )),
+                None => Ok(()),
+            }
+        }
+        _ => {
+            if contents.ends_with('\n') {
+                Ok(())
+            } else {
+                Err("export ends mid-record".to_string())
+            }
+        }
+    }
+}
+
+/// Verify an export after writing when enabled in settings; a file failing the
+/// check is deleted so a corrupt export is never handed out
+pub async fn verify_export(
+    settings: &crate::settings::SettingsStore,
+    output_path: &str,
+    format: &str
+) -> Result<(), AppError> {
+    if !settings.get().verify_exports {
+        return Ok(());
+    }
+
+    let result = match tokio::fs::read_to_string(output_path).await {
+        Ok(contents) => validate_export_contents(&contents, format),
+        Err(e) => Err(format!("export file cannot be read back: {}", e)),
+    };
+
+    if let Err(problem) = result {
+        if let Err(e) = tokio::fs::remove_file(output_path).await {
+            log::warn!("Failed to remove corrupt export {}: {}", output_path, e);
+        }
+        return Err(AppError::Io(format!("Export verification failed for {}: {}", output_path, problem)));
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MarkerExport {
+    pub file_path: String,
+    pub row_count: u64,
 }
 
 #[tauri::command]
 pub async fn export_markers(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    settings: State<'_, crate::settings::SettingsStore>,
     session_id: String,
     format: String, // "csv", "json", "jsonl"
-    marker_types: Vec<String> // Filter by marker types
-) -> Result<String, String> {
-    // TODO: Implement marker events export
+    marker_types: Vec<String> // Filter by marker types, empty for all
+) -> Result<MarkerExport, AppError> {
     log::info!("Exporting markers for session: {} in format: {} with types: {:?}", 
                session_id, format, marker_types);
     
-    let output_path = format!("/tmp/markers_{}.{}", session_id, format);
+    if !matches!(format.as_str(), "csv" | "json" | "jsonl") {
+        return Err(AppError::Validation(format!("Unsupported marker export format: {}", format)));
+    }
+    
+    let output_path = export_directory(&app, &settings.get())
+        .await?
+        .join(format!("markers_{}.{}", session_id, format))
+        .to_string_lossy()
+        .to_string();
+    
+    let exported = stream_markers_to_file(&state.db, &session_id, &format, &marker_types, &output_path).await?;
+    verify_export(&settings, &output_path, &format).await?;
+    log::info!("Exported {} markers to {}", exported, output_path);
+    
+    Ok(MarkerExport {
+        file_path: output_path,
+        row_count: exported,
+    })
+}
+
+/// Format seconds as `HH:MM:SS` for human-readable transcripts
+pub fn format_clock(seconds: f64) -> String {
+    let total = seconds.max(0.0).floor() as u64;
+    format!("{:02}:{:02}:{:02}", total / 3600, (total % 3600) / 60, total % 60)
+}
+
+/// Build a FHIR R4 `DocumentReference` describing a session.
+/// The transcript is only embedded (base64, `text/plain`) when `transcript` is given.
+pub fn session_to_fhir(
+    session: &crate::storage_commands::ConversationSession,
+    transcript: Option<&[crate::transcription_commands::SpeakerSegment]>
+) -> serde_json::Value {
+    let created = session.created_at.to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+
+    let mut content = vec![serde_json::json!({
+        "attachment": {
+            "contentType": "application/x-transrapport-session",
+            "url": format!("{}:session:{}", FHIR_SYSTEM, session.id),
+            "title": session.name,
+            "creation": created,
+        }
+    })];
+
+    if let Some(file_path) = &session.file_path {
+        content.push(serde_json::json!({
+            "attachment": {
+                "contentType": "audio/wav",
+                "url": format!("file://{}", file_path),
+                "title": format!("{} (audio)", session.name),
+                "creation": created,
+            }
+        }));
+    }
+
+    if let Some(segments) = transcript {
+        let text = segments
+            .iter()
+            .map(|segment| format!("[{}] {}: {}", format_clock(segment.start_time), segment.speaker_label, segment.text))
+            .collect::<Vec<_>>()
+            .join("\n");
+        content.push(serde_json::json!({
+            "attachment": {
+                "contentType": "text/plain; charset=utf-8",
+                "language": "und",
+                "data": base64::engine::general_purpose::STANDARD.encode(text.as_bytes()),
+                "title": format!("{} (transcript)", session.name),
+                "creation": created,
+            }
+        }));
+    }
+
+    let mut period = serde_json::json!({ "start": created });
+    if let Some(duration) = session.duration {
+        let end = session.created_at + chrono::Duration::milliseconds((duration * 1000.0) as i64);
+        period["end"] = serde_json::json!(end.to_rfc3339_opts(chrono::SecondsFormat::Secs, true));
+    }
+
+    let mut resource = serde_json::json!({
+        "resourceType": "DocumentReference",
+        "id": session.id,
+        "identifier": [{
+            "system": format!("{}:session", FHIR_SYSTEM),
+            "value": session.id,
+        }],
+        "status": "current",
+        "docStatus": if session.status == "completed" { "final" } else { "preliminary" },
+        "type": {
+            "coding": [{
+                "system": format!("{}:session-type", FHIR_SYSTEM),
+                "code": session.session_type,
+            }],
+            "text": session.session_type,
+        },
+        "date": session.updated_at.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        "description": session.name,
+        "content": content,
+        "context": { "period": period },
+    });
+
+    if let Some(duration) = session.duration {
+        resource["extension"] = serde_json::json!([{
+            "url": format!("{}:fhir:StructureDefinition:duration-seconds", FHIR_SYSTEM),
+            "valueDecimal": duration,
+        }]);
+    }
+
+    if let Some(client_reference) = &session.client_reference {
+        resource["subject"] = serde_json::json!({
+            "identifier": {
+                "system": format!("{}:client-reference", FHIR_SYSTEM),
+                "value": client_reference,
+            }
+        });
+    }
+
+    resource
+}
+
+#[tauri::command]
+pub async fn export_fhir(
+    state: State<'_, AppState>,
+    session_id: String,
+    include_transcript: Option<bool>
+) -> Result<String, AppError> {
+    log::info!("Exporting FHIR DocumentReference for session: {}", session_id);
+    
+    let session = crate::storage_commands::fetch_session(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load session: {}", e)))?
+        .ok_or_else(|| AppError::NotFound(format!("Session not found: {}", session_id)))?;
+    
+    let transcript = if include_transcript.unwrap_or(false) {
+        Some(
+            crate::storage_commands::load_segments(&state.db, &session_id)
+                .await
+                .map_err(|e| AppError::Database(format!("Failed to load transcript: {}", e)))?,
+        )
+    } else {
+        None
+    };
+    
+    let resource = session_to_fhir(&session, transcript.as_deref());
+    serde_json::to_string_pretty(&resource).map_err(|e| AppError::Internal(format!("Failed to serialize FHIR resource: {}", e)))
+}
+
+/// Version of the session document format written by `export_session_json`;
+/// bump it whenever a key is added, renamed or changes meaning
+pub const SESSION_DOCUMENT_VERSION: u32 = 1;
+
+/// Everything stored about one session, for interchange with other tools
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionDocument {
+    pub version: u32,
+    pub exported_at: chrono::DateTime<chrono::Utc>,
+    pub session: crate::storage_commands::ConversationSession,
+    pub segments: Vec<crate::transcription_commands::SpeakerSegment>,
+    pub markers: Vec<crate::analysis_commands::MarkerEvent>,
+    pub rapport: Vec<crate::analysis_commands::RapportIndicator>,
+}
+
+#[tauri::command]
+pub async fn export_session_json(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    settings: State<'_, crate::settings::SettingsStore>,
+    session_id: String
+) -> Result<String, AppError> {
+    log::info!("Exporting session document for session: {}", session_id);
+    
+    let session = crate::storage_commands::fetch_session(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load session: {}", e)))?
+        .ok_or_else(|| AppError::NotFound(format!("Session not found: {}", session_id)))?;
+    let segments = crate::storage_commands::load_segments(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load transcript: {}", e)))?;
+    let markers = crate::analysis_commands::load_markers(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load markers: {}", e)))?;
+    let rapport = crate::analysis_commands::load_rapport(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load rapport curve: {}", e)))?;
+    
+    let document = SessionDocument {
+        version: SESSION_DOCUMENT_VERSION,
+        exported_at: chrono::Utc::now(),
+        session,
+        segments,
+        markers,
+        rapport,
+    };
+    let contents = serde_json::to_string_pretty(&document)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize session: {}", e)))?;
+    
+    let output_path = export_directory(&app, &settings.get())
+        .await?
+        .join(format!("session_{}.json", session_id))
+        .to_string_lossy()
+        .to_string();
+    tokio::fs::write(&output_path, contents)
+        .await
+        .map_err(|e| AppError::Io(format!("Failed to write session export: {}", e)))?;
+    verify_export(&settings, &output_path, "json").await?;
     
     Ok(output_path)
-}
\ No newline at end of file
+}
+
+/// Check a session document before anything is written, naming the first
+/// field that fails
+pub fn validate_session_document(document: &SessionDocument) -> Result<(), AppError> {
+    use crate::storage_commands::{BUSY_SESSION_STATUSES, SESSION_STATUSES, SESSION_TYPES};
+
+    let session = &document.session;
+    if session.name.trim().is_empty() {
+        return Err(AppError::Validation("session.name must not be empty".to_string()));
+    }
+    if !SESSION_TYPES.contains(&session.session_type.as_str()) {
+        return Err(AppError::Validation(format!(
+            "session.session_type must be one of: {}",
+            SESSION_TYPES.join(", ")
+        )));
+    }
+    if !SESSION_STATUSES.contains(&session.status.as_str()) {
+        return Err(AppError::Validation(format!(
+            "session.status must be one of: {}",
+            SESSION_STATUSES.join(", ")
+        )));
+    }
+    if BUSY_SESSION_STATUSES.contains(&session.status.as_str()) {
+        return Err(AppError::Validation(format!(
+            "session.status: cannot import a session that is still {}",
+            session.status
+        )));
+    }
+
+    for (index, segment) in document.segments.iter().enumerate() {
+        crate::transcription_commands::validate_segment_times(segment)
+            .map_err(|e| AppError::Validation(format!("segments[{}]: {}", index, e)))?;
+    }
+
+    let mut marker_ids = std::collections::HashSet::new();
+    for (index, marker) in document.markers.iter().enumerate() {
+        crate::analysis_commands::validate_marker(marker)
+            .map_err(|e| AppError::Validation(format!("markers[{}]: {}", index, e)))?;
+        if marker.id.trim().is_empty() || !marker_ids.insert(marker.id.as_str()) {
+            return Err(AppError::Validation(format!("markers[{}].id must be present and unique", index)));
+        }
+    }
+
+    let mut timestamps = std::collections::HashSet::new();
+    for (index, indicator) in document.rapport.iter().enumerate() {
+        if !indicator.timestamp.is_finite() || !indicator.value.is_finite() {
+            return Err(AppError::Validation(format!("rapport[{}]: timestamp and value must be numbers", index)));
+        }
+        if !timestamps.insert(indicator.timestamp.to_bits()) {
+            return Err(AppError::Validation(format!("rapport[{}].timestamp is repeated", index)));
+        }
+    }
+
+    Ok(())
+}
+
+/// Restore a document written by `export_session_json` as a new session. The
+/// session gets a fresh id so importing never collides with existing data, and
+/// everything is written in one transaction.
+#[tauri::command]
+pub async fn import_session_json(
+    state: State<'_, AppState>,
+    file_path: String
+) -> Result<crate::storage_commands::ConversationSession, AppError> {
+    log::info!("Importing session document: {}", file_path);
+    
+    let contents = tokio::fs::read_to_string(&file_path)
+        .await
+        .map_err(|e| AppError::Io(format!("Failed to read {}: {}", file_path, e)))?;
+    let value: serde_json::Value = serde_json::from_str(&contents)
+        .map_err(|e| AppError::Validation(format!("{} is not valid JSON: {}", file_path, e)))?;
+    
+    // Check the version first so an incompatible file isn't reported as a field error
+    match value.get("version").and_then(|version| version.as_u64()) {
+        Some(version) if version == SESSION_DOCUMENT_VERSION as u64 => {}
+        Some(version) => {
+            return Err(AppError::Validation(format!(
+                "version {} is not supported (expected {})",
+                version, SESSION_DOCUMENT_VERSION
+            )))
+        }
+        None => return Err(AppError::Validation("version is missing or not a number".to_string())),
+    }
+    let mut document: SessionDocument = serde_json::from_value(value)
+        .map_err(|e| AppError::Validation(format!("Invalid session document: {}", e)))?;
+    validate_session_document(&document)?;
+    
+    let original_id = std::mem::replace(&mut document.session.id, uuid::Uuid::new_v4().to_string());
+    document.session.updated_at = chrono::Utc::now();
+    // Segment ids are unique across sessions, so the copies get new ones too
+    for segment in &mut document.segments {
+        segment.id = None;
+    }
+    let session = document.session;
+    
+    let mut tx = state.db.begin().await.map_err(AppError::from)?;
+    crate::storage_commands::insert_session(&mut tx, &session)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to create session: {}", e)))?;
+    crate::storage_commands::write_segments(&mut tx, &session.id, &document.segments)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to save transcript: {}", e)))?;
+    let markers: Vec<&crate::analysis_commands::MarkerEvent> = document.markers.iter().collect();
+    crate::analysis_commands::insert_markers(&mut tx, &session.id, &markers)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to save markers: {}", e)))?;
+    crate::analysis_commands::replace_rapport(&mut tx, &session.id, &document.rapport)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to save rapport curve: {}", e)))?;
+    tx.commit().await.map_err(|e| AppError::Database(format!("Failed to import session: {}", e)))?;
+    
+    log::info!(
+        "Imported session {} as {} with {} segments, {} markers and {} rapport points",
+        original_id,
+        session.id,
+        document.segments.len(),
+        document.markers.len(),
+        document.rapport.len()
+    );
+    Ok(session)
+}
+
+/// Placeholder for empty CoNLL columns
+const CONLL_EMPTY: &str = "_";
+
+/// Render tokens one per line as `token, start, end, speaker, marker` columns,
+/// with a blank line between segments. A token covered by several markers is
+/// tagged with the most confident one.
+pub fn render_conll(
+    segments: &[crate::transcription_commands::SpeakerSegment],
+    markers: &[crate::analysis_commands::MarkerEvent]
+) -> String {
+    let mut output = String::from("# token\tstart\tend\tspeaker\tmarker\n");
+
+    for (index, segment) in segments.iter().enumerate() {
+        if index > 0 {
+            output.push('\n');
+        }
+
+        for (token, start, end) in crate::transcription_commands::word_spans(segment) {
+            let midpoint = (start + end) / 2.0;
+            let tag = markers
+                .iter()
+                .filter(|marker| marker.start_time <= midpoint && midpoint <= marker.end_time)
+                .filter(|marker| match marker.speaker.as_deref() {
+                    Some(speaker) => speaker == segment.speaker_id || speaker == segment.speaker_label,
+                    None => true,
+                })
+                .max_by(|a, b| a.confidence.total_cmp(&b.confidence))
+                .map(|marker| marker.marker_type.as_str())
+                .unwrap_or(CONLL_EMPTY);
+
+            output.push_str(&format!(
+                "{}\t{:.3}\t{:.3}\t{}\t{}\n",
+                token,
+                start,
+                end,
+                segment.speaker_id.replace(char::is_whitespace, "_"),
+                tag
+            ));
+        }
+    }
+
+    output
+}
+
+#[tauri::command]
+pub async fn export_conll(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    settings: State<'_, crate::settings::SettingsStore>,
+    session_id: String
+) -> Result<String, AppError> {
+    log::info!("Exporting CoNLL tokens for session: {}", session_id);
+    
+    let segments = crate::storage_commands::load_segments(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load transcript: {}", e)))?;
+    if segments.is_empty() {
+        return Err(AppError::Validation("Session has no transcript to export".to_string()));
+    }
+    
+    let markers = crate::analysis_commands::load_markers(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load markers: {}", e)))?;
+    
+    let output_path = export_directory(&app, &settings.get())
+        .await?
+        .join(format!("analysis_{}.conll", session_id))
+        .to_string_lossy()
+        .to_string();
+    tokio::fs::write(&output_path, render_conll(&segments, &markers))
+        .await
+        .map_err(|e| AppError::Io(format!("Failed to write CoNLL export: {}", e)))?;
+    verify_export(&settings, &output_path, "conll").await?;
+    
+    Ok(output_path)
+}
+
+/// Replace every word character with `x`, keeping whitespace so word lengths
+/// and boundaries survive. Punctuation is kept or masked per `keep_punctuation`.
+pub fn mask_text(text: &str, keep_punctuation: bool) -> String {
+    text.chars()
+        .map(|c| {
+            if c.is_whitespace() || (keep_punctuation && !c.is_alphanumeric()) {
+                c
+            } else {
+                'x'
+            }
+        })
+        .collect()
+}
+
+/// Copy of a transcript with masked text but original timings and speakers
+pub fn structure_only(
+    segments: &[crate::transcription_commands::SpeakerSegment],
+    keep_punctuation: bool
+) -> Vec<crate::transcription_commands::SpeakerSegment> {
+    segments
+        .iter()
+        .map(|segment| crate::transcription_commands::SpeakerSegment {
+            id: segment.id.clone(),
+            speaker_id: segment.speaker_id.clone(),
+            speaker_label: segment.speaker_label.clone(),
+            start_time: segment.start_time,
+            end_time: segment.end_time,
+            text: mask_text(&segment.text, keep_punctuation),
+            confidence: segment.confidence,
+            // Word timings stay useful for structure analysis; their text is masked too
+            words: segment.words.as_ref().map(|words| {
+                words
+                    .iter()
+                    .map(|word| crate::transcription_commands::WordTiming {
+                        text: mask_text(&word.text, keep_punctuation),
+                        ..word.clone()
+                    })
+                    .collect()
+            }),
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub async fn export_structure_only(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    settings: State<'_, crate::settings::SettingsStore>,
+    session_id: String,
+    keep_punctuation: Option<bool>
+) -> Result<String, AppError> {
+    log::info!("Exporting structure-only transcript for session: {}", session_id);
+    
+    let segments = crate::storage_commands::load_segments(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load transcript: {}", e)))?;
+    if segments.is_empty() {
+        return Err(AppError::Validation("Session has no transcript to export".to_string()));
+    }
+    
+    let masked = structure_only(&segments, keep_punctuation.unwrap_or(false));
+    let contents = serde_json::to_string_pretty(&masked)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize transcript structure: {}", e)))?;
+    
+    let output_path = export_directory(&app, &settings.get())
+        .await?
+        .join(format!("structure_{}.json", session_id))
+        .to_string_lossy()
+        .to_string();
+    tokio::fs::write(&output_path, contents)
+        .await
+        .map_err(|e| AppError::Io(format!("Failed to write structure export: {}", e)))?;
+    verify_export(&settings, &output_path, "json").await?;
+    
+    Ok(output_path)
+}
+
+/// Render a rapport curve as CSV, with `lower`/`upper` columns when bands are included
+pub fn rapport_csv(
+    indicators: &[crate::analysis_commands::RapportIndicator],
+    include_bands: bool
+) -> String {
+    let mut csv = if include_bands {
+        String::from("timestamp,value,lower,upper,trend,contributing_markers\n")
+    } else {
+        String::from("timestamp,value,trend,contributing_markers\n")
+    };
+
+    for indicator in indicators {
+        let contributing = csv_field(&indicator.contributing_markers.join(";"));
+        if include_bands {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{}\n",
+                indicator.timestamp,
+                indicator.value,
+                indicator.lower.map(|v| v.to_string()).unwrap_or_default(),
+                indicator.upper.map(|v| v.to_string()).unwrap_or_default(),
+                csv_field(&indicator.trend),
+                contributing
+            ));
+        } else {
+            csv.push_str(&format!(
+                "{},{},{},{}\n",
+                indicator.timestamp,
+                indicator.value,
+                csv_field(&indicator.trend),
+                contributing
+            ));
+        }
+    }
+
+    csv
+}
+
+#[tauri::command]
+pub async fn export_rapport_csv(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    settings: State<'_, crate::settings::SettingsStore>,
+    session_id: String,
+    include_confidence_bands: Option<bool>
+) -> Result<String, AppError> {
+    log::info!("Exporting rapport curve for session: {}", session_id);
+    
+    let mut indicators = crate::analysis_commands::load_rapport(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load rapport curve: {}", e)))?;
+    if indicators.is_empty() {
+        return Err(AppError::NotFound(format!("No rapport curve stored for session: {}", session_id)));
+    }
+    
+    let include_bands = include_confidence_bands.unwrap_or(false);
+    if include_bands {
+        let markers = crate::analysis_commands::load_markers(&state.db, &session_id)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to load markers: {}", e)))?;
+        crate::analysis_commands::apply_confidence_bands(&mut indicators, &markers);
+    }
+    
+    let output_path = export_directory(&app, &settings.get())
+        .await?
+        .join(format!("rapport_{}.csv", session_id))
+        .to_string_lossy()
+        .to_string();
+    tokio::fs::write(&output_path, rapport_csv(&indicators, include_bands))
+        .await
+        .map_err(|e| AppError::Io(format!("Failed to write rapport export: {}", e)))?;
+    verify_export(&settings, &output_path, "csv").await?;
+    
+    Ok(output_path)
+}
+
+/// One row per observed transition: `from,to,count,probability`
+pub fn transition_csv(matrix: &crate::analysis_commands::TransitionMatrix) -> String {
+    let mut csv = String::from("from,to,count,probability\n");
+
+    for (from, row) in matrix.counts.iter().enumerate() {
+        for (to, count) in row.iter().enumerate().filter(|(_, count)| **count > 0) {
+            csv.push_str(&format!(
+                "{},{},{},{}\n",
+                csv_field(&matrix.marker_types[from]),
+                csv_field(&matrix.marker_types[to]),
+                count,
+                matrix.probabilities[from][to]
+            ));
+        }
+    }
+
+    csv
+}
+
+#[tauri::command]
+pub async fn export_transition_matrix(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    settings: State<'_, crate::settings::SettingsStore>,
+    session_id: String
+) -> Result<String, AppError> {
+    log::info!("Exporting marker transitions for session: {}", session_id);
+    
+    let markers = crate::analysis_commands::load_markers(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load markers: {}", e)))?;
+    let matrix = crate::analysis_commands::transition_matrix(&markers);
+    
+    let output_path = export_directory(&app, &settings.get())
+        .await?
+        .join(format!("transitions_{}.csv", session_id))
+        .to_string_lossy()
+        .to_string();
+    tokio::fs::write(&output_path, transition_csv(&matrix))
+        .await
+        .map_err(|e| AppError::Io(format!("Failed to write transition export: {}", e)))?;
+    verify_export(&settings, &output_path, "csv").await?;
+    
+    Ok(output_path)
+}
+
+/// Accepted `redaction` values for client references in administrative exports
+const CLIENT_REDACTION_LEVELS: &[&str] = &["none", "pseudonymize", "remove"];
+
+/// How client references appear in administrative exports
+pub fn redact_client_reference(client_reference: Option<&str>, redaction: &str) -> Result<String, String> {
+    let Some(reference) = client_reference.filter(|r| !r.is_empty()) else {
+        return Ok(String::new());
+    };
+
+    match redaction {
+        "none" => Ok(reference.to_string()),
+        // Stable pseudonym so sessions of one client can still be grouped
+        "pseudonymize" => {
+            use sha2::{Digest, Sha256};
+            let digest = Sha256::digest(reference.as_bytes());
+            let hex: String = digest.iter().take(4).map(|b| format!("{:02x}", b)).collect();
+            Ok(format!("client-{}", hex))
+        }
+        "remove" => Ok(REDACTED.to_string()),
+        other => Err(format!("Unsupported redaction level: {}", other)),
+    }
+}
+
+/// Replacement for every scrubbed name
+const REDACTED: &str = "[REDACTED]";
+
+/// Strips client names from exported text.
+///
+/// Scrubs the session's `client_reference` and the `redaction_terms` setting,
+/// case-insensitively and only as whole words when a term starts or ends with
+/// a letter or digit. Reports pass every block through it after loading, which
+/// covers the session name, speaker labels, transcript text and marker
+/// explanations; only the exported copy changes, stored data is never modified.
+pub struct Redactor {
+    /// Lowercased terms, longest first so "Anna Maria" wins over "Anna"
+    terms: Vec<Vec<char>>,
+}
+
+impl Redactor {
+    pub fn new(client_reference: Option<&str>, configured_terms: &[String]) -> Self {
+        let mut terms: Vec<Vec<char>> = client_reference
+            .into_iter()
+            .chain(configured_terms.iter().map(String::as_str))
+            .map(str::trim)
+            .filter(|term| !term.is_empty())
+            .map(|term| term.chars().flat_map(char::to_lowercase).collect())
+            .collect();
+        terms.sort_by_key(|term| std::cmp::Reverse(term.len()));
+        terms.dedup();
+        Self { terms }
+    }
+
+    /// Byte length of the term matching `text` from its start, if any
+    fn match_at(&self, text: &str, previous: Option<char>) -> Option<usize> {
+        self.terms.iter().find_map(|term| {
+            let mut chars = text.char_indices();
+            let mut pending = std::collections::VecDeque::new();
+            let mut end = 0;
+            for expected in term {
+                if pending.is_empty() {
+                    let (index, c) = chars.next()?;
+                    end = index + c.len_utf8();
+                    pending.extend(c.to_lowercase());
+                }
+                if pending.pop_front() != Some(*expected) {
+                    return None;
+                }
+            }
+            if !pending.is_empty() {
+                return None;
+            }
+
+            let next = text[end..].chars().next();
+            let starts_word = term.first().is_some_and(|c| c.is_alphanumeric());
+            let ends_word = term.last().is_some_and(|c| c.is_alphanumeric());
+            let bounded_before = !starts_word || !previous.is_some_and(char::is_alphanumeric);
+            let bounded_after = !ends_word || !next.is_some_and(char::is_alphanumeric);
+            (bounded_before && bounded_after).then_some(end)
+        })
+    }
+
+    pub fn redact(&self, text: &str) -> String {
+        if self.terms.is_empty() {
+            return text.to_string();
+        }
+
+        let mut output = String::with_capacity(text.len());
+        let mut previous = None;
+        let mut index = 0;
+        while let Some(c) = text[index..].chars().next() {
+            if let Some(length) = self.match_at(&text[index..], previous) {
+                output.push_str(REDACTED);
+                index += length;
+                previous = text[..index].chars().next_back();
+            } else {
+                output.push(c);
+                index += c.len_utf8();
+                previous = Some(c);
+            }
+        }
+        output
+    }
+}
+
+/// Session list CSV of the sessions matching `filters`, newest first, with
+/// client references shown per `redaction`; also returns the number of sessions
+async fn render_session_list(
+    pool: &SqlitePool,
+    filters: &crate::storage_commands::SessionFilters,
+    redaction: &str
+) -> Result<(String, usize), AppError> {
+    let mut builder = sqlx::QueryBuilder::new(format!(
+        "SELECT {}, (SELECT COUNT(*) FROM marker_events m WHERE m.session_id = conversation_sessions.id) AS marker_count \
+         FROM conversation_sessions",
+        crate::storage_commands::SESSION_COLUMNS
+    ));
+    filters.push_where(&mut builder);
+    builder.push(" ORDER BY created_at DESC, id");
+
+    let rows = builder
+        .build()
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load sessions: {}", e)))?;
+
+    let mut csv = String::from(
+        "id,name,session_type,client_reference,created_at,updated_at,duration,status,marker_count\n",
+    );
+    for row in &rows {
+        let session = crate::storage_commands::session_from_row(row)
+            .map_err(|e| AppError::Database(format!("Failed to read session: {}", e)))?;
+        let marker_count: i64 = sqlx::Row::try_get(row, "marker_count").map_err(AppError::from)?;
+        let client_reference = redact_client_reference(session.client_reference.as_deref(), redaction)
+            .map_err(AppError::Validation)?;
+
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{}\n",
+            csv_field(&session.id),
+            csv_field(&session.name),
+            csv_field(&session.session_type),
+            csv_field(&client_reference),
+            session.created_at.to_rfc3339(),
+            session.updated_at.to_rfc3339(),
+            session.duration.map(|d| d.to_string()).unwrap_or_default(),
+            csv_field(&session.status),
+            marker_count
+        ));
+    }
+
+    Ok((csv, rows.len()))
+}
+
+#[tauri::command]
+pub async fn export_session_list(
+    state: State<'_, AppState>,
+    settings: State<'_, crate::settings::SettingsStore>,
+    filters: Option<crate::storage_commands::SessionFilters>,
+    output_path: String,
+    redaction: Option<String> // "none", "pseudonymize", "remove"
+) -> Result<String, AppError> {
+    let redaction = redaction.unwrap_or_else(|| "pseudonymize".to_string());
+    log::info!("Exporting session list to: {} (client references: {})", output_path, redaction);
+    
+    if !CLIENT_REDACTION_LEVELS.contains(&redaction.as_str()) {
+        return Err(AppError::Validation(format!(
+            "Unsupported redaction level: {} (expected one of: {})",
+            redaction,
+            CLIENT_REDACTION_LEVELS.join(", ")
+        )));
+    }
+    
+    let (csv, count) = render_session_list(&state.db, &filters.unwrap_or_default(), &redaction).await?;
+    
+    tokio::fs::write(&output_path, csv)
+        .await
+        .map_err(|e| AppError::Io(format!("Failed to write session list: {}", e)))?;
+    verify_export(&settings, &output_path, "csv").await?;
+    
+    log::info!("Exported {} sessions", count);
+    Ok(output_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis_commands::MarkerType;
+    use crate::test_support::{insert, marker, memory_pool, segment, session, temp_dir};
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    /// Sink that remembers the size of every write reaching it
+    #[derive(Default)]
+    struct RecordingSink {
+        bytes: Vec<u8>,
+        writes: usize,
+        largest_write: usize,
+    }
+
+    impl tokio::io::AsyncWrite for RecordingSink {
+        fn poll_write(mut self: Pin<&mut Self>, _: &mut Context<'_>, buf: &[u8]) -> Poll<std::io::Result<usize>> {
+            self.writes += 1;
+            self.largest_write = self.largest_write.max(buf.len());
+            self.bytes.extend_from_slice(buf);
+            Poll::Ready(Ok(buf.len()))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    const LARGE_MARKER_SET: i64 = 50_000;
+
+    async fn insert_markers(pool: &SqlitePool, count: i64) {
+        sqlx::query(
+            "WITH RECURSIVE n(i) AS (SELECT 0 UNION ALL SELECT i + 1 FROM n WHERE i + 1 < ?) \
+             INSERT INTO marker_events \
+             (id, session_id, marker_type, start_time, end_time, confidence, evidence, explanation, speaker) \
+             SELECT 'm' || i, 's1', CASE i % 2 WHEN 0 THEN 'ATO' ELSE 'SEM' END, \
+                    i * 0.5, i * 0.5 + 0.4, 0.8, 'evidence, \"quoted\"', 'explanation', 'SPEAKER_00' FROM n",
+        )
+        .bind(count)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn large_marker_sets_stream_in_bounded_chunks() {
+        let pool = memory_pool().await;
+        insert_markers(&pool, LARGE_MARKER_SET).await;
+        let mut sink = RecordingSink::default();
+
+        let written = write_markers(&pool, "s1", "jsonl", &[], &mut sink).await.unwrap();
+
+        assert_eq!(written, LARGE_MARKER_SET as u64);
+        assert!(sink.bytes.len() > 10 * EXPORT_BUFFER_BYTES);
+        assert!(sink.largest_write <= EXPORT_BUFFER_BYTES);
+        assert!(sink.writes >= sink.bytes.len() / EXPORT_BUFFER_BYTES);
+
+        let lines: Vec<&str> = std::str::from_utf8(&sink.bytes).unwrap().lines().collect();
+        assert_eq!(lines.len(), LARGE_MARKER_SET as usize);
+        let last: crate::analysis_commands::MarkerEvent = serde_json::from_str(lines[lines.len() - 1]).unwrap();
+        assert_eq!(last.id, format!("m{}", LARGE_MARKER_SET - 1));
+    }
+
+    #[tokio::test]
+    async fn streamed_json_and_csv_stay_well_formed() {
+        let pool = memory_pool().await;
+        insert_markers(&pool, 10).await;
+
+        let mut json = RecordingSink::default();
+        let written = write_markers(&pool, "s1", "json", &["ATO".to_string()], &mut json).await.unwrap();
+        let markers: Vec<crate::analysis_commands::MarkerEvent> = serde_json::from_slice(&json.bytes).unwrap();
+        assert_eq!(written, 5);
+        assert!(markers.iter().all(|marker| marker.marker_type == crate::analysis_commands::MarkerType::Ato));
+
+        let mut csv = RecordingSink::default();
+        write_markers(&pool, "s1", "csv", &[], &mut csv).await.unwrap();
+        let csv = String::from_utf8(csv.bytes).unwrap();
+        assert_eq!(csv_record_widths(&csv), Some(vec![8; 11]));
+    }
+
+    #[test]
+    fn fhir_document_reference_has_required_fields() {
+        let mut session = session("s1", "Intake");
+        session.duration = Some(3600.5);
+        session.client_reference = Some("client-42".to_string());
+        session.file_path = Some("/recordings/s1.wav".to_string());
+
+        let resource = session_to_fhir(&session, None);
+
+        assert_eq!(resource["resourceType"], "DocumentReference");
+        assert_eq!(resource["id"], "s1");
+        // `status` and `content` are the resource's only required elements
+        assert!(["current", "superseded", "entered-in-error"].contains(&resource["status"].as_str().unwrap()));
+        assert_eq!(resource["docStatus"], "final");
+        let content = resource["content"].as_array().unwrap();
+        assert_eq!(content.len(), 2);
+        for entry in content {
+            assert!(entry["attachment"]["contentType"].is_string());
+            assert!(chrono::DateTime::parse_from_rfc3339(entry["attachment"]["creation"].as_str().unwrap()).is_ok());
+        }
+        assert!(chrono::DateTime::parse_from_rfc3339(resource["date"].as_str().unwrap()).is_ok());
+        assert_eq!(resource["type"]["coding"][0]["code"], "therapy");
+        assert_eq!(resource["identifier"][0]["value"], "s1");
+        assert_eq!(resource["subject"]["identifier"]["value"], "client-42");
+        assert_eq!(resource["extension"][0]["valueDecimal"].as_f64(), Some(3600.5));
+
+        let start = chrono::DateTime::parse_from_rfc3339(resource["context"]["period"]["start"].as_str().unwrap()).unwrap();
+        let end = chrono::DateTime::parse_from_rfc3339(resource["context"]["period"]["end"].as_str().unwrap()).unwrap();
+        assert!((end - start).num_seconds() >= 3600);
+    }
+
+    #[test]
+    fn fhir_export_embeds_the_transcript_only_when_asked() {
+        let session = session("s1", "Intake");
+        let transcript = [segment("SPEAKER_00", 61.0, 64.0, "How was your week?")];
+
+        let without = session_to_fhir(&session, None);
+        assert!(without["content"].as_array().unwrap().iter().all(|entry| entry["attachment"]["data"].is_null()));
+        assert!(without.get("subject").is_none());
+        assert!(without["context"]["period"].get("end").is_none());
+
+        let with = session_to_fhir(&session, Some(&transcript));
+        let data = with["content"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find_map(|entry| entry["attachment"]["data"].as_str())
+            .unwrap();
+        let text = base64::engine::general_purpose::STANDARD.decode(data).unwrap();
+        assert_eq!(String::from_utf8(text).unwrap(), "[00:01:01] SPEAKER_00: How was your week?");
+    }
+
+    #[test]
+    fn conll_rows_have_five_aligned_columns_and_blank_lines_between_segments() {
+        let mut aligned = segment("SPEAKER_00", 0.0, 1.0, "Hello there");
+        aligned.words = Some(vec![
+            crate::transcription_commands::WordTiming { text: "Hello".to_string(), start: 0.0, end: 0.4, confidence: 0.9 },
+            crate::transcription_commands::WordTiming { text: "there".to_string(), start: 0.5, end: 1.0, confidence: 0.9 },
+        ]);
+        let segments = [aligned, segment("SPEAKER 01", 1.0, 3.0, "Good to see you")];
+        let markers = [
+            marker("low", MarkerType::Ato, 0.0, 1.0, 0.4, None),
+            marker("high", MarkerType::Sem, 0.6, 1.0, 0.9, Some("SPEAKER_00")),
+            marker("other-speaker", MarkerType::Mema, 0.0, 1.0, 1.0, Some("SPEAKER_01")),
+        ];
+
+        let conll = render_conll(&segments, &markers);
+        let lines: Vec<&str> = conll.lines().collect();
+
+        assert_eq!(lines[0], "# token\tstart\tend\tspeaker\tmarker");
+        assert_eq!(lines.iter().filter(|line| line.is_empty()).count(), segments.len() - 1);
+        assert_eq!(lines[3], "", "segments are separated by one blank line");
+        for line in lines.iter().skip(1).filter(|line| !line.is_empty()) {
+            let columns: Vec<&str> = line.split('\t').collect();
+            assert_eq!(columns.len(), 5, "{:?}", line);
+            for time in &columns[1..3] {
+                assert_eq!(time.split('.').nth(1).map(str::len), Some(3), "{:?}", line);
+            }
+        }
+
+        assert_eq!(lines[1], "Hello\t0.000\t0.400\tSPEAKER_00\tATO");
+        // Both speaker-less ATO and SPEAKER_00's SEM cover it; the more confident one wins
+        assert_eq!(lines[2], "there\t0.500\t1.000\tSPEAKER_00\tSEM");
+        // Speaker ids can't break the columns
+        assert_eq!(lines[4], "Good\t1.000\t1.667\tSPEAKER_01\t_");
+        assert_eq!(lines.len(), 1 + 2 + 1 + 4);
+    }
+
+    #[test]
+    fn structure_only_masks_text_but_keeps_timing_and_speakers() {
+        let mut spoken = segment("SPEAKER_00", 1.5, 4.25, "Hi, Anna! How's it going?");
+        spoken.id = Some("seg-1".to_string());
+        spoken.words = Some(vec![
+            crate::transcription_commands::WordTiming { text: "Hi,".to_string(), start: 1.5, end: 1.8, confidence: 0.7 },
+            crate::transcription_commands::WordTiming { text: "Anna!".to_string(), start: 1.9, end: 2.3, confidence: 0.8 },
+        ]);
+        let segments = [spoken, segment("SPEAKER_01", 4.5, 6.0, "Fine  thanks")];
+
+        let masked = structure_only(&segments, false);
+
+        assert_eq!(masked.len(), segments.len());
+        for (original, masked) in segments.iter().zip(&masked) {
+            assert_eq!(masked.id, original.id);
+            assert_eq!(masked.speaker_id, original.speaker_id);
+            assert_eq!(masked.speaker_label, original.speaker_label);
+            assert_eq!(masked.start_time, original.start_time);
+            assert_eq!(masked.end_time, original.end_time);
+            assert_eq!(masked.confidence, original.confidence);
+            assert_eq!(masked.text.chars().count(), original.text.chars().count());
+        }
+        assert_eq!(masked[0].text, "xxx xxxxx xxxxx xx xxxxxx");
+        // Runs of whitespace survive, so word boundaries do too
+        assert_eq!(masked[1].text, "xxxx  xxxxxx");
+
+        let words = masked[0].words.as_ref().unwrap();
+        assert_eq!(words.iter().map(|word| word.text.as_str()).collect::<Vec<_>>(), ["xxx", "xxxxx"]);
+        assert_eq!((words[1].start, words[1].end, words[1].confidence), (1.9, 2.3, 0.8));
+        assert!(masked[1].words.is_none());
+    }
+
+    #[test]
+    fn structure_only_can_keep_punctuation() {
+        let masked = structure_only(&[segment("SPEAKER_00", 0.0, 1.0, "Hi, Anna! Ça va?")], true);
+        assert_eq!(masked[0].text, "xx, xxxx! xx xx?");
+    }
+
+    /// Records of an RFC 4180 document, unquoting fields
+    fn parse_csv(contents: &str) -> Vec<Vec<String>> {
+        let mut records = Vec::new();
+        let mut record = Vec::new();
+        let mut field = String::new();
+        let mut in_quotes = false;
+        let mut chars = contents.chars().peekable();
+        while let Some(c) = chars.next() {
+            match (c, in_quotes) {
+                ('"', true) if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                ('"', _) => in_quotes = !in_quotes,
+                (',', false) => record.push(std::mem::take(&mut field)),
+                ('\n', false) => {
+                    record.push(std::mem::take(&mut field));
+                    records.push(std::mem::take(&mut record));
+                }
+                _ => field.push(c),
+            }
+        }
+        records
+    }
+
+    #[test]
+    fn csv_fields_are_quoted_only_when_needed() {
+        assert_eq!(csv_field("plain text"), "plain text");
+        assert_eq!(csv_field(""), "");
+        assert_eq!(csv_field("Smith, Anna"), "\"Smith, Anna\"");
+        assert_eq!(csv_field("the \"good\" week"), "\"the \"\"good\"\" week\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+        assert_eq!(csv_field("carriage\rreturn"), "\"carriage\rreturn\"");
+    }
+
+    #[tokio::test]
+    async fn session_list_round_trips_awkward_names() {
+        let pool = memory_pool().await;
+        let names = ["Intake, part 1", "She said \"no\"", "Line one\nline two", "=SUM(A1)"];
+        for (index, name) in names.iter().enumerate() {
+            let mut stored = session(&format!("s{}", index), name);
+            stored.created_at = chrono::Utc::now() - chrono::Duration::minutes(index as i64);
+            stored.client_reference = Some("Doe, \"Jane\"".to_string());
+            insert(&pool, &stored).await;
+        }
+
+        let filters = crate::storage_commands::SessionFilters::default();
+        let (csv, count) = render_session_list(&pool, &filters, "none").await.unwrap();
+
+        assert_eq!(count, names.len());
+        validate_export_contents(&csv, "csv").unwrap();
+        let records = parse_csv(&csv);
+        assert_eq!(records.len(), 1 + names.len());
+        assert!(records.iter().all(|record| record.len() == 9));
+        let parsed_names: Vec<&str> = records[1..].iter().map(|record| record[1].as_str()).collect();
+        assert_eq!(parsed_names, names);
+        assert!(records[1..].iter().all(|record| record[3] == "Doe, \"Jane\""));
+    }
+
+    #[tokio::test]
+    async fn session_list_redacts_client_references() {
+        let pool = memory_pool().await;
+        let mut stored = session("s1", "Intake");
+        stored.client_reference = Some("Jane Doe".to_string());
+        insert(&pool, &stored).await;
+        let filters = crate::storage_commands::SessionFilters::default();
+
+        let (pseudonymized, _) = render_session_list(&pool, &filters, "pseudonymize").await.unwrap();
+        let (removed, _) = render_session_list(&pool, &filters, "remove").await.unwrap();
+
+        let reference = |csv: &str| parse_csv(csv)[1][3].clone();
+        assert!(reference(&pseudonymized).starts_with("client-"));
+        assert!(!pseudonymized.contains("Jane"));
+        assert_eq!(reference(&removed), REDACTED);
+    }
+
+    #[test]
+    fn csv_widths_follow_quoting() {
+        assert_eq!(csv_record_widths("a,b,c\n\"x,y\",\"two\nlines\",z\n"), Some(vec![3, 3]));
+        assert_eq!(csv_record_widths("a,\"\"\"quoted\"\"\"\n"), Some(vec![2]));
+        assert_eq!(csv_record_widths("a,\"never closed\n"), None);
+    }
+
+    #[test]
+    fn complete_exports_pass_validation() {
+        validate_export_contents("id,name\n1,\"Smith, Anna\"\n", "csv").unwrap();
+        validate_export_contents("[{\"id\":1},{\"id\":2}]", "json").unwrap();
+        validate_export_contents("{\"id\":1}\n{\"id\":2}\n", "jsonl").unwrap();
+        validate_export_contents("", "jsonl").unwrap();
+        validate_export_contents("[00:01] SPEAKER_00: hi\n", "txt").unwrap();
+    }
+
+    #[test]
+    fn truncated_or_malformed_exports_fail_validation() {
+        let failures = [
+            ("", "csv", "export file is empty"),
+            ("id,name\n1,Anna", "csv", "export ends mid-record"),
+            ("id,name\n1,\"Anna\n", "csv", "export ends inside a quoted field"),
+            ("id,name\n1,Anna,extra\n", "csv", "record 2 has 3 fields, header has 2"),
+            ("[{\"id\":1},{\"id\"", "json", "export is not valid JSON"),
+            ("{\"id\":1}\n{\"id\":2}", "jsonl", "export ends mid-record"),
+            ("{\"id\":1}\n{\"id\n", "jsonl", "line 2 is not valid JSON"),
+            ("[00:01] SPEAKER_00: h", "txt", "export ends mid-record"),
+        ];
+        for (contents, format, expected) in failures {
+            let problem = validate_export_contents(contents, format).unwrap_err();
+            assert!(problem.starts_with(expected), "{:?} as {}: {}", contents, format, problem);
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_verification_removes_the_export() {
+        let dir = temp_dir();
+        let settings = crate::settings::SettingsStore::load(dir.join("settings.json"));
+        let good = dir.join("good.csv");
+        let bad = dir.join("bad.csv");
+        std::fs::write(&good, "id,name\n1,Anna\n").unwrap();
+        std::fs::write(&bad, "id,name\n1,Anna,extra\n").unwrap();
+
+        verify_export(&settings, good.to_str().unwrap(), "csv").await.unwrap();
+        assert!(good.exists());
+        let error = verify_export(&settings, bad.to_str().unwrap(), "csv").await.unwrap_err();
+        assert!(matches!(error, AppError::Io(_)));
+        assert!(!bad.exists());
+
+        // Verification can be switched off, leaving the file as written
+        settings
+            .update(crate::settings::AppSettings {
+                verify_exports: false,
+                ..settings.get()
+            })
+            .unwrap();
+        std::fs::write(&bad, "id,name\n1,Anna,extra\n").unwrap();
+        verify_export(&settings, bad.to_str().unwrap(), "csv").await.unwrap();
+        assert!(bad.exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn transition_csv_lists_observed_transitions_only() {
+        let markers = [
+            marker("a", MarkerType::Ato, 0.0, 1.0, 0.8, None),
+            marker("b", MarkerType::Sem, 1.0, 2.0, 0.8, None),
+            marker("c", MarkerType::Ato, 2.0, 3.0, 0.8, None),
+            marker("d", MarkerType::Ato, 3.0, 4.0, 0.8, None),
+        ];
+
+        let csv = transition_csv(&crate::analysis_commands::transition_matrix(&markers));
+
+        assert_eq!(csv, "from,to,count,probability\nATO,ATO,1,0.5\nATO,SEM,1,0.5\nSEM,ATO,1,1\n");
+        validate_export_contents(&csv, "csv").unwrap();
+    }
+}
@@ -0,0 +1,448 @@
+use tauri::State;
+use serde::{Deserialize, Serialize};
+use sqlx::{SqlitePool, Row};
+use sha2::{Digest, Sha256};
+
+use crate::error::AppError;
+use crate::state::AppState;
+use crate::transcription_commands::SpeakerSegment;
+
+/// Bumped whenever the canonical serialization changes
+const FINGERPRINT_VERSION: &str = "v1";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FingerprintVerification {
+    pub session_id: String,
+    pub matches: bool,
+    pub stored_fingerprint: Option<String>,
+    pub current_fingerprint: String,
+}
+
+/// Stable SHA-256 over a canonical form of the transcript.
+/// Segments are sorted, times rounded to milliseconds and whitespace collapsed,
+/// so the hash only changes when the content does.
+pub fn compute_transcript_fingerprint(segments: &[SpeakerSegment]) -> String {
+    let mut canonical: Vec<String> = segments
+        .iter()
+        .map(|segment| {
+            let text = segment.text.split_whitespace().collect::<Vec<_>>().join(" ");
+            // Unit separators can't appear in transcript text, so fields never run together
+            format!(
+                "{}\u{1f}{}\u{1f}{}\u{1f}{}\u{1f}{}",
+                (segment.start_time * 1000.0).round() as i64,
+                (segment.end_time * 1000.0).round() as i64,
+                segment.speaker_id.trim(),
+                segment.speaker_label.trim(),
+                text
+            )
+        })
+        .collect();
+    canonical.sort();
+
+    let mut hasher = Sha256::new();
+    hasher.update(FINGERPRINT_VERSION.as_bytes());
+    for record in &canonical {
+        hasher.update([0x1e]);
+        hasher.update(record.as_bytes());
+    }
+
+    format!("{}:{:x}", FINGERPRINT_VERSION, hasher.finalize())
+}
+
+pub async fn stored_fingerprint(pool: &SqlitePool, session_id: &str) -> Result<Option<String>, sqlx::Error> {
+    let row = sqlx::query("SELECT fingerprint FROM transcript_fingerprints WHERE session_id = ?")
+        .bind(session_id)
+        .fetch_optional(pool)
+        .await?;
+
+    row.map(|row| row.try_get("fingerprint")).transpose()
+}
+
+/// How far past the end of the audio a transcript may run before it counts as a mismatch
+const AUDIO_END_TOLERANCE_SECS: f64 = 1.0;
+
+/// Segments sampled by the speech spot check
+const SPOT_CHECK_SEGMENTS: usize = 5;
+
+/// Minimum mean fraction of a sampled segment that must contain speech
+const MIN_SPOT_CHECK_SPEECH: f64 = 0.5;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MatchReport {
+    pub session_id: String,
+    pub audio_duration: f64,
+    pub transcript_end: f64,
+    /// Mean speech fraction of the spot-checked segments, when the check ran
+    pub spot_check_speech: Option<f64>,
+    pub confidence: f64, // 0.0 (wrong audio) to 1.0 (consistent)
+    pub warnings: Vec<String>,
+}
+
+/// Up to `count` segments spread evenly across the transcript
+fn spot_check_sample(segments: &[SpeakerSegment], count: usize) -> Vec<&SpeakerSegment> {
+    if segments.len() <= count {
+        return segments.iter().collect();
+    }
+    (0..count)
+        .map(|i| &segments[i * (segments.len() - 1) / (count - 1).max(1)])
+        .collect()
+}
+
+/// Judge whether a transcript plausibly belongs to audio of `audio_duration` seconds.
+/// `silence` enables the spot check: sampled segments should overlap detected speech.
+pub fn assess_transcript_audio_match(
+    session_id: &str,
+    segments: &[SpeakerSegment],
+    audio_duration: f64,
+    silence: Option<&[(f64, f64)]>
+) -> MatchReport {
+    let transcript_end = segments.iter().map(|segment| segment.end_time).fold(0.0, f64::max);
+    let mut confidence: f64 = 1.0;
+    let mut warnings = Vec::new();
+
+    if transcript_end > audio_duration + AUDIO_END_TOLERANCE_SECS {
+        // Confidence shrinks with how much of the transcript has no audio at all
+        confidence = (audio_duration / transcript_end).clamp(0.0, 1.0) * 0.5;
+        warnings.push(format!(
+            "Transcript ends at {:.1}s but the audio is only {:.1}s long",
+            transcript_end, audio_duration
+        ));
+    }
+
+    let spot_check_speech = silence.map(|regions| {
+        let sample = spot_check_sample(segments, SPOT_CHECK_SEGMENTS);
+        // Segments past the end of the audio contain no speech
+        let speech: f64 = sample
+            .iter()
+            .map(|segment| {
+                let audible_end = segment.end_time.min(audio_duration);
+                let span = segment.end_time - segment.start_time;
+                if span <= 0.0 || audible_end <= segment.start_time {
+                    return 0.0;
+                }
+                let silent = crate::audio_processing::overlap_fraction(segment.start_time, audible_end, regions);
+                (1.0 - silent) * (audible_end - segment.start_time) / span
+            })
+            .sum();
+        speech / sample.len().max(1) as f64
+    });
+
+    if let Some(speech) = spot_check_speech {
+        if speech < MIN_SPOT_CHECK_SPEECH {
+            confidence = confidence.min(speech);
+            warnings.push(format!(
+                "Only {:.0}% of spot-checked segments overlap speech in the audio",
+                speech * 100.0
+            ));
+        }
+    }
+
+    MatchReport {
+        session_id: session_id.to_string(),
+        audio_duration,
+        transcript_end,
+        spot_check_speech,
+        confidence: (confidence * 100.0).round() / 100.0,
+        warnings,
+    }
+}
+
+#[tauri::command]
+pub async fn transcript_fingerprint(
+    state: State<'_, AppState>,
+    session_id: String
+) -> Result<String, AppError> {
+    log::info!("Computing transcript fingerprint for session: {}", session_id);
+
+    let segments = crate::storage_commands::load_segments(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load transcript: {}", e)))?;
+
+    Ok(compute_transcript_fingerprint(&segments))
+}
+
+#[tauri::command]
+pub async fn verify_transcript_fingerprint(
+    state: State<'_, AppState>,
+    session_id: String
+) -> Result<FingerprintVerification, AppError> {
+    log::info!("Verifying transcript fingerprint for session: {}", session_id);
+
+    verify_fingerprint(&state.db, session_id).await
+}
+
+/// Compare a session's transcript as stored now with the fingerprint recorded when it was saved
+async fn verify_fingerprint(pool: &SqlitePool, session_id: String) -> Result<FingerprintVerification, AppError> {
+    let segments = crate::storage_commands::load_segments(pool, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load transcript: {}", e)))?;
+    let current_fingerprint = compute_transcript_fingerprint(&segments);
+
+    let stored = stored_fingerprint(pool, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load stored fingerprint: {}", e)))?;
+
+    if stored.as_deref() != Some(current_fingerprint.as_str()) {
+        log::warn!("Transcript fingerprint mismatch for session: {}", session_id);
+    }
+
+    Ok(FingerprintVerification {
+        session_id,
+        matches: stored.as_deref() == Some(current_fingerprint.as_str()),
+        stored_fingerprint: stored,
+        current_fingerprint,
+    })
+}
+
+#[tauri::command]
+pub async fn verify_transcript_audio_match(
+    state: State<'_, AppState>,
+    session_id: String,
+    spot_check: Option<bool>
+) -> Result<MatchReport, AppError> {
+    log::info!("Verifying transcript and audio belong together for session: {}", session_id);
+
+    let session = crate::storage_commands::fetch_session(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load session: {}", e)))?
+        .ok_or_else(|| AppError::NotFound(format!("Session not found: {}", session_id)))?;
+    let audio_path = session
+        .file_path
+        .ok_or_else(|| AppError::Validation("Session has no audio file".to_string()))?;
+    let segments = crate::storage_commands::load_segments(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load transcript: {}", e)))?;
+    if segments.is_empty() {
+        return Err(AppError::Validation("Session has no transcript to verify".to_string()));
+    }
+
+    let run_spot_check = spot_check.unwrap_or(true);
+    let (audio_duration, silence) = tauri::async_runtime::spawn_blocking(move || {
+        measure_audio(std::path::Path::new(&audio_path), run_spot_check)
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Audio check task failed: {}", e)))?
+    .map_err(AppError::Audio)?;
+
+    let report = assess_transcript_audio_match(&session_id, &segments, audio_duration, silence.as_deref());
+    if !report.warnings.is_empty() {
+        log::warn!("Transcript/audio mismatch suspected for session {}: {:?}", session_id, report.warnings);
+    }
+
+    Ok(report)
+}
+
+/// Length of an audio file in any imported format and, for the spot check, its
+/// silent spans. Duration comes from the container header where it has one; WAV
+/// silence is streamed, other formats are decoded once.
+fn measure_audio(path: &std::path::Path, spot_check: bool) -> Result<(f64, Option<Vec<(f64, f64)>>), String> {
+    use crate::audio_processing::VAD_SILENCE_DBFS;
+
+    let format = crate::audio_processing::detect_audio_format(path)?;
+    let declared = crate::audio_processing::probe_audio(path, Some(format))?.duration_secs;
+
+    if format == "wav" {
+        let silence = if spot_check {
+            Some(crate::audio_processing::stream_silent_regions(path, VAD_SILENCE_DBFS, 0.0)?)
+        } else {
+            None
+        };
+        if let Some(duration) = declared {
+            return Ok((duration, silence));
+        }
+    } else if let (Some(duration), false) = (declared, spot_check) {
+        return Ok((duration, None));
+    }
+
+    let audio = crate::audio_processing::decode_audio(path, Some(format))?;
+    let silence = spot_check.then(|| crate::audio_processing::silent_regions(&audio, VAD_SILENCE_DBFS));
+    Ok((declared.unwrap_or_else(|| audio.duration_secs()), silence))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AudioIntegrityVerification {
+    pub session_id: String,
+    pub file_path: String,
+    /// False when the file changed since import or is gone
+    pub matches: bool,
+    pub stored_sha256: String,
+    /// `None` when the file no longer exists
+    pub current_sha256: Option<String>,
+}
+
+/// Recompute the SHA-256 of a session's audio file and compare it with the
+/// checksum recorded at import. A changed or missing file is reported in the
+/// result rather than as an error.
+#[tauri::command]
+pub async fn verify_audio_integrity(
+    state: State<'_, AppState>,
+    session_id: String
+) -> Result<AudioIntegrityVerification, AppError> {
+    log::info!("Verifying audio integrity for session: {}", session_id);
+
+    let row = sqlx::query("SELECT file_path, audio_sha256 FROM conversation_sessions WHERE id = ?")
+        .bind(&session_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load session: {}", e)))?
+        .ok_or_else(|| AppError::NotFound(format!("Session not found: {}", session_id)))?;
+    let file_path: Option<String> = row.try_get("file_path").map_err(AppError::from)?;
+    let stored_sha256: Option<String> = row.try_get("audio_sha256").map_err(AppError::from)?;
+    let (Some(file_path), Some(stored_sha256)) = (file_path, stored_sha256) else {
+        return Err(AppError::Validation(format!(
+            "Session {} has no imported audio with a recorded checksum",
+            session_id
+        )));
+    };
+
+    let path = std::path::PathBuf::from(&file_path);
+    let current_sha256 = tauri::async_runtime::spawn_blocking(move || {
+        if !path.is_file() {
+            return Ok(None);
+        }
+        crate::audio_commands::file_sha256(&path).map(Some)
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Checksum task failed: {}", e)))?
+    .map_err(|e| AppError::Io(format!("Failed to hash {}: {}", file_path, e)))?;
+
+    let matches = current_sha256.as_deref() == Some(stored_sha256.as_str());
+    if !matches {
+        log::warn!("Audio of session {} no longer matches its import checksum", session_id);
+    }
+
+    Ok(AudioIntegrityVerification {
+        session_id,
+        file_path,
+        matches,
+        stored_sha256,
+        current_sha256,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{insert, memory_pool, segment, session, temp_dir, write_tone_wav};
+
+    fn transcript() -> Vec<SpeakerSegment> {
+        vec![
+            segment("SPEAKER_00", 0.0, 2.5, "How was the week?"),
+            segment("SPEAKER_01", 2.5, 6.0, "Better than the last one."),
+        ]
+    }
+
+    #[test]
+    fn fingerprint_ignores_order_whitespace_and_sub_millisecond_noise() {
+        let original = transcript();
+        let mut reordered = transcript();
+        reordered.reverse();
+        reordered[0].text = "  Better than   the last one. ".to_string();
+        reordered[1].end_time += 0.0001;
+
+        assert_eq!(compute_transcript_fingerprint(&original), compute_transcript_fingerprint(&reordered));
+        assert!(compute_transcript_fingerprint(&original).starts_with("v1:"));
+    }
+
+    #[test]
+    fn any_content_edit_changes_the_fingerprint() {
+        let original = compute_transcript_fingerprint(&transcript());
+
+        let mut text = transcript();
+        text[1].text = "Worse than the last one.".to_string();
+        let mut speaker = transcript();
+        speaker[0].speaker_id = "SPEAKER_01".to_string();
+        let mut timing = transcript();
+        timing[0].start_time = 0.01;
+
+        for edited in [text, speaker, timing] {
+            assert_ne!(compute_transcript_fingerprint(&edited), original);
+        }
+    }
+
+    #[tokio::test]
+    async fn verification_fails_after_the_stored_transcript_is_edited() {
+        let pool = memory_pool().await;
+        insert(&pool, &session("s1", "Intake")).await;
+        crate::storage_commands::write_segments(&mut pool.acquire().await.unwrap(), "s1", &transcript())
+            .await
+            .unwrap();
+
+        let verified = verify_fingerprint(&pool, "s1".to_string()).await.unwrap();
+        assert!(verified.matches);
+        assert_eq!(verified.stored_fingerprint.as_deref(), Some(verified.current_fingerprint.as_str()));
+
+        // An edit that bypasses save_transcript leaves the recorded fingerprint behind
+        sqlx::query("UPDATE transcript_segments SET text = 'Worse than the last one.' WHERE speaker_id = 'SPEAKER_01'")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let verified = verify_fingerprint(&pool, "s1".to_string()).await.unwrap();
+        assert!(!verified.matches);
+        assert_ne!(verified.stored_fingerprint.as_deref(), Some(verified.current_fingerprint.as_str()));
+    }
+
+    fn forty_seconds() -> Vec<SpeakerSegment> {
+        vec![
+            segment("SPEAKER_00", 0.0, 10.0, "First part."),
+            segment("SPEAKER_01", 10.0, 20.0, "Second part."),
+            segment("SPEAKER_00", 20.0, 30.0, "Third part."),
+            segment("SPEAKER_01", 30.0, 40.0, "Fourth part."),
+        ]
+    }
+
+    #[test]
+    fn transcript_longer_than_audio_lowers_confidence() {
+        let report = assess_transcript_audio_match("s1", &forty_seconds(), 20.0, None);
+
+        assert_eq!(report.transcript_end, 40.0);
+        assert_eq!(report.confidence, 0.25);
+        assert_eq!(report.warnings, ["Transcript ends at 40.0s but the audio is only 20.0s long"]);
+        assert!(report.spot_check_speech.is_none());
+    }
+
+    #[test]
+    fn segments_past_the_audio_count_as_silent_in_the_spot_check() {
+        // All of the audio is speech, yet half the transcript has no audio under it
+        let report = assess_transcript_audio_match("s1", &forty_seconds(), 20.0, Some(&[]));
+        assert_eq!(report.spot_check_speech, Some(0.5));
+        assert_eq!(report.warnings.len(), 1);
+
+        let report = assess_transcript_audio_match("s1", &forty_seconds(), 20.0, Some(&[(0.0, 5.0)]));
+        assert_eq!(report.spot_check_speech, Some(0.375));
+        assert_eq!(report.confidence, 0.25);
+        assert_eq!(report.warnings.len(), 2);
+        assert!(report.warnings[1].starts_with("Only 38% of spot-checked segments"));
+    }
+
+    #[test]
+    fn small_overruns_are_tolerated() {
+        let report = assess_transcript_audio_match("s1", &forty_seconds(), 40.0 - AUDIO_END_TOLERANCE_SECS / 2.0, None);
+        assert_eq!(report.confidence, 1.0);
+        assert!(report.warnings.is_empty());
+
+        // A segment straddling the end only counts its audible part
+        let straddling = [segment("SPEAKER_00", 15.0, 25.0, "Cut off.")];
+        let report = assess_transcript_audio_match("s1", &straddling, 20.0, Some(&[]));
+        assert_eq!(report.spot_check_speech, Some(0.5));
+    }
+
+    #[test]
+    fn measures_wav_length_and_silence() {
+        let dir = temp_dir();
+        let path = dir.join("session.wav");
+        write_tone_wav(&path, 16000, 1, 2.0);
+
+        let (duration, silence) = measure_audio(&path, false).unwrap();
+        assert!((duration - 2.0).abs() < 1e-6);
+        assert!(silence.is_none());
+
+        // A steady tone has no silent frames
+        let (duration, silence) = measure_audio(&path, true).unwrap();
+        assert!((duration - 2.0).abs() < 1e-6);
+        assert_eq!(silence, Some(Vec::new()));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,198 @@
+use serde::Serialize;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt};
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+
+use crate::error::AppError;
+use crate::recording_sink::LiveChunk;
+use crate::transcription_commands::SpeakerSegment;
+
+/// Long-running script that keeps a Whisper model loaded and transcribes one
+/// chunk per line of stdin
+const LIVE_TRANSCRIPTION_SCRIPT: &str = "src/lib/transcription/live_transcription_cli.py";
+
+/// Length of the windows a live recording is cut into. Shorter windows caption
+/// sooner but give Whisper less context, so words at window edges suffer.
+pub const LIVE_WINDOW_SECS: f64 = 10.0;
+
+/// Small enough to keep up with real time on a CPU
+const LIVE_MODEL_SIZE: &str = "base";
+
+/// A chunk can take this long, including the model load before the first one
+const LIVE_CHUNK_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// How a recording hands its chunks to live transcription
+#[derive(Debug, Clone)]
+pub struct LiveFeed {
+    /// Chunk files are named `<prefix>-<index>.wav`
+    pub chunk_prefix: PathBuf,
+    pub chunks: UnboundedSender<LiveChunk>,
+}
+
+/// Payload of `live-transcript`: captions for one chunk, timed against the recording
+#[derive(Debug, Clone, Serialize)]
+pub struct LiveTranscript {
+    pub session_id: String,
+    pub chunk_index: usize,
+    pub segments: Vec<SpeakerSegment>,
+}
+
+/// Start live transcription for the recording `session_id` and return the feed
+/// its capture writes chunks to.
+///
+/// Captions are a preview: each window is transcribed on its own by a small
+/// model without alignment or diarization, so every segment is attributed to one
+/// speaker and they arrive one window plus transcription time behind the audio.
+/// When transcription falls behind, queued windows are skipped to stay current.
+/// `start_transcription` on the finished recording stays the authoritative transcript.
+pub fn start(app: AppHandle, session_id: &str) -> LiveFeed {
+    let (chunks_tx, chunks_rx) = tokio::sync::mpsc::unbounded_channel();
+    let feed = LiveFeed {
+        chunk_prefix: std::env::temp_dir().join(format!("live-{}", session_id)),
+        chunks: chunks_tx,
+    };
+
+    let session_id = session_id.to_string();
+    tauri::async_runtime::spawn(async move {
+        run(&app, &session_id, chunks_rx).await;
+    });
+    feed
+}
+
+async fn run(app: &AppHandle, session_id: &str, mut chunks: UnboundedReceiver<LiveChunk>) {
+    let operation_id = format!("live-{}", session_id);
+    let temp_files = app.state::<crate::secure_temp::SecureTempRegistry>();
+    let _temp_guard = temp_files.track(&operation_id);
+
+    if let Err(e) = transcribe_chunks(app, session_id, &operation_id, &mut chunks).await {
+        log::warn!("Live transcription of {} stopped: {}", session_id, e);
+    }
+
+    // The capture sees the feed closed and stops cutting chunks; wipe any already queued
+    chunks.close();
+    while let Some(chunk) = chunks.recv().await {
+        wipe_chunk(&chunk);
+    }
+}
+
+fn wipe_chunk(chunk: &LiveChunk) {
+    if let Err(e) = crate::secure_temp::secure_delete(&chunk.path) {
+        log::warn!("Failed to wipe live chunk {}: {}", chunk.path.display(), e);
+    }
+}
+
+async fn transcribe_chunks(
+    app: &AppHandle,
+    session_id: &str,
+    operation_id: &str,
+    chunks: &mut UnboundedReceiver<LiveChunk>
+) -> Result<(), AppError> {
+    let temp_files = app.state::<crate::secure_temp::SecureTempRegistry>();
+    let state = app.state::<crate::state::AppState>();
+    let interpreter = state.python_interpreter(app).await?.to_path_buf();
+    // Held for the whole recording, like any other running pipeline script
+    let _permit = state.acquire_python_permit().await;
+
+    let mut args = vec!["--model".to_string(), LIVE_MODEL_SIZE.to_string()];
+    if let Some(language) = app.state::<crate::settings::SettingsStore>().get().default_language {
+        args.extend(["--language".to_string(), language]);
+    }
+    log::info!("Starting live transcription for {}: {} {:?}", session_id, LIVE_TRANSCRIPTION_SCRIPT, args);
+
+    let mut child = tokio::process::Command::new(&interpreter)
+        .arg(LIVE_TRANSCRIPTION_SCRIPT)
+        .args(&args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(crate::error::python_spawn_error)?;
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let mut replies = tokio::io::BufReader::new(child.stdout.take().expect("stdout is piped")).lines();
+    let stderr = child.stderr.take().expect("stderr is piped");
+    tauri::async_runtime::spawn(crate::python_integration::read_output_lines(stderr, |line| {
+        log::debug!("live transcription: {}", line)
+    }));
+
+    while let Some(mut chunk) = chunks.recv().await {
+        temp_files.register(operation_id, chunk.path.clone());
+        // Stay close to real time: when chunks queue up, skip to the newest
+        while let Ok(newer) = chunks.try_recv() {
+            temp_files.register(operation_id, newer.path.clone());
+            log::debug!("Live transcription of {} skipping chunk {}", session_id, chunk.index);
+            wipe_chunk(&chunk);
+            chunk = newer;
+        }
+
+        stdin
+            .write_all(format!("{}\n", chunk.path.display()).as_bytes())
+            .await
+            .map_err(|e| AppError::Python(format!("Failed to send chunk to live transcription: {}", e)))?;
+        stdin
+            .flush()
+            .await
+            .map_err(|e| AppError::Python(format!("Failed to send chunk to live transcription: {}", e)))?;
+        let reply = tokio::time::timeout(LIVE_CHUNK_TIMEOUT, replies.next_line()).await;
+        wipe_chunk(&chunk);
+        let reply = match reply {
+            Ok(Ok(Some(reply))) => reply,
+            Ok(Ok(None)) => return Err(AppError::Python("Live transcription exited".to_string())),
+            Ok(Err(e)) => return Err(AppError::Python(format!("Failed to read live transcription: {}", e))),
+            Err(_) => {
+                return Err(AppError::Timeout(format!(
+                    "Chunk {} took longer than {} s",
+                    chunk.index,
+                    LIVE_CHUNK_TIMEOUT.as_secs()
+                )))
+            }
+        };
+
+        let segments = match parse_reply(&reply, chunk.start_secs) {
+            Ok(segments) => segments,
+            Err(e) => {
+                log::warn!("Live transcription of {} chunk {} failed: {}", session_id, chunk.index, e);
+                continue;
+            }
+        };
+        let transcript = LiveTranscript {
+            session_id: session_id.to_string(),
+            chunk_index: chunk.index,
+            segments,
+        };
+        if let Err(e) = app.emit("live-transcript", &transcript) {
+            log::warn!("Failed to emit live-transcript: {}", e);
+        }
+    }
+
+    // Closing stdin ends the script
+    drop(stdin);
+    if let Err(e) = child.wait().await {
+        log::warn!("Failed to wait for live transcription: {}", e);
+    }
+    Ok(())
+}
+
+/// Segments of a `{"segments": [...]}` reply, moved from chunk time to recording
+/// time; an `{"error": ...}` reply is returned as the error
+pub fn parse_reply(reply: &str, chunk_start_secs: f64) -> Result<Vec<SpeakerSegment>, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(reply).map_err(|e| format!("Invalid live transcription output: {}", e))?;
+    if let Some(error) = value.get("error").and_then(|error| error.as_str()) {
+        return Err(error.to_string());
+    }
+
+    let mut segments = crate::transcription_commands::parse_whisperx_output(reply)?;
+    for segment in &mut segments {
+        segment.start_time += chunk_start_secs;
+        segment.end_time += chunk_start_secs;
+        for word in segment.words.iter_mut().flatten() {
+            word.start += chunk_start_secs;
+            word.end += chunk_start_secs;
+        }
+    }
+    Ok(segments)
+}
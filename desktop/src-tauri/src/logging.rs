@@ -0,0 +1,182 @@
+use log::{LevelFilter, Log, Metadata, Record};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use tauri::State;
+
+use crate::error::AppError;
+
+/// A log file is rotated once writing the next line would take it past this size
+const MAX_LOG_FILE_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Rotated files kept besides the live one, `<log>.1` being the newest
+const ROTATED_LOG_FILES: usize = 4;
+
+/// Overrides the file log level, e.g. `debug`. Debug output may contain session
+/// names and transcript text, so the default stays at info.
+const LOG_LEVEL_ENV_VAR: &str = "TRANSRAPPORT_LOG_LEVEL";
+
+const DEFAULT_LOG_LEVEL: LevelFilter = LevelFilter::Info;
+
+static LOGGER: OnceLock<AppLogger> = OnceLock::new();
+
+/// Appends to a log file, rotating it when it grows past `MAX_LOG_FILE_BYTES`
+struct RotatingFile {
+    path: PathBuf,
+    /// `None` only while rotating, as Windows won't rename an open file
+    file: Option<File>,
+    size: u64,
+}
+
+fn open_append(path: &Path) -> std::io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    PathBuf::from(format!("{}.{}", path.display(), index))
+}
+
+impl RotatingFile {
+    fn open(path: PathBuf) -> std::io::Result<Self> {
+        let file = open_append(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            path,
+            file: Some(file),
+            size,
+        })
+    }
+
+    fn write_line(&mut self, line: &str) -> std::io::Result<()> {
+        if self.size > 0 && self.size + line.len() as u64 > MAX_LOG_FILE_BYTES {
+            self.rotate()?;
+        }
+        let file = match self.file.as_mut() {
+            Some(file) => file,
+            None => self.file.insert(open_append(&self.path)?),
+        };
+        file.write_all(line.as_bytes())?;
+        self.size += line.len() as u64;
+        Ok(())
+    }
+
+    /// Shift `<log>.N` to `<log>.N+1`, dropping the oldest, and start a new live file
+    fn rotate(&mut self) -> std::io::Result<()> {
+        drop(self.file.take());
+        for index in (1..ROTATED_LOG_FILES).rev() {
+            let from = rotated_path(&self.path, index);
+            if from.exists() {
+                std::fs::rename(&from, rotated_path(&self.path, index + 1))?;
+            }
+        }
+        std::fs::rename(&self.path, rotated_path(&self.path, 1))?;
+        self.file = Some(open_append(&self.path)?);
+        self.size = 0;
+        Ok(())
+    }
+
+    fn flush(&mut self) {
+        if let Some(file) = self.file.as_mut() {
+            let _ = file.flush();
+        }
+    }
+}
+
+/// Writes records to the log file once one is attached, and to stderr through
+/// env_logger in debug builds
+struct AppLogger {
+    console: Option<env_logger::Logger>,
+    file_level: LevelFilter,
+    file: OnceLock<Mutex<RotatingFile>>,
+}
+
+impl Log for AppLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.file_level || self.console.as_ref().is_some_and(|console| console.enabled(metadata))
+    }
+
+    fn log(&self, record: &Record) {
+        if let Some(console) = &self.console {
+            if console.matches(record) {
+                console.log(record);
+            }
+        }
+
+        if record.level() > self.file_level {
+            return;
+        }
+        let Some(file) = self.file.get() else {
+            return;
+        };
+        let line = format!(
+            "{} {:<5} {}: {}\n",
+            chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            record.level(),
+            record.target(),
+            record.args()
+        );
+        if let Err(e) = file.lock().unwrap().write_line(&line) {
+            // Only the console of debug builds hears about it; release builds drop the record
+            if let Some(console) = &self.console {
+                console.log(
+                    &Record::builder()
+                        .level(log::Level::Warn)
+                        .target(module_path!())
+                        .args(format_args!("Failed to write log file: {}", e))
+                        .build(),
+                );
+            }
+        }
+    }
+
+    fn flush(&self) {
+        if let Some(console) = &self.console {
+            console.flush();
+        }
+        if let Some(file) = self.file.get() {
+            file.lock().unwrap().flush();
+        }
+    }
+}
+
+/// Install the app logger. Records only reach the log file once `attach_file`
+/// has been called; before that, and in release builds, they go nowhere else
+/// but the console of debug builds.
+pub fn init() {
+    let console = cfg!(debug_assertions).then(|| env_logger::Builder::from_default_env().build());
+    let file_level = std::env::var(LOG_LEVEL_ENV_VAR)
+        .ok()
+        .and_then(|level| level.parse().ok())
+        .unwrap_or(DEFAULT_LOG_LEVEL);
+    let max_level = console
+        .as_ref()
+        .map(|console| console.filter())
+        .unwrap_or(LevelFilter::Off)
+        .max(file_level);
+
+    let logger = LOGGER.get_or_init(|| AppLogger {
+        console,
+        file_level,
+        file: OnceLock::new(),
+    });
+    if log::set_logger(logger).is_ok() {
+        log::set_max_level(max_level);
+    }
+}
+
+/// Start writing to `path`, e.g. once the app data directory is known
+pub fn attach_file(path: PathBuf) -> std::io::Result<()> {
+    let Some(logger) = LOGGER.get() else {
+        return Ok(());
+    };
+    let file = RotatingFile::open(path)?;
+    let _ = logger.file.set(Mutex::new(file));
+    Ok(())
+}
+
+/// Path of the current log file, for attaching to bug reports
+#[tauri::command]
+pub async fn get_log_path(paths: State<'_, crate::paths::AppPaths>) -> Result<String, AppError> {
+    Ok(paths.log_file().to_string_lossy().to_string())
+}
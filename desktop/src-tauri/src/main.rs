@@ -1,62 +1,221 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod error;
 mod audio_commands;
+mod audio_processing;
+mod recording_sink;
+mod live_transcription;
 mod transcription_commands;
 mod analysis_commands;
 mod export_commands;
+mod report_pdf;
+mod report_docx;
+mod report_templates;
 mod storage_commands;
 mod python_integration;
+mod settings;
+mod embedding_commands;
+mod cancellation;
+mod duplicate_commands;
+mod security_commands;
+mod search_commands;
+mod integrity_commands;
+mod backup_commands;
+mod recovery;
+mod diagnostics;
+mod logging;
+mod secure_temp;
+mod paths;
+mod state;
+#[cfg(test)]
+mod test_support;
 
-use tauri::Manager;
+use tauri::{Manager, RunEvent};
 
 fn main() {
-    env_logger::init();
+    logging::init();
     
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_process::init())
+        .manage(cancellation::CancellationManager::default())
+        .manage(secure_temp::SecureTempRegistry::default())
+        .manage(analysis_commands::AnalysisJobs::default())
+        .manage(analysis_commands::RapportRecalculations::default())
         .invoke_handler(tauri::generate_handler![
             // Audio commands
             audio_commands::start_recording,
             audio_commands::stop_recording,
+            audio_commands::pause_recording,
+            audio_commands::resume_recording,
             audio_commands::import_audio_file,
+            audio_commands::import_audio_directory,
             audio_commands::get_audio_devices,
+            audio_commands::export_audio_clip,
+            audio_commands::trim_audio,
+            audio_commands::normalize_audio,
+            audio_commands::get_waveform,
+            audio_commands::detect_silence,
             
             // Transcription commands
             transcription_commands::start_transcription,
+            transcription_commands::list_whisper_models,
+            transcription_commands::detect_language,
+            transcription_commands::enqueue_transcription,
+            transcription_commands::get_transcription_queue,
+            transcription_commands::dequeue_transcription,
             transcription_commands::get_transcription_progress,
+            transcription_commands::cancel_transcription,
+            transcription_commands::get_transcription_result,
             transcription_commands::update_speaker_labels,
+            transcription_commands::merge_segments,
+            transcription_commands::split_segment,
+            transcription_commands::detect_hallucinations,
+            transcription_commands::normalize_speaker_ids,
             
             // Analysis commands
             analysis_commands::analyze_transcript,
             analysis_commands::get_analysis_progress,
+            analysis_commands::cancel_analysis,
             analysis_commands::calculate_rapport,
+            analysis_commands::get_rapport_timeline,
+            analysis_commands::recalculate_rapport,
+            analysis_commands::marker_rapport_correlation,
+            analysis_commands::marker_transition_matrix,
+            analysis_commands::get_marker_cooccurrence,
+            analysis_commands::speaking_rate,
+            analysis_commands::suggest_speaker_roles,
+            analysis_commands::get_markers,
+            analysis_commands::list_marker_types,
+            analysis_commands::add_marker,
+            analysis_commands::update_marker,
+            analysis_commands::delete_marker,
+            analysis_commands::query_markers_global,
             
             // Export commands
             export_commands::generate_report,
+            report_templates::list_report_templates,
+            export_commands::export_sessions_bundle,
             export_commands::export_transcript,
             export_commands::export_markers,
+            export_commands::export_fhir,
+            export_commands::export_session_json,
+            export_commands::import_session_json,
+            export_commands::export_conll,
+            export_commands::export_structure_only,
+            export_commands::export_rapport_csv,
+            export_commands::export_transition_matrix,
+            export_commands::export_session_list,
             
             // Storage commands
             storage_commands::create_session,
             storage_commands::get_sessions,
             storage_commands::save_transcript,
-            storage_commands::load_session
+            storage_commands::load_transcript,
+            storage_commands::load_session,
+            storage_commands::update_session,
+            storage_commands::get_session_summary,
+            storage_commands::delete_session,
+            storage_commands::add_session_tag,
+            storage_commands::remove_session_tag,
+            storage_commands::list_tags,
+            storage_commands::import_plaintext_transcript,
+            recovery::get_recovery_summary,
+            duplicate_commands::find_duplicate_sessions,
+            duplicate_commands::merge_duplicates,
+            
+            // Integrity commands
+            integrity_commands::transcript_fingerprint,
+            integrity_commands::verify_transcript_fingerprint,
+            integrity_commands::verify_transcript_audio_match,
+            integrity_commands::verify_audio_integrity,
+            
+            // Search commands
+            embedding_commands::index_session_embedding,
+            embedding_commands::semantic_search,
+            search_commands::rebuild_search_index,
+            search_commands::search_transcripts,
+            
+            // Operation commands
+            cancellation::cancel,
+            cancellation::list_active_operations,
+            
+            // Backend commands
+            python_integration::check_python_environment,
+            python_integration::get_backend_load,
+            
+            // Security commands
+            storage_commands::unlock_database,
+            security_commands::check_passphrase_strength,
+            secure_temp::list_secure_temp,
+            secure_temp::wipe_secure_temp,
+            
+            // Backup commands
+            backup_commands::trigger_backup_now,
+            backup_commands::list_backups,
+            backup_commands::backup_database,
+            backup_commands::restore_database,
+            
+            // Diagnostics commands
+            diagnostics::get_app_diagnostics,
+            logging::get_log_path,
+            
+            // Settings commands
+            settings::get_settings,
+            settings::update_settings,
+            settings::export_preset,
+            settings::import_preset
         ])
         .setup(|app| {
-            // Initialize database
-            let app_handle = app.handle();
-            tauri::async_runtime::spawn(async move {
-                if let Err(e) = storage_commands::initialize_database().await {
-                    log::error!("Failed to initialize database: {}", e);
-                }
-            });
+            // Load persisted settings
+            let settings_path = app.path().app_config_dir()?.join("settings.json");
+            app.manage(settings::SettingsStore::load(settings_path));
+            
+            // Resolve the app's data locations and create their directories
+            let app_paths = paths::AppPaths::resolve(app.handle())?;
+            app_paths.create_dirs()?;
+            if let Err(e) = logging::attach_file(app_paths.log_file()) {
+                log::warn!("Logging to {} is unavailable: {}", app_paths.log_file().display(), e);
+            }
+            app_paths.adopt_legacy_database()?;
+            app_paths.apply_pending_restore()?;
+            app.manage(app_paths.clone());
+            
+            // A passphrase-protected database opens in unlock_database instead
+            if app.state::<settings::SettingsStore>().get().database_passphrase {
+                log::info!("Database is passphrase protected; waiting for unlock");
+            } else {
+                // Initialize database; commands need the pool, so fail startup without it
+                let key = storage_commands::DatabaseKey::Keyring;
+                let pool = tauri::async_runtime::block_on(storage_commands::initialize_database(&app_paths.database(), &key))
+                    .map_err(|e| {
+                        log::error!("Failed to initialize database: {}", e);
+                        e
+                    })?;
+                tauri::async_runtime::block_on(state::manage_database(app.handle(), pool, key));
+            }
+            
+            // Watch for input devices being plugged in or removed
+            app.manage(audio_commands::DeviceWatcher::spawn(app.handle().clone()));
+            
+            // Run scheduled backups once the pool and settings are available
+            app.manage(backup_commands::BackupScheduler::spawn(app.handle().clone()));
             
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app_handle, event| {
+            if let RunEvent::Exit = event {
+                app_handle.state::<audio_commands::DeviceWatcher>().stop();
+                if let Some(state) = app_handle.try_state::<state::AppState>() {
+                    audio_commands::finalize_all_recordings(&state.recordings);
+                }
+                app_handle.state::<backup_commands::BackupScheduler>().stop();
+                app_handle.state::<secure_temp::SecureTempRegistry>().wipe_all();
+            }
+        });
 }
\ No newline at end of file
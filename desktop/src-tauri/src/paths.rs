@@ -0,0 +1,124 @@
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+use crate::error::AppError;
+
+const RECORDINGS_DIR: &str = "recordings";
+const TRANSCRIPTIONS_DIR: &str = "transcriptions";
+const EXPORTS_DIR: &str = "exports";
+const LOGS_DIR: &str = "logs";
+const DATABASE_FILE: &str = "transrapport.db";
+const LOG_FILE: &str = "transrapport.log";
+/// A restored database waiting to replace the live one at the next start
+const PENDING_RESTORE_FILE: &str = "transrapport.db.restore";
+/// Where the database replaced by a restore is kept
+const PRE_RESTORE_FILE: &str = "transrapport.db.pre-restore";
+
+/// Where the app keeps its files, all under `app.path().app_data_dir()`.
+/// Resolved once in `setup`, which also creates the subdirectories, and managed
+/// by Tauri from then on.
+#[derive(Debug, Clone)]
+pub struct AppPaths {
+    data_dir: PathBuf,
+}
+
+impl AppPaths {
+    pub fn resolve(app: &AppHandle) -> Result<Self, AppError> {
+        let data_dir = app
+            .path()
+            .app_data_dir()
+            .map_err(|e| AppError::Io(format!("Failed to resolve app data directory: {}", e)))?;
+        Ok(Self { data_dir })
+    }
+
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    pub fn recordings(&self) -> PathBuf {
+        self.data_dir.join(RECORDINGS_DIR)
+    }
+
+    pub fn transcriptions(&self) -> PathBuf {
+        self.data_dir.join(TRANSCRIPTIONS_DIR)
+    }
+
+    /// WhisperX `--output_dir` of one transcription session
+    pub fn transcription_output(&self, session_id: &str) -> PathBuf {
+        self.transcriptions().join(session_id)
+    }
+
+    /// Default export location; the `export_directory` setting overrides it
+    pub fn exports(&self) -> PathBuf {
+        self.data_dir.join(EXPORTS_DIR)
+    }
+
+    /// Rotated log files written by `logging`
+    pub fn logs(&self) -> PathBuf {
+        self.data_dir.join(LOGS_DIR)
+    }
+
+    /// Log file currently written to; rotated files sit next to it
+    pub fn log_file(&self) -> PathBuf {
+        self.logs().join(LOG_FILE)
+    }
+
+    pub fn database(&self) -> PathBuf {
+        self.data_dir.join(DATABASE_FILE)
+    }
+
+    pub fn pending_restore(&self) -> PathBuf {
+        self.data_dir.join(PENDING_RESTORE_FILE)
+    }
+
+    /// Whether `path` lies in the directory holding the live database
+    pub fn is_in_data_dir(&self, path: &Path) -> bool {
+        let data_dir = self.data_dir.canonicalize().unwrap_or_else(|_| self.data_dir.clone());
+        path.starts_with(&data_dir) || path.starts_with(&self.data_dir)
+    }
+
+    /// Create the data directory and its subdirectories
+    pub fn create_dirs(&self) -> Result<(), AppError> {
+        for dir in [self.recordings(), self.transcriptions(), self.exports(), self.logs()] {
+            std::fs::create_dir_all(&dir)
+                .map_err(|e| AppError::Io(format!("Failed to create {}: {}", dir.display(), e)))?;
+        }
+        Ok(())
+    }
+
+    /// Move a database left in the working directory by earlier versions into
+    /// the data directory, unless one is already there
+    pub fn adopt_legacy_database(&self) -> Result<(), AppError> {
+        let legacy = Path::new(DATABASE_FILE);
+        let target = self.database();
+        if !legacy.is_file() || target.exists() {
+            return Ok(());
+        }
+
+        log::info!("Moving database from {} to {}", legacy.display(), target.display());
+        std::fs::rename(legacy, &target)
+            .or_else(|_| std::fs::copy(legacy, &target).and_then(|_| std::fs::remove_file(legacy)))
+            .map_err(|e| AppError::Io(format!("Failed to move database to {}: {}", target.display(), e)))
+    }
+
+    /// Swap in a database staged by `restore_database`, keeping the replaced
+    /// one next to it. Must run before the database is opened.
+    pub fn apply_pending_restore(&self) -> Result<(), AppError> {
+        let pending = self.pending_restore();
+        if !pending.is_file() {
+            return Ok(());
+        }
+
+        let target = self.database();
+        if target.exists() {
+            let previous = self.data_dir.join(PRE_RESTORE_FILE);
+            std::fs::rename(&target, &previous)
+                .map_err(|e| AppError::Io(format!("Failed to set aside {}: {}", target.display(), e)))?;
+            log::info!("Kept the replaced database at {}", previous.display());
+        }
+        std::fs::rename(&pending, &target)
+            .map_err(|e| AppError::Io(format!("Failed to restore database to {}: {}", target.display(), e)))?;
+        log::info!("Restored database from {}", pending.display());
+        Ok(())
+    }
+}
@@ -1,7 +1,10 @@
 use tauri::State;
 use serde::{Deserialize, Serialize};
-use std::process::{Command, Stdio};
-use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+use crate::error::AppError;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PythonCommand {
@@ -16,45 +19,572 @@ pub struct PythonResult {
     pub stdout: String,
     pub stderr: String,
     pub exit_code: Option<i32>,
+    pub duration_ms: u64,
+    /// Reproducible command line with secrets redacted
+    pub command_line: String,
+}
+
+/// Environment variable naming the interpreter to use, e.g. one inside a virtualenv
+pub const PYTHON_ENV_VAR: &str = "TRANSRAPPORT_PYTHON";
+
+/// Interpreters looked up on PATH when `TRANSRAPPORT_PYTHON` is unset or doesn't work
+const PATH_INTERPRETERS: &[&str] = &["python3", "python"];
+
+/// Virtualenv bundled under the app's resource directory
+const BUNDLED_VENV_DIR: &str = "python-venv";
+
+/// Environment variable prefixes that influence the Python pipeline's behaviour
+const CAPTURED_ENV_PREFIXES: &[&str] = &[
+    "PYTHON", "CUDA_", "HF_", "HUGGINGFACE_", "TRANSFORMERS_", "TORCH_", "WHISPERX_", "OMP_NUM_THREADS",
+];
+
+/// Name fragments marking an environment variable or flag as secret
+const SECRET_NAME_FRAGMENTS: &[&str] = &["KEY", "TOKEN", "SECRET", "PASSWORD", "PASSPHRASE", "AUTH", "CREDENTIAL"];
+
+const REDACTED: &str = "[REDACTED]";
+
+fn is_secret_name(name: &str) -> bool {
+    let upper = name.to_uppercase();
+    SECRET_NAME_FRAGMENTS.iter().any(|fragment| upper.contains(fragment))
+}
+
+/// Quote a word for POSIX shells when it contains anything but safe characters
+fn shell_quote(word: &str) -> String {
+    let safe = !word.is_empty()
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c));
+    if safe {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', "'\\''"))
+    }
+}
+
+/// Redact values of secret-looking flags, both `--token value` and `--token=value`
+pub fn sanitize_args(args: &[String]) -> Vec<String> {
+    let mut sanitized = Vec::with_capacity(args.len());
+    let mut redact_next = false;
+
+    for arg in args {
+        if redact_next {
+            sanitized.push(REDACTED.to_string());
+            redact_next = false;
+            continue;
+        }
+
+        match arg.strip_prefix("--").or_else(|| arg.strip_prefix('-')) {
+            Some(flag) => match flag.split_once('=') {
+                Some((name, _)) if is_secret_name(name) => {
+                    sanitized.push(format!("{}={}", &arg[..arg.len() - flag.len() + name.len()], REDACTED));
+                }
+                Some(_) => sanitized.push(arg.clone()),
+                None => {
+                    redact_next = is_secret_name(flag);
+                    sanitized.push(arg.clone());
+                }
+            },
+            None => sanitized.push(arg.clone()),
+        }
+    }
+
+    sanitized
+}
+
+/// Pipeline-relevant environment variables, sorted, with secret values redacted
+pub fn captured_env(vars: impl Iterator<Item = (String, String)>) -> Vec<(String, String)> {
+    let mut captured: Vec<(String, String)> = vars
+        .filter(|(name, _)| CAPTURED_ENV_PREFIXES.iter().any(|prefix| name.starts_with(prefix)))
+        .map(|(name, value)| {
+            let value = if is_secret_name(&name) { REDACTED.to_string() } else { value };
+            (name, value)
+        })
+        .collect();
+    captured.sort();
+    captured
+}
+
+/// Shell command line that re-runs a script the way the app launched it
+pub fn reproducible_command_line(
+    interpreter: &Path,
+    script_path: &str,
+    args: &[String],
+    env: &[(String, String)]
+) -> String {
+    env.iter()
+        .map(|(name, value)| format!("{}={}", name, shell_quote(value)))
+        .chain(std::iter::once(shell_quote(&interpreter.to_string_lossy())))
+        .chain(std::iter::once(shell_quote(script_path)))
+        .chain(sanitize_args(args).iter().map(|arg| shell_quote(arg)))
+        .collect::<Vec<_>>()
+        .join(" ")
 }
 
-/// Execute Python script for ASR and analysis integration
+fn bundled_interpreter(venv_dir: &Path) -> PathBuf {
+    if cfg!(windows) {
+        venv_dir.join("Scripts").join("python.exe")
+    } else {
+        venv_dir.join("bin").join("python3")
+    }
+}
+
+/// Whether `candidate --version` runs and exits successfully
+async fn interpreter_works(candidate: &Path) -> bool {
+    tokio::process::Command::new(candidate)
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .status()
+        .await
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+/// Version reported by `interpreter --version`, e.g. "Python 3.11.4"
+pub async fn interpreter_version(interpreter: &Path) -> Result<String, String> {
+    let output = tokio::process::Command::new(interpreter)
+        .arg("--version")
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run {}: {}", interpreter.display(), e))?;
+    if !output.status.success() {
+        return Err(format!("{} --version exited with {}", interpreter.display(), output.status));
+    }
+    // Python 2 and early 3.x print the version to stderr
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Ok(if stdout.trim().is_empty() { stderr } else { stdout }.trim().to_string())
+}
+
+/// Find a working Python interpreter, trying `TRANSRAPPORT_PYTHON`, then
+/// `python3`/`python` on PATH, then the virtualenv bundled under `resource_dir`
+pub async fn resolve_python_interpreter(resource_dir: Option<&Path>) -> Result<PathBuf, AppError> {
+    let mut candidates: Vec<PathBuf> = Vec::new();
+    if let Some(configured) = std::env::var_os(PYTHON_ENV_VAR).filter(|value| !value.is_empty()) {
+        candidates.push(PathBuf::from(configured));
+    }
+    candidates.extend(PATH_INTERPRETERS.iter().map(PathBuf::from));
+    if let Some(dir) = resource_dir {
+        candidates.push(bundled_interpreter(&dir.join(BUNDLED_VENV_DIR)));
+    }
+    
+    for candidate in &candidates {
+        if interpreter_works(candidate).await {
+            log::info!("Using Python interpreter: {}", candidate.display());
+            return Ok(candidate.clone());
+        }
+    }
+    
+    let tried: Vec<String> = candidates.iter().map(|candidate| candidate.display().to_string()).collect();
+    Err(AppError::PythonMissing(format!(
+        "No working Python interpreter found (tried: {}). Install Python 3 or set {} to its path.",
+        tried.join(", "),
+        PYTHON_ENV_VAR
+    )))
+}
+
+/// Record a finished Python run in `run_metrics`. The command line is only stored
+/// when `capture_python_command_lines` is enabled in settings.
+pub async fn record_python_run(
+    pool: &sqlx::SqlitePool,
+    settings: &crate::settings::SettingsStore,
+    session_id: Option<&str>,
+    script_path: &str,
+    result: &PythonResult
+) -> Result<(), sqlx::Error> {
+    let command_line = settings
+        .get()
+        .capture_python_command_lines
+        .then_some(result.command_line.as_str());
+
+    sqlx::query(
+        "INSERT INTO run_metrics \
+         (id, session_id, script, success, exit_code, duration_ms, command_line, created_at) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(uuid::Uuid::new_v4().to_string())
+    .bind(session_id)
+    .bind(script_path)
+    .bind(result.success)
+    .bind(result.exit_code)
+    .bind(result.duration_ms as i64)
+    .bind(command_line)
+    .bind(chrono::Utc::now().to_rfc3339())
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Shortest WhisperX chunk; shorter chunks cut words at boundaries and hurt accuracy
+pub const MIN_CHUNK_LENGTH_SECS: u32 = 5;
+
+/// Longest WhisperX chunk, matching Whisper's 30 s input window
+pub const MAX_CHUNK_LENGTH_SECS: u32 = 30;
+
+/// WhisperX default; lower it on GPUs with limited VRAM
+pub const DEFAULT_CHUNK_LENGTH_SECS: u32 = 30;
+
+/// Validate a requested chunk length, defaulting to `DEFAULT_CHUNK_LENGTH_SECS`
+pub fn resolve_chunk_length(chunk_length_secs: Option<u32>) -> Result<u32, String> {
+    let chunk_length = chunk_length_secs.unwrap_or(DEFAULT_CHUNK_LENGTH_SECS);
+    if !(MIN_CHUNK_LENGTH_SECS..=MAX_CHUNK_LENGTH_SECS).contains(&chunk_length) {
+        return Err(format!(
+            "chunk_length_secs must be between {} and {} seconds, got {}",
+            MIN_CHUNK_LENGTH_SECS, MAX_CHUNK_LENGTH_SECS, chunk_length
+        ));
+    }
+    Ok(chunk_length)
+}
+
+/// Whisper model sizes WhisperX accepts, with their approximate download size in MB
+pub const WHISPER_MODELS: &[(&str, u64)] = &[
+    ("tiny", 75),
+    ("base", 145),
+    ("small", 485),
+    ("medium", 1530),
+    ("large-v2", 3090),
+    ("large-v3", 3090),
+];
+
+/// Reject model sizes WhisperX would only fail on after starting up
+pub fn validate_model_size(model_size: &str) -> Result<(), String> {
+    if WHISPER_MODELS.iter().any(|(name, _)| *name == model_size) {
+        return Ok(());
+    }
+    let known: Vec<&str> = WHISPER_MODELS.iter().map(|(name, _)| *name).collect();
+    Err(format!("Unknown model size: {} (expected one of: {})", model_size, known.join(", ")))
+}
+
+/// Hugging Face hub cache WhisperX downloads its faster-whisper models into:
+/// `HF_HUB_CACHE`, else `HF_HOME/hub`, else `.cache/huggingface/hub` under `home_dir`
+pub fn huggingface_cache_dir(home_dir: Option<&Path>) -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("HF_HUB_CACHE").filter(|value| !value.is_empty()) {
+        return Some(PathBuf::from(dir));
+    }
+    if let Some(dir) = std::env::var_os("HF_HOME").filter(|value| !value.is_empty()) {
+        return Some(PathBuf::from(dir).join("hub"));
+    }
+    home_dir.map(|home| home.join(".cache").join("huggingface").join("hub"))
+}
+
+/// Whether the faster-whisper model for `model_size` has been downloaded to `cache_dir`
+pub fn whisper_model_present(cache_dir: &Path, model_size: &str) -> bool {
+    let snapshots = cache_dir
+        .join(format!("models--Systran--faster-whisper-{}", model_size))
+        .join("snapshots");
+    std::fs::read_dir(snapshots)
+        .map(|mut entries| entries.next().is_some())
+        .unwrap_or(false)
+}
+
+/// Execute Python script for ASR and analysis integration, waiting for a free process
+/// slot first. A run exceeding `timeout` is killed and reaped, and reported with
+/// whatever output it produced so far.
 pub async fn execute_python_script(
+    app: &tauri::AppHandle,
     script_path: &str,
-    args: Vec<String>
-) -> Result<PythonResult, String> {
-    log::info!("Executing Python script: {} with args: {:?}", script_path, args);
+    args: Vec<String>,
+    timeout: Option<Duration>
+) -> Result<PythonResult, AppError> {
+    use tauri::Manager;
+    use tokio::io::AsyncReadExt;
+
+    let state = app.state::<crate::state::AppState>();
+    let interpreter = state.python_interpreter(app).await?;
+    let _permit = state.acquire_python_permit().await;
+    log::info!("Executing Python script: {} with args: {:?}", script_path, sanitize_args(&args));
+    
+    let command_line = reproducible_command_line(interpreter, script_path, &args, &captured_env(std::env::vars()));
+    let started = std::time::Instant::now();
     
-    let mut cmd = Command::new("python3")
+    let mut child = tokio::process::Command::new(interpreter)
         .arg(script_path)
         .args(&args)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
+        .kill_on_drop(true)
         .spawn()
-        .map_err(|e| format!("Failed to spawn Python process: {}", e))?;
+        .map_err(crate::error::python_spawn_error)?;
+    let mut stdout = child.stdout.take().expect("stdout is piped");
+    let mut stderr = child.stderr.take().expect("stderr is piped");
     
-    let output = cmd.wait_with_output()
-        .map_err(|e| format!("Failed to read Python output: {}", e))?;
+    // Read both pipes concurrently so neither fills up and stalls the script
+    let mut stdout_bytes = Vec::new();
+    let mut stderr_bytes = Vec::new();
+    let run = async {
+        let (stdout_read, stderr_read) = tokio::join!(
+            stdout.read_to_end(&mut stdout_bytes),
+            stderr.read_to_end(&mut stderr_bytes),
+        );
+        stdout_read.and(stderr_read)?;
+        child.wait().await
+    };
+    let outcome = match timeout {
+        Some(limit) => tokio::time::timeout(limit, run).await.ok(),
+        None => Some(run.await),
+    };
+    
+    let Some(status) = outcome else {
+        // kill() also waits for the process, so it doesn't linger as a zombie
+        if let Err(e) = child.kill().await {
+            log::warn!("Failed to kill timed out Python script {}: {}", script_path, e);
+        }
+        return Err(AppError::Timeout(format!(
+            "Python script {} timed out after {:.1} s\nstdout so far: {}\nstderr so far: {}",
+            script_path,
+            timeout.unwrap_or_default().as_secs_f64(),
+            String::from_utf8_lossy(&stdout_bytes).trim(),
+            String::from_utf8_lossy(&stderr_bytes).trim()
+        )));
+    };
+    let status = status.map_err(|e| AppError::Python(format!("Failed to read Python output: {}", e)))?;
     
     Ok(PythonResult {
-        success: output.status.success(),
-        stdout: String::from_utf8_lossy(&output.stdout).to_string(),
-        stderr: String::from_utf8_lossy(&output.stderr).to_string(),
-        exit_code: output.status.code(),
+        success: status.success(),
+        stdout: String::from_utf8_lossy(&stdout_bytes).to_string(),
+        stderr: String::from_utf8_lossy(&stderr_bytes).to_string(),
+        exit_code: status.code(),
+        duration_ms: started.elapsed().as_millis() as u64,
+        command_line,
     })
 }
 
-/// Start WhisperX transcription process
-pub async fn start_whisperx_transcription(
+/// Execute a long-running Python script, emitting each stdout line as `event_name`
+/// while it runs. Stderr is drained concurrently so a chatty script can't block on a
+/// full pipe. A run exceeding `timeout` is killed and reaped.
+pub async fn execute_python_script_streaming(
+    app: tauri::AppHandle,
+    script_path: &str,
+    args: Vec<String>,
+    event_name: &str,
+    timeout: Option<Duration>
+) -> Result<PythonResult, AppError> {
+    use tauri::{Emitter, Manager};
+    use tokio::io::AsyncReadExt;
+
+    let state = app.state::<crate::state::AppState>();
+    let interpreter = state.python_interpreter(&app).await?;
+    let _permit = state.acquire_python_permit().await;
+    log::info!("Executing Python script: {} with args: {:?}", script_path, sanitize_args(&args));
+    let command_line = reproducible_command_line(interpreter, script_path, &args, &captured_env(std::env::vars()));
+    let started = std::time::Instant::now();
+
+    let mut child = tokio::process::Command::new(interpreter)
+        .arg(script_path)
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(crate::error::python_spawn_error)?;
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let mut stderr = child.stderr.take().expect("stderr is piped");
+
+    let mut stdout_lines = Vec::new();
+    let mut stderr_bytes = Vec::new();
+    let run = async {
+        let (_, stderr_read) = tokio::join!(
+            read_output_lines(stdout, |line| {
+                if let Err(e) = app.emit(event_name, line) {
+                    log::warn!("Failed to emit {}: {}", event_name, e);
+                }
+                stdout_lines.push(line.to_string());
+            }),
+            stderr.read_to_end(&mut stderr_bytes),
+        );
+        if let Err(e) = stderr_read {
+            log::warn!("Failed to read stderr of {}: {}", script_path, e);
+        }
+        child.wait().await
+    };
+    let outcome = match timeout {
+        Some(limit) => tokio::time::timeout(limit, run).await.ok(),
+        None => Some(run.await),
+    };
+
+    let Some(status) = outcome else {
+        // kill() also waits for the process, so it doesn't linger as a zombie
+        if let Err(e) = child.kill().await {
+            log::warn!("Failed to kill timed out Python script {}: {}", script_path, e);
+        }
+        return Err(AppError::Timeout(format!(
+            "Python script {} timed out after {:.1} s\nstdout so far: {}\nstderr so far: {}",
+            script_path,
+            timeout.unwrap_or_default().as_secs_f64(),
+            stdout_lines.join("\n").trim(),
+            String::from_utf8_lossy(&stderr_bytes).trim()
+        )));
+    };
+    let status = status.map_err(|e| AppError::Python(format!("Failed to wait for Python process: {}", e)))?;
+
+    Ok(PythonResult {
+        success: status.success(),
+        stdout: stdout_lines.join("\n"),
+        stderr: String::from_utf8_lossy(&stderr_bytes).to_string(),
+        exit_code: status.code(),
+        duration_ms: started.elapsed().as_millis() as u64,
+        command_line,
+    })
+}
+
+/// CLI wrapper around the WhisperX pipeline
+const WHISPERX_SCRIPT: &str = "src/lib/transcription/whisperx_cli.py";
+
+/// Stderr kept for the error message of a failed run
+const MAX_STDERR_TAIL_LINES: usize = 20;
+
+/// WhisperX pipeline stages in execution order, with the share of total runtime each
+/// typically takes; used to turn per-stage progress into overall progress
+pub const WHISPERX_STAGES: &[(&str, f64)] = &[
+    ("load model", 0.03),
+    (DOWNLOAD_STAGE, 0.07),
+    ("transcribe", 0.60),
+    ("align", 0.15),
+    ("diarize", 0.15),
+];
+
+/// Stage while the Whisper model is fetched on first use. It sits between loading
+/// and transcribing, so a run without a download simply skips its share.
+pub const DOWNLOAD_STAGE: &str = "Downloading model";
+
+/// Model weight files whose download progress stands for the whole download;
+/// small config and vocabulary files finish instantly and would read as done
+const MODEL_WEIGHT_FILES: &[&str] = &["model.bin", ".safetensors"];
+
+/// What a line of WhisperX output says about the run
+#[derive(Debug, Clone, PartialEq)]
+pub enum WhisperxUpdate {
+    Stage(&'static str),
+    /// Fraction of the current stage completed
+    StageProgress(f64),
+    /// Fraction of the model download completed, `None` for files not tracked
+    Download(Option<f64>),
+    /// A stage together with the fraction of it completed
+    StageAt(&'static str, f64),
+}
+
+/// Percentage in a `--print_progress` or tqdm line, as a fraction
+fn parse_percent(line: &str) -> Option<f64> {
+    let percent_end = line.find('%')?;
+    let digits_start = line[..percent_end]
+        .rfind(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    let percent: f64 = line[digits_start..percent_end].parse().ok()?;
+    (0.0..=100.0).contains(&percent).then_some(percent / 100.0)
+}
+
+/// Recognize Hugging Face hub download bars ("model.bin:  42%|####  | 1.30G/3.09G",
+/// "Downloading (…)model.bin: 42%|"), which name the file before the bar
+fn parse_download_line(line: &str) -> Option<WhisperxUpdate> {
+    let (file, bar) = line.split_once(':')?;
+    if !bar.contains("%|") {
+        return None;
+    }
+    let file = file.trim().to_lowercase();
+    let is_download = file.starts_with("downloading")
+        || [".bin", ".json", ".txt", ".safetensors"].iter().any(|ext| file.ends_with(ext));
+    if !is_download {
+        return None;
+    }
+
+    let is_weights = MODEL_WEIGHT_FILES.iter().any(|name| file.ends_with(name));
+    Some(WhisperxUpdate::Download(if is_weights { parse_percent(bar) } else { None }))
+}
+
+/// Pipeline stage named in a structured progress line; WhisperX's nouns are accepted too
+fn json_stage(name: &str) -> Option<&'static str> {
+    match name.trim().to_lowercase().replace('_', " ").as_str() {
+        "load model" | "loading model" => Some("load model"),
+        "download" | "downloading model" => Some(DOWNLOAD_STAGE),
+        "transcribe" | "transcription" => Some("transcribe"),
+        "align" | "alignment" => Some("align"),
+        "diarize" | "diarization" => Some("diarize"),
+        _ => None,
+    }
+}
+
+/// Structured progress line such as `{"stage": "align", "progress": 0.4}`, where
+/// `progress` is the completed fraction of the stage and either field may be missing
+fn parse_json_progress(value: &serde_json::Value) -> Option<WhisperxUpdate> {
+    let stage = value.get("stage").and_then(|stage| stage.as_str()).and_then(json_stage);
+    let progress = value
+        .get("progress")
+        .and_then(|progress| progress.as_f64())
+        .filter(|progress| (0.0..=1.0).contains(progress));
+
+    match (stage, progress) {
+        (Some(DOWNLOAD_STAGE), fraction) => Some(WhisperxUpdate::Download(fraction)),
+        (Some(stage), Some(fraction)) => Some(WhisperxUpdate::StageAt(stage, fraction)),
+        (Some(stage), None) => Some(WhisperxUpdate::Stage(stage)),
+        (None, Some(fraction)) => Some(WhisperxUpdate::StageProgress(fraction)),
+        (None, None) => None,
+    }
+}
+
+/// Recognize structured JSON progress lines, falling back to WhisperX stage banners
+/// (">>Performing alignment..."), model download bars and `--print_progress` / tqdm
+/// percentages ("Progress: 42.50%...", " 42%|####")
+pub fn parse_whisperx_line(line: &str) -> Option<WhisperxUpdate> {
+    let line = line.trim();
+    if line.starts_with('{') {
+        if let Ok(value @ serde_json::Value::Object(_)) = serde_json::from_str(line) {
+            return parse_json_progress(&value);
+        }
+    }
+    let lower = line.to_lowercase();
+
+    if lower.contains("performing transcription") {
+        return Some(WhisperxUpdate::Stage("transcribe"));
+    }
+    if lower.contains("performing alignment") || lower.contains("loading align") {
+        return Some(WhisperxUpdate::Stage("align"));
+    }
+    if lower.contains("performing diarization") || lower.contains("diarization model") {
+        return Some(WhisperxUpdate::Stage("diarize"));
+    }
+    if lower.starts_with("loading") && lower.contains("model") {
+        return Some(WhisperxUpdate::Stage("load model"));
+    }
+    if let Some(update) = parse_download_line(line) {
+        return Some(update);
+    }
+
+    parse_percent(line).map(WhisperxUpdate::StageProgress)
+}
+
+/// Overall progress when `stage` is `stage_fraction` complete
+pub fn overall_progress(stage: &str, stage_fraction: f64) -> f64 {
+    let mut done = 0.0;
+    for (name, weight) in WHISPERX_STAGES {
+        if *name == stage {
+            return (done + weight * stage_fraction.clamp(0.0, 1.0)).min(1.0);
+        }
+        done += weight;
+    }
+    done.min(1.0)
+}
+
+fn whisperx_args(
     audio_file: &str,
+    output_dir: &str,
     language: Option<&str>,
-    model_size: Option<&str>
-) -> Result<String, String> {
+    model_size: Option<&str>,
+    chunk_length_secs: Option<u32>
+) -> Result<Vec<String>, String> {
+    let chunk_length = resolve_chunk_length(chunk_length_secs)?;
     let mut args = vec![
         "--audio".to_string(),
         audio_file.to_string(),
         "--output_dir".to_string(),
-        "/tmp/transcription".to_string(),
+        output_dir.to_string(),
+        "--chunk_size".to_string(),
+        chunk_length.to_string(),
+        "--print_progress".to_string(),
+        "True".to_string(),
     ];
     
     if let Some(lang) = language {
@@ -65,20 +595,229 @@ pub async fn start_whisperx_transcription(
         args.extend(vec!["--model".to_string(), model.to_string()]);
     }
     
-    let result = execute_python_script("src/lib/transcription/whisperx_cli.py", args).await?;
-    
-    if result.success {
-        Ok(result.stdout)
-    } else {
-        Err(format!("WhisperX failed: {}", result.stderr))
+    Ok(args)
+}
+
+/// Feed every line of `reader` to `on_line`, splitting on `\r` as well as `\n`
+/// because progress bars redraw in place
+pub async fn read_output_lines<R, F>(reader: R, mut on_line: F)
+where
+    R: tokio::io::AsyncRead + Unpin,
+    F: FnMut(&str),
+{
+    use tokio::io::AsyncReadExt;
+
+    let mut reader = reader;
+    let mut pending: Vec<u8> = Vec::new();
+    let mut buffer = [0u8; 4096];
+    loop {
+        let read = match reader.read(&mut buffer).await {
+            Ok(0) | Err(_) => break,
+            Ok(read) => read,
+        };
+        for byte in &buffer[..read] {
+            if *byte == b'\n' || *byte == b'\r' {
+                if !pending.is_empty() {
+                    on_line(&String::from_utf8_lossy(&pending));
+                    pending.clear();
+                }
+            } else {
+                pending.push(*byte);
+            }
+        }
+    }
+    if !pending.is_empty() {
+        on_line(&String::from_utf8_lossy(&pending));
     }
 }
 
-/// Execute LD-3.4 marker analysis
+/// Launch WhisperX for `session_id` and return once it is running.
+/// Output is parsed in the background into the session's `AppState::transcriptions`
+/// entry; the returned handle resolves once the process has exited, with an
+/// error unless the run completed. A run exceeding `timeout` is killed and
+/// ends in the "timeout" stage with `AppError::Timeout`.
+pub async fn start_whisperx_transcription(
+    app: tauri::AppHandle,
+    session_id: &str,
+    audio_file: &str,
+    language: Option<&str>,
+    model_size: Option<&str>,
+    chunk_length_secs: Option<u32>,
+    timeout: Option<Duration>
+) -> Result<tauri::async_runtime::JoinHandle<Result<(), AppError>>, AppError> {
+    use tauri::Manager;
+
+    let output_dir = app
+        .state::<crate::paths::AppPaths>()
+        .transcription_output(session_id)
+        .to_string_lossy()
+        .to_string();
+    let args = whisperx_args(audio_file, &output_dir, language, model_size, chunk_length_secs)
+        .map_err(AppError::Validation)?;
+    tokio::fs::create_dir_all(&output_dir)
+        .await
+        .map_err(|e| AppError::Io(format!("Failed to create transcription output directory: {}", e)))?;
+    
+    let state = app.state::<crate::state::AppState>();
+    let interpreter = state.python_interpreter(&app).await?.to_path_buf();
+    // Held by the background task until WhisperX exits
+    let permit = state.acquire_python_permit().await;
+    
+    log::info!("Executing Python script: {} with args: {:?}", WHISPERX_SCRIPT, sanitize_args(&args));
+    let command_line = reproducible_command_line(&interpreter, WHISPERX_SCRIPT, &args, &captured_env(std::env::vars()));
+    let started = std::time::Instant::now();
+    
+    let mut child = tokio::process::Command::new(&interpreter)
+        .arg(WHISPERX_SCRIPT)
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(crate::error::python_spawn_error)?;
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let stderr = child.stderr.take().expect("stderr is piped");
+    
+    // The declared length bounds the ETA; files without one just go unbounded
+    let probe_path = std::path::PathBuf::from(audio_file);
+    let audio_duration = tauri::async_runtime::spawn_blocking(move || {
+        crate::audio_processing::probe_audio(&probe_path, None).ok().and_then(|probe| probe.duration_secs)
+    })
+    .await
+    .unwrap_or(None);
+    app.state::<crate::state::AppState>().transcriptions.start(session_id, audio_duration);
+    // Cancelled through `cancel_transcription` or the generic `cancel` under the run id
+    let cancel_token = app.state::<crate::cancellation::CancellationManager>().register_with_id(session_id);
+    
+    let session_id = session_id.to_string();
+    let run = tauri::async_runtime::spawn(async move {
+        let state = app.state::<crate::state::AppState>();
+        let jobs = &state.transcriptions;
+        let handle_line = |line: &str| {
+            use tauri::Emitter;
+
+            match parse_whisperx_line(line) {
+                Some(WhisperxUpdate::Stage(stage)) => jobs.set_stage(&session_id, stage),
+                Some(WhisperxUpdate::StageProgress(fraction)) => jobs.set_stage_progress(&session_id, fraction),
+                Some(WhisperxUpdate::StageAt(stage, fraction)) => {
+                    jobs.set_stage(&session_id, stage);
+                    jobs.set_stage_progress(&session_id, fraction);
+                }
+                Some(WhisperxUpdate::Download(fraction)) => {
+                    jobs.set_stage(&session_id, DOWNLOAD_STAGE);
+                    if let Some(fraction) = fraction {
+                        jobs.set_stage_progress(&session_id, fraction);
+                    }
+                }
+                None => return,
+            }
+            if let Some(progress) = jobs.progress(&session_id) {
+                if let Err(e) = app.emit("transcription-progress", &progress) {
+                    log::warn!("Failed to emit transcription progress: {}", e);
+                }
+            }
+        };
+        
+        // WhisperX logs stages to stderr and progress to stdout depending on version; read both
+        let mut stderr_tail: std::collections::VecDeque<String> = std::collections::VecDeque::new();
+        let outputs = async {
+            tokio::join!(
+                read_output_lines(stdout, handle_line),
+                read_output_lines(stderr, |line| {
+                    handle_line(line);
+                    if stderr_tail.len() == MAX_STDERR_TAIL_LINES {
+                        stderr_tail.pop_front();
+                    }
+                    stderr_tail.push_back(line.to_string());
+                }),
+            )
+        };
+        
+        let time_limit = async {
+            match timeout {
+                Some(limit) => tokio::time::sleep(limit).await,
+                None => std::future::pending().await,
+            }
+        };
+        
+        // Pipes close when the process exits; a cancel or timeout kills it first
+        let (cancelled, timed_out) = tokio::select! {
+            _ = outputs => (false, false),
+            _ = cancel_token.cancelled() => (true, false),
+            _ = time_limit => (false, true),
+        };
+        if cancelled || timed_out {
+            if cancelled {
+                log::info!("Killing WhisperX for cancelled session: {}", session_id);
+            } else {
+                log::error!(
+                    "Killing WhisperX for session {} after {} s",
+                    session_id,
+                    timeout.unwrap_or_default().as_secs()
+                );
+            }
+            if let Err(e) = child.kill().await {
+                log::warn!("Failed to kill WhisperX for session {}: {}", session_id, e);
+            }
+        }
+        
+        let status = child.wait().await;
+        let stderr_tail = Vec::from(stderr_tail).join("\n");
+        let (success, exit_code) = match &status {
+            Ok(status) => (status.success(), status.code()),
+            Err(_) => (false, None),
+        };
+        let outcome = if cancelled {
+            jobs.finish(&session_id, "cancelled");
+            Err(AppError::Cancelled(format!("Transcription cancelled for session: {}", session_id)))
+        } else if timed_out {
+            jobs.finish(&session_id, crate::transcription_commands::TIMEOUT_STAGE);
+            Err(AppError::Timeout(format!(
+                "WhisperX timed out after {} s for session: {}",
+                timeout.unwrap_or_default().as_secs(),
+                session_id
+            )))
+        } else if success {
+            jobs.finish(&session_id, "completed");
+            Ok(())
+        } else {
+            log::error!("WhisperX failed for session {}: {}", session_id, stderr_tail);
+            jobs.finish(&session_id, "failed");
+            Err(AppError::Python(format!("WhisperX exited with code {:?}", exit_code)))
+        };
+        app.state::<crate::cancellation::CancellationManager>().finish(&session_id);
+        
+        let result = PythonResult {
+            success,
+            stdout: String::new(),
+            stderr: stderr_tail,
+            exit_code,
+            duration_ms: started.elapsed().as_millis() as u64,
+            command_line,
+        };
+        let settings = app.state::<crate::settings::SettingsStore>();
+        if let Err(e) = record_python_run(&state.db, &settings, Some(&session_id), WHISPERX_SCRIPT, &result).await {
+            log::warn!("Failed to record WhisperX run metrics: {}", e);
+        }
+        drop(permit);
+        outcome
+    });
+    
+    Ok(run)
+}
+
+/// Event carrying each stdout line of a marker analysis run as it is printed
+pub const ANALYSIS_OUTPUT_EVENT: &str = "analysis-output";
+
+/// Execute LD-3.4 marker analysis. The script reports each finished level as a
+/// `{"stage", "markers"}` line, streamed as `analysis-output` events, and prints
+/// the marker array as its last line, which is returned.
 pub async fn analyze_markers(
+    app: &tauri::AppHandle,
     transcript_file: &str,
-    session_id: &str
-) -> Result<String, String> {
+    session_id: &str,
+    timeout: Option<Duration>
+) -> Result<String, AppError> {
     let args = vec![
         "--transcript".to_string(),
         transcript_file.to_string(),
@@ -88,20 +827,36 @@ pub async fn analyze_markers(
         "json".to_string(),
     ];
     
-    let result = execute_python_script("src/lib/analysis/marker_analysis_cli.py", args).await?;
+    let result = execute_python_script_streaming(
+        app.clone(),
+        "src/lib/analysis/marker_analysis_cli.py",
+        args,
+        ANALYSIS_OUTPUT_EVENT,
+        timeout,
+    )
+    .await?;
     
-    if result.success {
-        Ok(result.stdout)
-    } else {
-        Err(format!("Marker analysis failed: {}", result.stderr))
+    if !result.success {
+        return Err(result.into());
     }
+    result
+        .stdout
+        .lines()
+        .rev()
+        .find(|line| !line.trim().is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| AppError::Python("Marker analysis printed no markers".to_string()))
 }
 
 /// Calculate rapport indicators from markers
+// The app computes rapport curves in Rust; this wrapper is kept for the Python calculator
+#[allow(dead_code)]
 pub async fn calculate_rapport_indicators(
+    app: &tauri::AppHandle,
     markers_file: &str,
-    session_id: &str
-) -> Result<String, String> {
+    session_id: &str,
+    timeout: Option<Duration>
+) -> Result<String, AppError> {
     let args = vec![
         "--markers".to_string(),
         markers_file.to_string(),
@@ -109,11 +864,213 @@ pub async fn calculate_rapport_indicators(
         session_id.to_string(),
     ];
     
-    let result = execute_python_script("src/lib/analysis/rapport_calculation_cli.py", args).await?;
+    let result = execute_python_script(app, "src/lib/analysis/rapport_calculation_cli.py", args, timeout).await?;
     
     if result.success {
         Ok(result.stdout)
     } else {
-        Err(format!("Rapport calculation failed: {}", result.stderr))
+        Err(result.into())
+    }
+}
+
+/// Probe reporting the interpreter version and which backend packages import
+const ENVIRONMENT_CHECK_SCRIPT: &str = "src/cli/environment_check_cli.py";
+
+/// Importing torch can take a while on a cold start, but the probe must not hang the UI
+const ENVIRONMENT_CHECK_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EnvHealth {
+    pub python_version: String,
+    pub whisperx_available: bool,
+    pub torch_cuda: bool,
+    /// Packages and LD-3.4 modules that failed to import
+    pub missing_packages: Vec<String>,
+}
+
+#[tauri::command]
+pub async fn check_python_environment(
+    app: tauri::AppHandle
+) -> Result<EnvHealth, AppError> {
+    log::info!("Checking Python environment");
+    
+    let result = execute_python_script(&app, ENVIRONMENT_CHECK_SCRIPT, Vec::new(), Some(ENVIRONMENT_CHECK_TIMEOUT))
+        .await?;
+    
+    if !result.success {
+        // The last stderr line carries the exception; the full traceback only goes to the log
+        log::error!("Python environment check failed: {}", result.stderr);
+        let reason = result.stderr.lines().last().unwrap_or("no error output").trim();
+        return Err(AppError::Python(format!(
+            "Python environment check failed (exit code {}): {}",
+            result.exit_code.map(|code| code.to_string()).unwrap_or_else(|| "none".to_string()),
+            reason
+        )));
+    }
+    
+    serde_json::from_str(result.stdout.trim())
+        .map_err(|e| AppError::Python(format!("Failed to parse environment check output: {}", e)))
+}
+
+#[tauri::command]
+pub async fn get_backend_load(
+    state: State<'_, crate::state::AppState>
+) -> Result<crate::state::BackendLoad, AppError> {
+    Ok(state.backend_load())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{memory_pool, temp_dir};
+
+    #[test]
+    fn chunk_length_defaults_and_accepts_its_bounds() {
+        assert_eq!(resolve_chunk_length(None), Ok(DEFAULT_CHUNK_LENGTH_SECS));
+        assert_eq!(resolve_chunk_length(Some(MIN_CHUNK_LENGTH_SECS)), Ok(MIN_CHUNK_LENGTH_SECS));
+        assert_eq!(resolve_chunk_length(Some(MAX_CHUNK_LENGTH_SECS)), Ok(MAX_CHUNK_LENGTH_SECS));
+        assert_eq!(resolve_chunk_length(Some(12)), Ok(12));
+    }
+
+    #[test]
+    fn chunk_length_outside_its_bounds_is_rejected() {
+        for chunk_length in [0, MIN_CHUNK_LENGTH_SECS - 1, MAX_CHUNK_LENGTH_SECS + 1, u32::MAX] {
+            let error = resolve_chunk_length(Some(chunk_length)).unwrap_err();
+            assert!(error.ends_with(&format!("got {}", chunk_length)), "{}", error);
+        }
+    }
+
+    #[test]
+    fn whisperx_gets_the_resolved_chunk_size() {
+        let args = whisperx_args("in.wav", "out", Some("de"), None, Some(10)).unwrap();
+        let chunk_size = args.iter().position(|arg| arg == "--chunk_size").unwrap();
+        assert_eq!(args[chunk_size + 1], "10");
+        assert!(!args.iter().any(|arg| arg == "--model"));
+
+        let args = whisperx_args("in.wav", "out", None, None, None).unwrap();
+        assert_eq!(args[chunk_size + 1], DEFAULT_CHUNK_LENGTH_SECS.to_string());
+        assert!(whisperx_args("in.wav", "out", None, None, Some(60)).is_err());
     }
-}
\ No newline at end of file
+
+    fn strings(words: &[&str]) -> Vec<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn secret_flag_values_are_redacted() {
+        let args = strings(&[
+            "--audio", "in.wav", "--hf_token", "hf_abc", "--api-key=xyz", "-password", "pw", "--model=base", "--output_dir",
+            "out",
+        ]);
+
+        assert_eq!(
+            sanitize_args(&args),
+            strings(&[
+                "--audio", "in.wav", "--hf_token", REDACTED, "--api-key=[REDACTED]", "-password", REDACTED, "--model=base",
+                "--output_dir", "out",
+            ])
+        );
+    }
+
+    #[test]
+    fn only_pipeline_environment_is_captured() {
+        let vars = [("PATH", "/usr/bin"), ("HF_TOKEN", "hf_abc"), ("CUDA_VISIBLE_DEVICES", "0"), ("HOME", "/home/me")]
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()));
+
+        assert_eq!(
+            captured_env(vars),
+            [
+                ("CUDA_VISIBLE_DEVICES".to_string(), "0".to_string()),
+                ("HF_TOKEN".to_string(), REDACTED.to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn command_line_is_quoted_for_the_shell() {
+        let env = [("CUDA_VISIBLE_DEVICES".to_string(), "0,1".to_string())];
+        let args = strings(&["--audio", "/tmp/Session 1/it's.wav", "--hf_token", "hf_abc"]);
+
+        let line = reproducible_command_line(Path::new("/usr/bin/python3"), "whisperx_cli.py", &args, &env);
+
+        assert_eq!(
+            line,
+            "CUDA_VISIBLE_DEVICES=0,1 /usr/bin/python3 whisperx_cli.py --audio '/tmp/Session 1/it'\\''s.wav' \
+             --hf_token '[REDACTED]'"
+        );
+        assert!(!line.contains("hf_abc"));
+    }
+
+    #[tokio::test]
+    async fn command_lines_are_stored_only_when_enabled() {
+        let pool = memory_pool().await;
+        let dir = temp_dir();
+        let settings = crate::settings::SettingsStore::load(dir.join("settings.json"));
+        let result = PythonResult {
+            success: true,
+            stdout: String::new(),
+            stderr: String::new(),
+            exit_code: Some(0),
+            duration_ms: 1200,
+            command_line: "python3 whisperx_cli.py --audio in.wav".to_string(),
+        };
+
+        record_python_run(&pool, &settings, None, "whisperx_cli.py", &result).await.unwrap();
+        settings
+            .update(crate::settings::AppSettings {
+                capture_python_command_lines: true,
+                ..settings.get()
+            })
+            .unwrap();
+        record_python_run(&pool, &settings, None, "whisperx_cli.py", &result).await.unwrap();
+
+        let stored: Vec<Option<String>> =
+            sqlx::query_scalar("SELECT command_line FROM run_metrics ORDER BY command_line IS NOT NULL")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(stored, [None, Some(result.command_line.clone())]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn structured_progress_lines_are_parsed() {
+        assert_eq!(
+            parse_whisperx_line(r#"{"stage": "align", "progress": 0.4}"#),
+            Some(WhisperxUpdate::StageAt("align", 0.4))
+        );
+        assert_eq!(
+            parse_whisperx_line(r#"  {"stage": "transcription"}"#),
+            Some(WhisperxUpdate::Stage("transcribe"))
+        );
+        assert_eq!(parse_whisperx_line(r#"{"progress": 0.25}"#), Some(WhisperxUpdate::StageProgress(0.25)));
+        assert_eq!(
+            parse_whisperx_line(r#"{"stage": "download", "progress": 1}"#),
+            Some(WhisperxUpdate::Download(Some(1.0)))
+        );
+        assert_eq!(
+            parse_whisperx_line(r#"{"stage": "load_model"}"#),
+            Some(WhisperxUpdate::Stage("load model"))
+        );
+    }
+
+    #[test]
+    fn structured_lines_without_usable_fields_are_ignored() {
+        assert_eq!(parse_whisperx_line(r#"{"stage": "warming up", "progress": 42}"#), None);
+        // A percentage in another field must not be mistaken for progress
+        assert_eq!(parse_whisperx_line(r#"{"message": "50% done"}"#), None);
+    }
+
+    #[test]
+    fn text_progress_is_parsed_when_the_line_is_not_json() {
+        assert_eq!(parse_whisperx_line(">>Performing alignment..."), Some(WhisperxUpdate::Stage("align")));
+        assert_eq!(parse_whisperx_line("Progress: 42.50%..."), Some(WhisperxUpdate::StageProgress(0.425)));
+        // Looks like JSON but isn't, so the text rules apply
+        assert_eq!(parse_whisperx_line("{stage: align} 10%"), Some(WhisperxUpdate::StageProgress(0.1)));
+        assert_eq!(
+            parse_whisperx_line("model.bin:  50%|#####     | 1.5G/3.0G"),
+            Some(WhisperxUpdate::Download(Some(0.5)))
+        );
+    }
+}
@@ -0,0 +1,650 @@
+use flacenc::component::{BitRepr, StreamInfo};
+use flacenc::error::Verify;
+use flacenc::source::{Fill, FrameBuf};
+use std::fs::File;
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Encodings `start_recording` can write while capturing
+pub const RECORDING_ENCODINGS: &[&str] = &["wav", "flac"];
+
+/// Samples per channel in each FLAC frame
+const FLAC_BLOCK_SIZE: usize = 4096;
+
+/// Smallest frame the FLAC encoder accepts
+const MIN_FLAC_BLOCK_SIZE: usize = flacenc::constant::MIN_BLOCK_SIZE;
+
+/// Captured audio is stored as 16-bit PCM in either container
+const RECORDING_BITS_PER_SAMPLE: u16 = 16;
+
+/// Byte offset of the STREAMINFO body: "fLaC" plus the 4-byte metadata block header
+const FLAC_STREAMINFO_OFFSET: u64 = 8;
+
+/// Destination for captured audio, fed incrementally from the capture callback
+pub trait RecordingSink: Send {
+    /// Append interleaved samples in -1.0..=1.0
+    fn write_samples(&mut self, samples: &[f32]) -> Result<(), String>;
+
+    /// Flush buffered audio and complete the container headers
+    fn finalize(self: Box<Self>) -> Result<(), String>;
+}
+
+/// File extension for a recording encoding
+pub fn recording_extension(encoding: &str) -> Result<&'static str, String> {
+    match encoding {
+        "wav" => Ok("wav"),
+        "flac" => Ok("flac"),
+        other => Err(format!(
+            "Unsupported recording encoding: {} (expected one of: {})",
+            other,
+            RECORDING_ENCODINGS.join(", ")
+        )),
+    }
+}
+
+/// Open a sink writing `encoding` to `path`
+pub fn create_sink(
+    path: &Path,
+    encoding: &str,
+    channels: u16,
+    sample_rate: u32
+) -> Result<Box<dyn RecordingSink>, String> {
+    match recording_extension(encoding)? {
+        "flac" => Ok(Box::new(FlacSink::create(path, channels, sample_rate)?)),
+        _ => Ok(Box::new(WavSink::create(path, channels, sample_rate)?)),
+    }
+}
+
+fn to_pcm16(sample: f32) -> i32 {
+    (sample.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i32
+}
+
+pub struct WavSink {
+    writer: hound::WavWriter<BufWriter<File>>,
+}
+
+impl WavSink {
+    pub fn create(path: &Path, channels: u16, sample_rate: u32) -> Result<Self, String> {
+        let spec = hound::WavSpec {
+            channels,
+            sample_rate,
+            bits_per_sample: RECORDING_BITS_PER_SAMPLE,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let writer = hound::WavWriter::create(path, spec)
+            .map_err(|e| format!("Failed to create WAV file {}: {}", path.display(), e))?;
+        Ok(Self { writer })
+    }
+}
+
+impl RecordingSink for WavSink {
+    fn write_samples(&mut self, samples: &[f32]) -> Result<(), String> {
+        samples
+            .iter()
+            .try_for_each(|sample| self.writer.write_sample(to_pcm16(*sample) as i16))
+            .map_err(|e| format!("Failed to write WAV samples: {}", e))
+    }
+
+    fn finalize(self: Box<Self>) -> Result<(), String> {
+        self.writer
+            .finalize()
+            .map_err(|e| format!("Failed to finalize WAV file: {}", e))
+    }
+}
+
+/// Streaming FLAC encoder: each full block is encoded and written as soon as it
+/// is captured, and STREAMINFO is rewritten with the final totals on finalize.
+pub struct FlacSink {
+    file: BufWriter<File>,
+    config: flacenc::error::Verified<flacenc::config::Encoder>,
+    stream_info: StreamInfo,
+    framebuf: FrameBuf,
+    pending: Vec<i32>,
+    channels: usize,
+    frame_number: usize,
+    total_frames: u64,
+    largest_block: usize,
+    min_frame_bytes: usize,
+    max_frame_bytes: usize,
+}
+
+fn bit_bytes<T: BitRepr>(component: &T) -> Result<Vec<u8>, String> {
+    let mut sink = flacenc::bitsink::MemSink::<u8>::with_capacity(component.count_bits());
+    component
+        .write(&mut sink)
+        .map_err(|e| format!("Failed to serialize FLAC data: {:?}", e))?;
+    Ok(sink.into_inner())
+}
+
+impl FlacSink {
+    pub fn create(path: &Path, channels: u16, sample_rate: u32) -> Result<Self, String> {
+        let channels = channels as usize;
+        let stream_info = StreamInfo::new(sample_rate as usize, channels, RECORDING_BITS_PER_SAMPLE as usize)
+            .map_err(|e| format!("Invalid FLAC stream parameters: {:?}", e))?;
+        let mut config = flacenc::config::Encoder::default();
+        config.block_size = FLAC_BLOCK_SIZE;
+        // Encoding runs on the capture writer thread; stay single-threaded
+        config.multithread = false;
+        let config = config
+            .into_verified()
+        .map_err(|(_, e)| format!("Invalid FLAC encoder configuration: {:?}", e))?;
+        let framebuf = FrameBuf::with_size(channels, FLAC_BLOCK_SIZE)
+            .map_err(|e| format!("Failed to allocate FLAC frame buffer: {:?}", e))?;
+
+        let file = File::create(path)
+            .map_err(|e| format!("Failed to create FLAC file {}: {}", path.display(), e))?;
+        let mut sink = Self {
+            file: BufWriter::new(file),
+            config,
+            stream_info,
+            framebuf,
+            pending: Vec::with_capacity(FLAC_BLOCK_SIZE * channels),
+            channels,
+            frame_number: 0,
+            total_frames: 0,
+            largest_block: 0,
+            min_frame_bytes: usize::MAX,
+            max_frame_bytes: 0,
+        };
+
+        // STREAMINFO is the only metadata block; its totals are patched on finalize
+        let header = bit_bytes(&sink.stream_info)?;
+        let mut preamble = b"fLaC".to_vec();
+        preamble.push(0x80);
+        preamble.extend_from_slice(&(header.len() as u32).to_be_bytes()[1..]);
+        preamble.extend_from_slice(&header);
+        sink.file
+            .write_all(&preamble)
+            .map_err(|e| format!("Failed to write FLAC header: {}", e))?;
+
+        Ok(sink)
+    }
+
+    fn encode_block(&mut self, samples_per_channel: usize) -> Result<(), String> {
+        let block_len = samples_per_channel * self.channels;
+        if samples_per_channel != self.framebuf.size() {
+            self.framebuf.resize(samples_per_channel);
+        }
+        self.framebuf
+            .fill_interleaved(&self.pending[..block_len])
+            .map_err(|e| format!("Failed to buffer FLAC samples: {:?}", e))?;
+
+        let frame = flacenc::encode_fixed_size_frame(
+            &self.config,
+            &self.framebuf,
+            self.frame_number,
+            &self.stream_info,
+        )
+        .map_err(|e| format!("Failed to encode FLAC frame: {:?}", e))?;
+        let bytes = bit_bytes(&frame)?;
+        self.file
+            .write_all(&bytes)
+            .map_err(|e| format!("Failed to write FLAC frame: {}", e))?;
+
+        self.min_frame_bytes = self.min_frame_bytes.min(bytes.len());
+        self.max_frame_bytes = self.max_frame_bytes.max(bytes.len());
+        self.frame_number += 1;
+        self.total_frames += samples_per_channel as u64;
+        self.largest_block = self.largest_block.max(samples_per_channel);
+        self.pending.drain(..block_len);
+        Ok(())
+    }
+}
+
+impl RecordingSink for FlacSink {
+    fn write_samples(&mut self, samples: &[f32]) -> Result<(), String> {
+        self.pending.extend(samples.iter().map(|sample| to_pcm16(*sample)));
+        // Hold back enough samples that the final frame never drops below the
+        // encoder's minimum block size
+        while self.pending.len() >= (FLAC_BLOCK_SIZE + MIN_FLAC_BLOCK_SIZE) * self.channels {
+            self.encode_block(FLAC_BLOCK_SIZE)?;
+        }
+        Ok(())
+    }
+
+    fn finalize(mut self: Box<Self>) -> Result<(), String> {
+        // Only the last frame of a fixed-blocksize stream may differ in size
+        let mut remaining = self.pending.len() / self.channels;
+        if remaining > 0 && remaining < MIN_FLAC_BLOCK_SIZE {
+            // Recordings this short are padded with a few milliseconds of silence
+            self.pending.resize(MIN_FLAC_BLOCK_SIZE * self.channels, 0);
+            remaining = MIN_FLAC_BLOCK_SIZE;
+        }
+        if remaining > 0 {
+            self.encode_block(remaining)?;
+        }
+
+        let (min_block, max_block) = match self.frame_number {
+            0 => (FLAC_BLOCK_SIZE, FLAC_BLOCK_SIZE),
+            1 => (self.largest_block, self.largest_block),
+            _ => (FLAC_BLOCK_SIZE, self.largest_block.max(FLAC_BLOCK_SIZE)),
+        };
+        self.stream_info
+            .set_block_sizes(min_block, max_block)
+            .map_err(|e| format!("Invalid FLAC block size: {:?}", e))?;
+        if self.frame_number > 0 {
+            self.stream_info
+                .set_frame_sizes(self.min_frame_bytes, self.max_frame_bytes)
+                .map_err(|e| format!("Invalid FLAC frame sizes: {:?}", e))?;
+        }
+        self.stream_info.set_total_samples(self.total_frames as usize);
+
+        let header = bit_bytes(&self.stream_info)?;
+        self.file
+            .seek(SeekFrom::Start(FLAC_STREAMINFO_OFFSET))
+            .and_then(|_| self.file.write_all(&header))
+            .and_then(|_| self.file.flush())
+            .map_err(|e| format!("Failed to finalize FLAC file: {}", e))
+    }
+}
+
+/// Peak and RMS of the most recent capture buffer, in linear full-scale units
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RecordingLevel {
+    pub peak: f32,
+    pub rms: f32,
+}
+
+/// Wraps any sink with level metering and duration tracking, independent of encoding
+pub struct MeteredSink {
+    inner: Box<dyn RecordingSink>,
+    channels: u16,
+    sample_rate: u32,
+    frames_written: u64,
+    /// Frames after which further samples are dropped, if the recording is capped
+    frame_limit: Option<u64>,
+    level: RecordingLevel,
+}
+
+impl MeteredSink {
+    pub fn new(inner: Box<dyn RecordingSink>, channels: u16, sample_rate: u32) -> Self {
+        Self {
+            inner,
+            channels: channels.max(1),
+            sample_rate,
+            frames_written: 0,
+            frame_limit: None,
+            level: RecordingLevel::default(),
+        }
+    }
+
+    /// Stop writing once `max_duration_secs` of audio has been recorded
+    pub fn with_max_duration(mut self, max_duration_secs: Option<f64>) -> Self {
+        self.frame_limit = max_duration_secs.map(|secs| (secs * self.sample_rate as f64).round() as u64);
+        self
+    }
+
+    pub fn write_samples(&mut self, samples: &[f32]) -> Result<(), String> {
+        let samples = match self.frame_limit {
+            Some(limit) => {
+                let remaining = limit.saturating_sub(self.frames_written) as usize * self.channels as usize;
+                &samples[..samples.len().min(remaining)]
+            }
+            None => samples,
+        };
+        if samples.is_empty() {
+            return Ok(());
+        }
+
+        let peak = samples.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+        let rms = (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt();
+        self.level = RecordingLevel { peak, rms };
+        self.inner.write_samples(samples)?;
+        self.frames_written += (samples.len() / self.channels as usize) as u64;
+        Ok(())
+    }
+
+    pub fn level(&self) -> RecordingLevel {
+        self.level
+    }
+
+    /// Whether the maximum duration has been written
+    pub fn limit_reached(&self) -> bool {
+        self.frame_limit.is_some_and(|limit| self.frames_written >= limit)
+    }
+
+    pub fn duration_secs(&self) -> f64 {
+        self.frames_written as f64 / self.sample_rate as f64
+    }
+
+    /// Finish the file and return the recorded duration in seconds
+    pub fn finalize(self) -> Result<f64, String> {
+        let duration = self.duration_secs();
+        self.inner.finalize()?;
+        Ok(duration)
+    }
+}
+
+/// A finished window of a recording, handed to live transcription
+#[derive(Debug, Clone)]
+pub struct LiveChunk {
+    pub index: usize,
+    /// Position of the chunk's first frame in the recording, excluding pauses
+    pub start_secs: f64,
+    pub path: PathBuf,
+}
+
+/// Forwards audio to the recording's own sink while also cutting it into
+/// fixed-length WAV chunks for live transcription. A chunk that cannot be
+/// written or delivered only ends the live feed; the recording carries on.
+pub struct LiveChunkTap {
+    inner: Box<dyn RecordingSink>,
+    /// Chunk files are named `<prefix>-<index>.wav`
+    chunk_prefix: PathBuf,
+    channels: u16,
+    sample_rate: u32,
+    window_frames: u64,
+    chunks: tokio::sync::mpsc::UnboundedSender<LiveChunk>,
+    /// Chunk being written, its sink and the frames in it so far
+    current: Option<(LiveChunk, WavSink, u64)>,
+    next_index: usize,
+    frames_written: u64,
+    stopped: bool,
+}
+
+impl LiveChunkTap {
+    pub fn new(
+        inner: Box<dyn RecordingSink>,
+        chunk_prefix: PathBuf,
+        window_secs: f64,
+        channels: u16,
+        sample_rate: u32,
+        chunks: tokio::sync::mpsc::UnboundedSender<LiveChunk>
+    ) -> Self {
+        Self {
+            inner,
+            chunk_prefix,
+            channels: channels.max(1),
+            sample_rate,
+            window_frames: ((window_secs * sample_rate as f64) as u64).max(1),
+            chunks,
+            current: None,
+            next_index: 0,
+            frames_written: 0,
+            stopped: false,
+        }
+    }
+
+    fn write_chunks(&mut self, samples: &[f32]) -> Result<(), String> {
+        let channels = self.channels as usize;
+        let mut rest = samples;
+        while !rest.is_empty() {
+            if self.current.is_none() {
+                let chunk = LiveChunk {
+                    index: self.next_index,
+                    start_secs: self.frames_written as f64 / self.sample_rate as f64,
+                    path: PathBuf::from(format!("{}-{:05}.wav", self.chunk_prefix.display(), self.next_index)),
+                };
+                let sink = WavSink::create(&chunk.path, self.channels, self.sample_rate)?;
+                self.current = Some((chunk, sink, 0));
+                self.next_index += 1;
+            }
+            let Some((_, sink, frames)) = self.current.as_mut() else {
+                unreachable!("a chunk was just opened");
+            };
+
+            let room = (self.window_frames - *frames) as usize * channels;
+            let (head, tail) = rest.split_at(rest.len().min(room));
+            sink.write_samples(head)?;
+            let written = (head.len() / channels) as u64;
+            *frames += written;
+            self.frames_written += written;
+            rest = tail;
+
+            if *frames >= self.window_frames {
+                self.send_current()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Finalize the chunk being written and hand it over
+    fn send_current(&mut self) -> Result<(), String> {
+        let Some((chunk, sink, _)) = self.current.take() else {
+            return Ok(());
+        };
+        let path = chunk.path.clone();
+        let sent = Box::new(sink)
+            .finalize()
+            .and_then(|()| self.chunks.send(chunk).map_err(|_| "Live transcription has stopped".to_string()));
+        if sent.is_err() {
+            let _ = crate::secure_temp::secure_delete(&path);
+        }
+        sent
+    }
+
+    /// Stop feeding chunks, removing the one in progress
+    fn stop(&mut self, reason: &str) {
+        log::warn!("Live transcription feed stopped: {}", reason);
+        self.stopped = true;
+        if let Some((chunk, sink, _)) = self.current.take() {
+            let _ = Box::new(sink).finalize();
+            let _ = crate::secure_temp::secure_delete(&chunk.path);
+        }
+    }
+}
+
+impl RecordingSink for LiveChunkTap {
+    fn write_samples(&mut self, samples: &[f32]) -> Result<(), String> {
+        self.inner.write_samples(samples)?;
+        if !self.stopped {
+            if let Err(e) = self.write_chunks(samples) {
+                self.stop(&e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Hand over the last, shorter chunk, then finalize the recording
+    fn finalize(mut self: Box<Self>) -> Result<(), String> {
+        if let Err(e) = self.send_current() {
+            log::warn!("Failed to hand over the last live chunk: {}", e);
+        }
+        self.inner.finalize()
+    }
+}
+
+/// Deinterleaves multi-channel audio into one mono sink per channel, e.g. one
+/// file per lapel microphone
+pub struct ChannelSplitSink {
+    sinks: Vec<Box<dyn RecordingSink>>,
+    /// Reused per-channel buffers so the capture callback doesn't allocate
+    buffers: Vec<Vec<f32>>,
+}
+
+impl ChannelSplitSink {
+    /// Open one mono sink per path, channel 1 first
+    pub fn create(paths: &[PathBuf], encoding: &str, sample_rate: u32) -> Result<Self, String> {
+        let sinks = paths
+            .iter()
+            .map(|path| create_sink(path, encoding, 1, sample_rate))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            buffers: vec![Vec::new(); sinks.len()],
+            sinks,
+        })
+    }
+}
+
+impl RecordingSink for ChannelSplitSink {
+    fn write_samples(&mut self, samples: &[f32]) -> Result<(), String> {
+        let channels = self.sinks.len();
+        for (channel, (sink, buffer)) in self.sinks.iter_mut().zip(&mut self.buffers).enumerate() {
+            buffer.clear();
+            buffer.extend(samples.iter().skip(channel).step_by(channels));
+            sink.write_samples(buffer)?;
+        }
+        Ok(())
+    }
+
+    fn finalize(self: Box<Self>) -> Result<(), String> {
+        // Finalize every file even if one fails, so the others stay readable
+        let mut first_error = None;
+        for sink in self.sinks {
+            if let Err(e) = sink.finalize() {
+                first_error.get_or_insert(e);
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+}
+
+/// Read a little-endian u32 at `offset`
+fn read_u32_le(bytes: &[u8], offset: usize) -> Option<u32> {
+    bytes.get(offset..offset + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+/// Complete the headers of a WAV file whose writer never finalized, e.g. after
+/// a crash mid-recording. The RIFF and data chunk sizes are rewritten to cover
+/// every whole frame on disk; a trailing partial frame is ignored. Returns the
+/// recoverable duration in seconds.
+pub fn repair_wav(path: &Path) -> Result<f64, String> {
+    let mut file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let file_len = file
+        .metadata()
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?
+        .len();
+
+    // hound writes the fmt chunk ahead of data, so the header fits in the first few hundred bytes
+    let mut header = Vec::new();
+    (&mut file)
+        .take(512)
+        .read_to_end(&mut header)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    if header.get(0..4) != Some(b"RIFF") || header.get(8..12) != Some(b"WAVE") {
+        return Err(format!("{} is not a WAV file", path.display()));
+    }
+
+    let mut block_align = None;
+    let mut data_start = None;
+    let mut offset = 12;
+    while let (Some(id), Some(size)) = (header.get(offset..offset + 4), read_u32_le(&header, offset + 4)) {
+        match id {
+            b"fmt " => {
+                block_align = header
+                    .get(offset + 20..offset + 22)
+                    .map(|b| u16::from_le_bytes([b[0], b[1]]));
+            }
+            b"data" => {
+                data_start = Some(offset + 8);
+                break;
+            }
+            _ => {}
+        }
+        offset += 8 + size as usize + (size as usize & 1);
+    }
+    let (Some(block_align), Some(data_start)) = (block_align.filter(|align| *align > 0), data_start) else {
+        return Err(format!("{} has no readable fmt and data chunks", path.display()));
+    };
+
+    let data_len = (file_len.saturating_sub(data_start as u64) / block_align as u64) * block_align as u64;
+    let data_len = u32::try_from(data_len).map_err(|_| format!("{} is too large to repair", path.display()))?;
+    let riff_len = data_start as u32 - 8 + data_len;
+    for (position, value) in [(4u64, riff_len), (data_start as u64 - 4, data_len)] {
+        file.seek(SeekFrom::Start(position))
+            .and_then(|_| file.write_all(&value.to_le_bytes()))
+            .map_err(|e| format!("Failed to repair {}: {}", path.display(), e))?;
+    }
+    file.set_len(data_start as u64 + data_len as u64)
+        .and_then(|_| file.sync_all())
+        .map_err(|e| format!("Failed to repair {}: {}", path.display(), e))?;
+    drop(file);
+
+    // Confirm the result is readable before reporting it as recovered
+    let reader = hound::WavReader::open(path)
+        .map_err(|e| format!("{} is still unreadable after repair: {}", path.display(), e))?;
+    Ok(reader.duration() as f64 / reader.spec().sample_rate as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+
+    /// `frames` of a stereo tone, different on each channel
+    fn stereo_tone(frames: usize, sample_rate: u32) -> Vec<f32> {
+        (0..frames)
+            .flat_map(|frame| {
+                let t = frame as f32 / sample_rate as f32;
+                let left = (t * 220.0 * std::f32::consts::TAU).sin() * 0.5;
+                let right = (t * 330.0 * std::f32::consts::TAU).sin() * 0.25;
+                [left, right]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn flac_recording_streams_in_bounded_blocks_and_decodes_losslessly() {
+        let dir = temp_dir();
+        let path = dir.join("recording.flac");
+        let sample_rate = 16_000;
+        let samples = stereo_tone(40_000, sample_rate);
+
+        let mut sink = FlacSink::create(&path, 2, sample_rate).unwrap();
+        // Capture callbacks deliver a few hundred frames at a time
+        for callback in samples.chunks(480 * 2) {
+            sink.write_samples(callback).unwrap();
+            assert!(sink.pending.len() < (FLAC_BLOCK_SIZE + MIN_FLAC_BLOCK_SIZE) * 2);
+        }
+        assert!(sink.frame_number > 0, "frames are written while recording");
+        Box::new(sink).finalize().unwrap();
+
+        let probe = crate::audio_processing::probe_audio(&path, Some("flac")).unwrap();
+        assert_eq!(probe.duration_secs, Some(2.5));
+        let decoded = crate::audio_processing::decode_audio(&path, Some("flac")).unwrap();
+        assert_eq!((decoded.spec.channels, decoded.spec.sample_rate), (2, sample_rate));
+        assert_eq!(decoded.samples.len(), samples.len());
+        for (original, decoded) in samples.iter().zip(&decoded.samples) {
+            assert_eq!(to_pcm16(*original) as f32, (decoded * 32768.0).round());
+        }
+
+        let pcm_bytes = samples.len() as u64 * 2;
+        assert!(std::fs::metadata(&path).unwrap().len() < pcm_bytes);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn very_short_flac_recording_is_padded_to_one_frame() {
+        let dir = temp_dir();
+        let path = dir.join("short.flac");
+
+        let mut sink = create_sink(&path, "flac", 1, 16_000).unwrap();
+        sink.write_samples(&[0.5; 5]).unwrap();
+        sink.finalize().unwrap();
+
+        let decoded = crate::audio_processing::decode_audio(&path, Some("flac")).unwrap();
+        assert_eq!(decoded.frame_count(), MIN_FLAC_BLOCK_SIZE);
+        assert!(decoded.samples[5..].iter().all(|sample| *sample == 0.0));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn metered_wav_recording_stops_at_its_maximum_duration() {
+        let dir = temp_dir();
+        let path = dir.join("capped.wav");
+        let sample_rate = 8_000;
+
+        let mut sink = MeteredSink::new(create_sink(&path, "wav", 2, sample_rate).unwrap(), 2, sample_rate)
+            .with_max_duration(Some(1.0));
+        for callback in stereo_tone(12_000, sample_rate).chunks(1000 * 2) {
+            sink.write_samples(callback).unwrap();
+        }
+        assert!(sink.limit_reached());
+        assert!(sink.level().peak > 0.0 && sink.level().rms <= sink.level().peak);
+        assert_eq!(sink.finalize().unwrap(), 1.0);
+
+        let recorded = crate::audio_processing::read_wav(&path).unwrap();
+        assert_eq!(recorded.frame_count(), sample_rate as usize);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn unknown_recording_encodings_are_rejected() {
+        assert_eq!(recording_extension("flac"), Ok("flac"));
+        assert!(recording_extension("mp3").unwrap_err().contains("wav, flac"));
+    }
+}
@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::path::Path;
+use tauri::State;
+
+use crate::error::AppError;
+use crate::storage_commands::{session_from_row, BUSY_SESSION_STATUSES, SESSION_COLUMNS};
+
+/// Status given to a recording that was cut short by a crash
+const INTERRUPTED_STATUS: &str = "interrupted";
+
+/// Status a stuck transcription is reset to, from which it can be started again
+const RETRYABLE_STATUS: &str = "created";
+
+/// One session reconciled at startup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveredSession {
+    pub session_id: String,
+    pub name: String,
+    pub previous_status: String,
+    pub status: String,
+    /// Seconds of audio kept after repairing an unfinished recording
+    pub recovered_duration: Option<f64>,
+    /// Why the recording could not be repaired, if it couldn't
+    pub error: Option<String>,
+}
+
+/// Sessions found mid-recording or mid-transcription when the app last started,
+/// kept in managed state for the UI to show once
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecoverySummary {
+    pub sessions: Vec<RecoveredSession>,
+}
+
+/// Repair the recording an interrupted session was writing to. Only WAV
+/// recordings can be completed; other files are left as they are.
+fn repair_recording(file_path: Option<&str>) -> Result<Option<f64>, String> {
+    let Some(file_path) = file_path else {
+        return Ok(None);
+    };
+    let path = Path::new(file_path);
+    if !path.is_file() {
+        return Err(format!("Recording file is missing: {}", file_path));
+    }
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("wav") => crate::recording_sink::repair_wav(path).map(Some),
+        _ => Ok(None),
+    }
+}
+
+/// Reconcile sessions left in a busy status by a previous run. Runs in `setup`
+/// before any command can start a recording or transcription, so every busy
+/// session found here is orphaned.
+pub async fn recover_sessions(pool: &SqlitePool) -> Result<RecoverySummary, AppError> {
+    let placeholders = vec!["?"; BUSY_SESSION_STATUSES.len()].join(", ");
+    let sql = format!(
+        "SELECT {} FROM conversation_sessions WHERE status IN ({}) ORDER BY created_at",
+        SESSION_COLUMNS, placeholders
+    );
+    let mut query = sqlx::query(&sql);
+    for status in BUSY_SESSION_STATUSES {
+        query = query.bind(*status);
+    }
+    let orphaned = query
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to scan for interrupted sessions: {}", e)))?
+        .iter()
+        .map(session_from_row)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| AppError::Database(format!("Failed to scan for interrupted sessions: {}", e)))?;
+
+    let mut summary = RecoverySummary::default();
+    for session in orphaned {
+        let mut recovered = RecoveredSession {
+            session_id: session.id.clone(),
+            name: session.name.clone(),
+            previous_status: session.status.clone(),
+            status: RETRYABLE_STATUS.to_string(),
+            recovered_duration: None,
+            error: None,
+        };
+
+        if session.status == "recording" {
+            recovered.status = INTERRUPTED_STATUS.to_string();
+            let file_path = session.file_path.clone();
+            match tauri::async_runtime::spawn_blocking(move || repair_recording(file_path.as_deref()))
+                .await
+                .map_err(|e| format!("Recording repair task failed: {}", e))
+                .and_then(|repaired| repaired)
+            {
+                Ok(duration) => recovered.recovered_duration = duration,
+                Err(e) => {
+                    log::warn!("Could not repair recording of session {}: {}", session.id, e);
+                    recovered.error = Some(e);
+                }
+            }
+        }
+
+        sqlx::query(
+            "UPDATE conversation_sessions SET status = ?, duration = COALESCE(?, duration), updated_at = ? WHERE id = ?",
+        )
+        .bind(&recovered.status)
+        .bind(recovered.recovered_duration)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .bind(&session.id)
+        .execute(pool)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to recover session {}: {}", session.id, e)))?;
+
+        log::info!(
+            "Recovered session {} ({}): {} -> {}{}",
+            session.id,
+            session.name,
+            recovered.previous_status,
+            recovered.status,
+            recovered
+                .recovered_duration
+                .map(|duration| format!(", {:.1}s of audio kept", duration))
+                .unwrap_or_default()
+        );
+        summary.sessions.push(recovered);
+    }
+
+    Ok(summary)
+}
+
+#[tauri::command]
+pub async fn get_recovery_summary(summary: State<'_, RecoverySummary>) -> Result<RecoverySummary, AppError> {
+    Ok(summary.inner().clone())
+}
@@ -0,0 +1,69 @@
+use docx_rs::{Docx, Footer, Paragraph, Run, Style, StyleType, Table, TableCell, TableRow};
+
+use crate::report_pdf::ReportBlock;
+
+const TITLE_STYLE: &str = "Title";
+const HEADING_STYLE: &str = "Heading1";
+
+/// Font sizes in half-points, as Word stores them
+const TITLE_FONT_SIZE: usize = 36;
+const HEADING_FONT_SIZE: usize = 26;
+const BODY_FONT_SIZE: usize = 20;
+const FOOTER_FONT_SIZE: usize = 16;
+
+fn text_paragraph(text: &str, size: usize, bold: bool) -> Paragraph {
+    let run = Run::new().add_text(text).size(size);
+    Paragraph::new().add_run(if bold { run.bold() } else { run })
+}
+
+fn table_row(cells: &[String], bold: bool) -> TableRow {
+    TableRow::new(
+        cells
+            .iter()
+            .map(|cell| TableCell::new().add_paragraph(text_paragraph(cell, BODY_FONT_SIZE, bold)))
+            .collect(),
+    )
+}
+
+/// Render `blocks` as an editable Word document with styled headings, tables
+/// for tabular sections and `confidentiality` in every footer, and return the
+/// DOCX bytes
+pub fn render_report(confidentiality: &str, blocks: &[ReportBlock]) -> Result<Vec<u8>, String> {
+    let mut docx = Docx::new()
+        .add_style(
+            Style::new(TITLE_STYLE, StyleType::Paragraph)
+                .name("Title")
+                .size(TITLE_FONT_SIZE)
+                .bold(),
+        )
+        .add_style(
+            Style::new(HEADING_STYLE, StyleType::Paragraph)
+                .name("Heading 1")
+                .size(HEADING_FONT_SIZE)
+                .bold(),
+        )
+        .footer(Footer::new().add_paragraph(text_paragraph(confidentiality, FOOTER_FONT_SIZE, false)));
+
+    for block in blocks {
+        docx = match block {
+            ReportBlock::Title(text) => {
+                docx.add_paragraph(Paragraph::new().add_run(Run::new().add_text(text)).style(TITLE_STYLE))
+            }
+            ReportBlock::Heading(text) => {
+                docx.add_paragraph(Paragraph::new().add_run(Run::new().add_text(text)).style(HEADING_STYLE))
+            }
+            ReportBlock::Paragraph(text) => docx.add_paragraph(text_paragraph(text, BODY_FONT_SIZE, false)),
+            ReportBlock::Table { columns, rows } => {
+                let mut table_rows = vec![table_row(columns, true)];
+                table_rows.extend(rows.iter().map(|row| table_row(row, false)));
+                docx.add_table(Table::new(table_rows))
+            }
+        };
+    }
+
+    let mut bytes = std::io::Cursor::new(Vec::new());
+    docx.build()
+        .pack(&mut bytes)
+        .map_err(|e| format!("Failed to write DOCX: {}", e))?;
+    Ok(bytes.into_inner())
+}
@@ -0,0 +1,155 @@
+use printpdf::{BuiltinFont, IndirectFontRef, Mm, PdfDocument, PdfLayerReference};
+
+/// A4 portrait
+const PAGE_WIDTH_MM: f32 = 210.0;
+const PAGE_HEIGHT_MM: f32 = 297.0;
+const MARGIN_MM: f32 = 20.0;
+
+/// Baseline of the footer carrying the confidentiality stamp and page number
+const FOOTER_BASELINE_MM: f32 = 10.0;
+
+const TITLE_FONT_SIZE: f32 = 18.0;
+const HEADING_FONT_SIZE: f32 = 13.0;
+const BODY_FONT_SIZE: f32 = 10.0;
+const FOOTER_FONT_SIZE: f32 = 8.0;
+
+/// Helvetica characters of `BODY_FONT_SIZE` that fit the text width on average
+const BODY_WRAP_COLUMNS: usize = 95;
+
+const MM_PER_POINT: f32 = 0.3528;
+
+/// Line spacing relative to the font size
+const LINE_SPACING: f32 = 1.4;
+
+/// One piece of report content, laid out top to bottom
+#[derive(Debug, Clone)]
+pub enum ReportBlock {
+    Title(String),
+    Heading(String),
+    Paragraph(String),
+    /// Header row plus body rows; PDF output lays each row out as one line
+    Table { columns: Vec<String>, rows: Vec<Vec<String>> },
+}
+
+/// Separator between table cells in PDF output
+const TABLE_CELL_SEPARATOR: &str = "  |  ";
+
+impl ReportBlock {
+    /// Scrub names from the block's text in place
+    pub fn redact(&mut self, redactor: &crate::export_commands::Redactor) {
+        match self {
+            ReportBlock::Title(text) | ReportBlock::Heading(text) | ReportBlock::Paragraph(text) => {
+                *text = redactor.redact(text);
+            }
+            ReportBlock::Table { columns, rows } => {
+                for cell in columns.iter_mut().chain(rows.iter_mut().flatten()) {
+                    *cell = redactor.redact(cell);
+                }
+            }
+        }
+    }
+
+    /// Lines of text with their font size and weight
+    fn lines(&self) -> Vec<(String, f32, bool)> {
+        match self {
+            ReportBlock::Title(text) => vec![(text.clone(), TITLE_FONT_SIZE, true)],
+            ReportBlock::Heading(text) => vec![(text.clone(), HEADING_FONT_SIZE, true)],
+            ReportBlock::Paragraph(text) => vec![(text.clone(), BODY_FONT_SIZE, false)],
+            ReportBlock::Table { columns, rows } => {
+                let mut lines = vec![(columns.join(TABLE_CELL_SEPARATOR), BODY_FONT_SIZE, true)];
+                lines.extend(rows.iter().map(|row| (row.join(TABLE_CELL_SEPARATOR), BODY_FONT_SIZE, false)));
+                lines
+            }
+        }
+    }
+}
+
+/// Break `text` into lines of at most `columns` characters, splitting only
+/// overlong words
+pub fn wrap_text(text: &str, columns: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+        while word.len() > columns {
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+            }
+            lines.push(word.drain(..columns).collect());
+        }
+        let word: String = word.into_iter().collect();
+
+        let needed = current.chars().count() + usize::from(!current.is_empty()) + word.chars().count();
+        if needed > columns && !current.is_empty() {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(&word);
+    }
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+fn line_height_mm(font_size: f32) -> f32 {
+    font_size * LINE_SPACING * MM_PER_POINT
+}
+
+/// Render `blocks` onto A4 pages, stamping `confidentiality` and the page number
+/// in every footer, and return the PDF bytes
+pub fn render_report(title: &str, confidentiality: &str, blocks: &[ReportBlock]) -> Result<Vec<u8>, String> {
+    let (doc, first_page, first_layer) =
+        PdfDocument::new(title, Mm(PAGE_WIDTH_MM), Mm(PAGE_HEIGHT_MM), "Content");
+    let regular = doc
+        .add_builtin_font(BuiltinFont::Helvetica)
+        .map_err(|e| format!("Failed to load report font: {}", e))?;
+    let bold = doc
+        .add_builtin_font(BuiltinFont::HelveticaBold)
+        .map_err(|e| format!("Failed to load report font: {}", e))?;
+
+    let mut layers: Vec<PdfLayerReference> = vec![doc.get_page(first_page).get_layer(first_layer)];
+    let top = PAGE_HEIGHT_MM - MARGIN_MM;
+    let bottom = MARGIN_MM;
+    let mut y = top;
+
+    for block in blocks {
+        let lines = block.lines();
+
+        // Headings and tables get breathing room above, but not at the top of a page
+        if let Some((_, first_size, _)) = lines.first() {
+            if !matches!(block, ReportBlock::Paragraph(_)) && y < top {
+                y -= line_height_mm(*first_size) / 2.0;
+            }
+        }
+        for (text, font_size, is_bold) in lines {
+            let font: &IndirectFontRef = if is_bold { &bold } else { &regular };
+            let columns = (BODY_WRAP_COLUMNS as f32 * BODY_FONT_SIZE / font_size) as usize;
+            let height = line_height_mm(font_size);
+
+            for line in wrap_text(&text, columns) {
+                if y - height < bottom {
+                    let (page, layer) = doc.add_page(Mm(PAGE_WIDTH_MM), Mm(PAGE_HEIGHT_MM), "Content");
+                    layers.push(doc.get_page(page).get_layer(layer));
+                    y = top;
+                }
+                y -= height;
+                if let Some(layer) = layers.last() {
+                    layer.use_text(line, font_size, Mm(MARGIN_MM), Mm(y), font);
+                }
+            }
+        }
+    }
+
+    let page_count = layers.len();
+    for (index, layer) in layers.iter().enumerate() {
+        let footer = format!("{} | Page {} of {}", confidentiality, index + 1, page_count);
+        layer.use_text(footer, FOOTER_FONT_SIZE, Mm(MARGIN_MM), Mm(FOOTER_BASELINE_MM), &regular);
+    }
+
+    doc.save_to_bytes()
+        .map_err(|e| format!("Failed to write PDF: {}", e))
+}
@@ -0,0 +1,99 @@
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+
+use crate::error::AppError;
+use crate::export_commands::ReportTemplate;
+
+/// Report sections a template may lay out
+pub const REPORT_SECTIONS: &[&str] = &["transcript", "markers", "rapport"];
+
+/// Opening and closing of the Handlebars comment holding an `.hbs` template's descriptor
+const HBS_DESCRIPTOR_OPEN: &str = "{{!--";
+const HBS_DESCRIPTOR_CLOSE: &str = "--}}";
+
+/// Template directories in lookup order: templates shipped with the app, then
+/// `templates/` in the app data directory, whose files override shipped ones by id
+pub fn template_directories(app: &tauri::AppHandle) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Ok(dir) = app.path().resource_dir() {
+        dirs.push(dir.join("resources").join("templates"));
+    }
+    if let Ok(dir) = app.path().app_data_dir() {
+        dirs.push(dir.join("templates"));
+    }
+    dirs
+}
+
+/// Parse a template descriptor: a `.json` file, or a `.hbs` file starting with
+/// a `{{!-- ... --}}` comment that holds the same JSON
+pub fn parse_template(path: &Path) -> Result<ReportTemplate, String> {
+    let contents = std::fs::read_to_string(path).map_err(|e| format!("cannot be read: {}", e))?;
+    let descriptor = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => contents.as_str(),
+        Some("hbs") => contents
+            .trim_start()
+            .strip_prefix(HBS_DESCRIPTOR_OPEN)
+            .and_then(|rest| rest.split_once(HBS_DESCRIPTOR_CLOSE))
+            .map(|(descriptor, _)| descriptor)
+            .ok_or_else(|| "does not start with a {{!-- descriptor --}} comment".to_string())?,
+        _ => return Err("is not a .json or .hbs template".to_string()),
+    };
+
+    let template: ReportTemplate =
+        serde_json::from_str(descriptor).map_err(|e| format!("is not a valid template descriptor: {}", e))?;
+    if template.id.trim().is_empty() {
+        return Err("has an empty id".to_string());
+    }
+    if let Some(section) = template.sections.iter().find(|section| !REPORT_SECTIONS.contains(&section.as_str())) {
+        return Err(format!(
+            "uses unknown section {} (expected one of: {})",
+            section,
+            REPORT_SECTIONS.join(", ")
+        ));
+    }
+    Ok(template)
+}
+
+/// Every valid template in `dirs`, sorted by id. Unreadable or invalid files
+/// are skipped with a warning so one broken template cannot hide the others.
+pub fn load_templates(dirs: &[PathBuf]) -> Vec<ReportTemplate> {
+    let mut templates: std::collections::BTreeMap<String, ReportTemplate> = Default::default();
+
+    for dir in dirs {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        let mut paths: Vec<PathBuf> = entries.filter_map(|entry| entry.ok().map(|entry| entry.path())).collect();
+        paths.sort();
+
+        for path in paths {
+            if !matches!(path.extension().and_then(|ext| ext.to_str()), Some("json" | "hbs")) {
+                continue;
+            }
+            match parse_template(&path) {
+                Ok(template) => {
+                    templates.insert(template.id.clone(), template);
+                }
+                Err(problem) => log::warn!("Skipping report template {}: {}", path.display(), problem),
+            }
+        }
+    }
+
+    templates.into_values().collect()
+}
+
+/// Resolve `template_id` against the template directories
+pub fn find_template(app: &tauri::AppHandle, template_id: &str) -> Result<ReportTemplate, AppError> {
+    load_templates(&template_directories(app))
+        .into_iter()
+        .find(|template| template.id == template_id)
+        .ok_or_else(|| AppError::NotFound(format!("Report template not found: {}", template_id)))
+}
+
+#[tauri::command]
+pub async fn list_report_templates(app: tauri::AppHandle) -> Result<Vec<ReportTemplate>, AppError> {
+    let dirs = template_directories(&app);
+    tauri::async_runtime::spawn_blocking(move || load_templates(&dirs))
+        .await
+        .map_err(|e| AppError::Internal(format!("Template scan failed: {}", e)))
+}
@@ -0,0 +1,224 @@
+use tauri::State;
+use serde::{Deserialize, Serialize};
+use sqlx::{SqlitePool, Row};
+
+use crate::error::AppError;
+use crate::state::AppState;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SearchIndexRebuild {
+    pub rows_indexed: i64,
+    pub duration_ms: u64,
+}
+
+/// Drop and repopulate the transcript full-text index from `transcript_segments`
+pub async fn rebuild_transcript_index(pool: &SqlitePool) -> Result<i64, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("DROP TABLE IF EXISTS transcript_search")
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query(crate::storage_commands::TRANSCRIPT_SEARCH_DDL)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("INSERT INTO transcript_search(transcript_search) VALUES ('rebuild')")
+        .execute(&mut *tx)
+        .await?;
+
+    let rows_indexed: i64 = sqlx::query("SELECT COUNT(*) AS count FROM transcript_segments")
+        .fetch_one(&mut *tx)
+        .await?
+        .try_get("count")?;
+
+    tx.commit().await?;
+    Ok(rows_indexed)
+}
+
+#[tauri::command]
+pub async fn rebuild_search_index(state: State<'_, AppState>) -> Result<SearchIndexRebuild, AppError> {
+    log::info!("Rebuilding transcript search index");
+
+    let started = std::time::Instant::now();
+    let rows_indexed = rebuild_transcript_index(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to rebuild search index: {}", e)))?;
+    let duration_ms = started.elapsed().as_millis() as u64;
+
+    log::info!("Indexed {} transcript segments in {} ms", rows_indexed, duration_ms);
+    Ok(SearchIndexRebuild { rows_indexed, duration_ms })
+}
+
+/// Hits returned by `search_transcripts` when no limit is given
+const DEFAULT_SEARCH_LIMIT: u32 = 50;
+
+/// Upper bound on hits returned by one transcript search
+const MAX_SEARCH_LIMIT: u32 = 500;
+
+/// Tokens of context around the match in a hit's snippet
+const SNIPPET_TOKENS: i64 = 12;
+
+/// Control characters FTS5 puts around matches; they can't occur in transcript text
+const MATCH_START: char = '\u{2}';
+const MATCH_END: char = '\u{3}';
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TranscriptSearchHit {
+    pub session_id: String,
+    pub segment_id: String,
+    /// HTML-escaped transcript text with the matched words wrapped in `<mark>`
+    pub snippet: String,
+    pub start_time: f64,
+}
+
+/// FTS5 query matching `text` as one literal phrase, so user input can't use query syntax
+pub fn phrase_query(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\"\""))
+}
+
+#[tauri::command]
+pub async fn search_transcripts(
+    state: State<'_, AppState>,
+    query: String,
+    limit: Option<u32>
+) -> Result<Vec<TranscriptSearchHit>, AppError> {
+    log::info!("Searching transcripts");
+
+    let limit = limit.unwrap_or(DEFAULT_SEARCH_LIMIT);
+    if limit == 0 || limit > MAX_SEARCH_LIMIT {
+        return Err(AppError::Validation(format!("limit must be between 1 and {}", MAX_SEARCH_LIMIT)));
+    }
+
+    let query = query.trim();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    find_segments(&state.db, query, limit)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to search transcripts: {}", e)))
+}
+
+/// Best-ranked segments containing `query` as a phrase
+async fn find_segments(pool: &SqlitePool, query: &str, limit: u32) -> Result<Vec<TranscriptSearchHit>, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT s.session_id, s.id AS segment_id, s.start_time, \
+         snippet(transcript_search, 0, ?, ?, '…', ?) AS snippet \
+         FROM transcript_search JOIN transcript_segments s ON s.rowid = transcript_search.rowid \
+         WHERE transcript_search MATCH ? ORDER BY rank LIMIT ?",
+    )
+    .bind(MATCH_START.to_string())
+    .bind(MATCH_END.to_string())
+    .bind(SNIPPET_TOKENS)
+    .bind(phrase_query(query))
+    .bind(limit as i64)
+    .fetch_all(pool)
+    .await?;
+
+    rows.iter()
+        .map(|row| {
+            Ok(TranscriptSearchHit {
+                session_id: row.try_get("session_id")?,
+                segment_id: row.try_get("segment_id")?,
+                snippet: highlight_snippet(&row.try_get::<String, _>("snippet")?),
+                start_time: row.try_get("start_time")?,
+            })
+        })
+        .collect()
+}
+
+/// Escape a raw FTS5 snippet for HTML and turn its match delimiters into `<mark>` tags
+fn highlight_snippet(raw: &str) -> String {
+    let mut html = String::with_capacity(raw.len());
+    for c in raw.chars() {
+        match c {
+            MATCH_START => html.push_str("<mark>"),
+            MATCH_END => html.push_str("</mark>"),
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            '\'' => html.push_str("&#39;"),
+            c => html.push(c),
+        }
+    }
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{insert, memory_pool, segment, session};
+
+    async fn pool_with_transcript() -> SqlitePool {
+        let pool = memory_pool().await;
+        insert(&pool, &session("s1", "Intake")).await;
+        crate::storage_commands::write_segments(
+            &mut pool.acquire().await.unwrap(),
+            "s1",
+            &[
+                segment("SPEAKER_00", 0.0, 2.0, "How did you sleep this week?"),
+                segment("SPEAKER_01", 2.0, 5.0, "Badly, the insomnia is back."),
+            ],
+        )
+        .await
+        .unwrap();
+        pool
+    }
+
+    #[tokio::test]
+    async fn rebuild_restores_hits_after_the_index_is_cleared() {
+        let pool = pool_with_transcript().await;
+        assert_eq!(find_segments(&pool, "insomnia", 10).await.unwrap().len(), 1);
+
+        sqlx::query("INSERT INTO transcript_search(transcript_search) VALUES ('delete-all')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert!(find_segments(&pool, "insomnia", 10).await.unwrap().is_empty());
+
+        assert_eq!(rebuild_transcript_index(&pool).await.unwrap(), 2);
+
+        let hits = find_segments(&pool, "insomnia", 10).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].session_id, "s1");
+        assert_eq!(hits[0].start_time, 2.0);
+        assert!(hits[0].snippet.contains("<mark>insomnia</mark>"));
+    }
+
+    #[tokio::test]
+    async fn rebuild_recreates_a_dropped_index() {
+        let pool = pool_with_transcript().await;
+        sqlx::query("DROP TABLE transcript_search").execute(&pool).await.unwrap();
+
+        rebuild_transcript_index(&pool).await.unwrap();
+
+        assert_eq!(find_segments(&pool, "sleep this week", 10).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn query_syntax_is_searched_literally() {
+        let pool = pool_with_transcript().await;
+        assert!(find_segments(&pool, "insomnia OR sleep", 10).await.unwrap().is_empty());
+        assert!(find_segments(&pool, "\"unbalanced", 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn snippets_escape_transcript_markup() {
+        let pool = memory_pool().await;
+        insert(&pool, &session("s1", "Intake")).await;
+        crate::storage_commands::write_segments(
+            &mut pool.acquire().await.unwrap(),
+            "s1",
+            &[segment("SPEAKER_00", 0.0, 2.0, "Typed <img src=x onerror=alert(1)> & said insomnia")],
+        )
+        .await
+        .unwrap();
+
+        let hits = find_segments(&pool, "insomnia", 10).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert!(!hits[0].snippet.contains("<img"));
+        assert!(hits[0].snippet.contains("&lt;img"));
+        assert!(hits[0].snippet.contains("&amp;"));
+        assert!(hits[0].snippet.contains("<mark>insomnia</mark>"));
+    }
+}
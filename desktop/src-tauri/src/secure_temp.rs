@@ -0,0 +1,222 @@
+use tauri::State;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::error::AppError;
+
+/// Chunk size used when overwriting files before removal
+const WIPE_CHUNK_BYTES: usize = 64 * 1024;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SecureTempEntry {
+    pub operation_id: String,
+    pub file_path: String,
+    pub size_bytes: u64,
+}
+
+/// Overwrite a file with zeros, flush it to disk, then unlink it.
+/// Copy-on-write and journaling filesystems may keep old blocks regardless,
+/// so this is best effort on top of the files being short-lived.
+pub fn secure_delete(path: &Path) -> std::io::Result<()> {
+    let mut file = std::fs::OpenOptions::new().write(true).open(path)?;
+    let len = file.metadata()?.len();
+
+    file.seek(SeekFrom::Start(0))?;
+    let zeros = vec![0u8; WIPE_CHUNK_BYTES];
+    let mut remaining = len;
+    while remaining > 0 {
+        let chunk = remaining.min(WIPE_CHUNK_BYTES as u64) as usize;
+        file.write_all(&zeros[..chunk])?;
+        remaining -= chunk as u64;
+    }
+    file.sync_all()?;
+    drop(file);
+
+    std::fs::remove_file(path)
+}
+
+/// Decrypted temp files currently on disk, grouped by the operation using them
+#[derive(Default)]
+pub struct SecureTempRegistry {
+    files: Mutex<HashMap<String, Vec<PathBuf>>>,
+}
+
+impl SecureTempRegistry {
+    pub fn register(&self, operation_id: &str, path: PathBuf) {
+        self.files
+            .lock()
+            .unwrap()
+            .entry(operation_id.to_string())
+            .or_default()
+            .push(path);
+    }
+
+    /// Track temp files for `operation_id` until the returned guard is dropped
+    pub fn track(&self, operation_id: &str) -> SecureTempGuard<'_> {
+        SecureTempGuard {
+            registry: self,
+            operation_id: operation_id.to_string(),
+        }
+    }
+
+    /// Wipe all files of a finished operation; returns how many were removed
+    pub fn release(&self, operation_id: &str) -> usize {
+        let paths = self.files.lock().unwrap().remove(operation_id).unwrap_or_default();
+        wipe_paths(&paths)
+    }
+
+    /// Wipe every tracked file, e.g. on app exit
+    pub fn wipe_all(&self) -> usize {
+        let paths: Vec<PathBuf> = self
+            .files
+            .lock()
+            .unwrap()
+            .drain()
+            .flat_map(|(_, paths)| paths)
+            .collect();
+        wipe_paths(&paths)
+    }
+
+    pub fn entries(&self) -> Vec<SecureTempEntry> {
+        let files = self.files.lock().unwrap();
+        let mut entries: Vec<SecureTempEntry> = files
+            .iter()
+            .flat_map(|(operation_id, paths)| {
+                paths.iter().map(move |path| SecureTempEntry {
+                    operation_id: operation_id.clone(),
+                    file_path: path.to_string_lossy().to_string(),
+                    size_bytes: std::fs::metadata(path).map(|m| m.len()).unwrap_or(0),
+                })
+            })
+            .collect();
+        entries.sort_by(|a, b| {
+            a.operation_id
+                .cmp(&b.operation_id)
+                .then_with(|| a.file_path.cmp(&b.file_path))
+        });
+        entries
+    }
+}
+
+fn wipe_paths(paths: &[PathBuf]) -> usize {
+    let mut wiped = 0;
+    for path in paths {
+        match secure_delete(path) {
+            Ok(()) => wiped += 1,
+            // Already gone is as good as wiped
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => log::warn!("Failed to wipe temp file {}: {}", path.display(), e),
+        }
+    }
+    wiped
+}
+
+/// Wipes an operation's temp files when the operation goes out of scope,
+/// whether it completed, failed or was cancelled
+pub struct SecureTempGuard<'a> {
+    registry: &'a SecureTempRegistry,
+    operation_id: String,
+}
+
+impl SecureTempGuard<'_> {
+    pub fn register(&self, path: PathBuf) {
+        self.registry.register(&self.operation_id, path);
+    }
+}
+
+impl Drop for SecureTempGuard<'_> {
+    fn drop(&mut self) {
+        let wiped = self.registry.release(&self.operation_id);
+        if wiped > 0 {
+            log::info!("Wiped {} temp file(s) of operation: {}", wiped, self.operation_id);
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn list_secure_temp(
+    registry: State<'_, SecureTempRegistry>
+) -> Result<Vec<SecureTempEntry>, AppError> {
+    Ok(registry.entries())
+}
+
+#[tauri::command]
+pub async fn wipe_secure_temp(
+    registry: State<'_, SecureTempRegistry>
+) -> Result<usize, AppError> {
+    log::info!("Wiping all decrypted temp files");
+
+    Ok(registry.wipe_all())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+
+    fn temp_file(dir: &Path, name: &str, bytes: usize) -> PathBuf {
+        let path = dir.join(name);
+        std::fs::write(&path, vec![0xAB; bytes]).unwrap();
+        path
+    }
+
+    #[test]
+    fn lists_registered_files_and_releases_one_operation() {
+        let dir = temp_dir();
+        let registry = SecureTempRegistry::default();
+        let first = temp_file(&dir, "a.wav", 100);
+        let second = temp_file(&dir, "b.wav", 70_000);
+        let other = temp_file(&dir, "c.wav", 10);
+        registry.register("op-1", second.clone());
+        registry.register("op-1", first.clone());
+        registry.register("op-2", other.clone());
+
+        let entries = registry.entries();
+        let listed: Vec<(&str, &str, u64)> = entries
+            .iter()
+            .map(|entry| (entry.operation_id.as_str(), entry.file_path.as_str(), entry.size_bytes))
+            .collect();
+        assert_eq!(
+            listed,
+            [
+                ("op-1", first.to_str().unwrap(), 100),
+                ("op-1", second.to_str().unwrap(), 70_000),
+                ("op-2", other.to_str().unwrap(), 10),
+            ]
+        );
+
+        assert_eq!(registry.release("op-1"), 2);
+        assert!(!first.exists() && !second.exists());
+        assert!(other.exists());
+        assert_eq!(registry.entries().len(), 1);
+        assert_eq!(registry.release("op-1"), 0);
+
+        assert_eq!(registry.wipe_all(), 1);
+        assert!(!other.exists());
+        assert!(registry.entries().is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn tracked_files_are_wiped_when_the_operation_completes() {
+        let dir = temp_dir();
+        let registry = SecureTempRegistry::default();
+        let decrypted = temp_file(&dir, "decrypted.wav", 1024);
+        let already_gone = dir.join("gone.wav");
+
+        {
+            let guard = registry.track("transcribe-1");
+            guard.register(decrypted.clone());
+            guard.register(already_gone);
+            assert!(decrypted.exists());
+            assert_eq!(registry.entries().len(), 2);
+        }
+
+        assert!(!decrypted.exists());
+        assert!(registry.entries().is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
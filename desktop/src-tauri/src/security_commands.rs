@@ -0,0 +1,218 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+
+/// Highest score `check_passphrase_strength` reports
+pub const MAX_PASSPHRASE_SCORE: u8 = 4;
+
+/// Frequently used passwords that must never protect clinical data
+const COMMON_PASSWORDS: &[&str] = &[
+    "password", "passwort", "123456", "12345678", "123456789", "qwerty", "qwertz",
+    "letmein", "welcome", "admin", "iloveyou", "monkey", "dragon", "sunshine",
+    "football", "princess", "master", "secret", "geheim", "hallo123", "abc123",
+    "password1", "trustno1", "changeme", "transrapport", "therapy", "test1234",
+];
+
+/// Keyboard and alphabet runs that add length without adding entropy
+const SEQUENCES: &[&str] = &[
+    "abcdefghijklmnopqrstuvwxyz",
+    "0123456789",
+    "qwertyuiop",
+    "qwertzuiop",
+    "asdfghjkl",
+    "yxcvbnm",
+    "zxcvbnm",
+];
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PassphraseStrength {
+    pub score: u8, // 0 (very weak) to 4 (strong)
+    pub entropy_bits: f64,
+    pub suggestions: Vec<String>,
+}
+
+/// Number of characters that sit inside a repeat or a known sequence run
+fn predictable_chars(lower: &[char]) -> usize {
+    let mut predictable = vec![false; lower.len()];
+
+    for i in 2..lower.len() {
+        if lower[i] == lower[i - 1] && lower[i] == lower[i - 2] {
+            predictable[i - 2..=i].iter_mut().for_each(|p| *p = true);
+        }
+    }
+
+    for sequence in SEQUENCES {
+        let sequence: Vec<char> = sequence.chars().collect();
+        for window in 0..lower.len().saturating_sub(2) {
+            let run = &lower[window..window + 3];
+            let forward = sequence.windows(3).any(|w| w == run);
+            let backward = sequence.windows(3).any(|w| w.iter().rev().eq(run.iter()));
+            if forward || backward {
+                predictable[window..window + 3].iter_mut().for_each(|p| *p = true);
+            }
+        }
+    }
+
+    predictable.iter().filter(|p| **p).count()
+}
+
+/// Estimate passphrase strength from character-pool entropy, discounted for
+/// common passwords, repeats and keyboard sequences.
+pub fn evaluate_passphrase(passphrase: &str) -> PassphraseStrength {
+    let chars: Vec<char> = passphrase.chars().collect();
+    let lower: Vec<char> = passphrase.to_lowercase().chars().collect();
+    let mut suggestions = Vec::new();
+
+    let has_lower = chars.iter().any(|c| c.is_lowercase());
+    let has_upper = chars.iter().any(|c| c.is_uppercase());
+    let has_digit = chars.iter().any(|c| c.is_ascii_digit());
+    let has_space = chars.iter().any(|c| c.is_whitespace());
+    let has_symbol = chars.iter().any(|c| !c.is_alphanumeric() && !c.is_whitespace());
+
+    let mut pool = 0u32;
+    if has_lower {
+        pool += 26;
+    }
+    if has_upper {
+        pool += 26;
+    }
+    if has_digit {
+        pool += 10;
+    }
+    if has_space {
+        pool += 1;
+    }
+    if has_symbol {
+        pool += 33;
+    }
+    if chars.iter().any(|c| !c.is_ascii()) {
+        pool += 40;
+    }
+
+    // Predictable runs contribute almost nothing
+    let predictable = predictable_chars(&lower);
+    let effective_len = (lower.len() - predictable) as f64 + predictable as f64 * 0.25;
+    let mut entropy_bits = if pool > 0 { effective_len * (pool as f64).log2() } else { 0.0 };
+
+    let normalized: String = lower.iter().filter(|c| c.is_alphanumeric()).collect();
+    let is_common = COMMON_PASSWORDS
+        .iter()
+        .any(|common| {
+            // Also catch trivial suffixes such as "password1!"
+            normalized == *common
+                || (normalized.len() <= common.len() + 2 && normalized.starts_with(common))
+        });
+    if is_common {
+        entropy_bits = entropy_bits.min(10.0);
+        suggestions.push("Avoid common passwords; use several unrelated words instead".to_string());
+    }
+
+    if chars.len() < 12 {
+        suggestions.push("Use at least 12 characters; a passphrase of 4+ words is easier to remember".to_string());
+    }
+    let classes = [has_lower, has_upper, has_digit, has_symbol || has_space]
+        .iter()
+        .filter(|present| **present)
+        .count();
+    if classes < 3 {
+        suggestions.push("Mix upper- and lowercase letters, digits and symbols".to_string());
+    }
+    if predictable > 0 {
+        suggestions.push("Avoid repeated characters and keyboard or alphabet sequences".to_string());
+    }
+
+    let score = match entropy_bits {
+        bits if bits < 28.0 => 0,
+        bits if bits < 36.0 => 1,
+        bits if bits < 60.0 => 2,
+        bits if bits < 80.0 => 3,
+        _ => MAX_PASSPHRASE_SCORE,
+    };
+
+    PassphraseStrength {
+        score,
+        entropy_bits: (entropy_bits * 10.0).round() / 10.0,
+        suggestions,
+    }
+}
+
+/// Reject a passphrase below `min_score`, if one is set. Applies when a passphrase
+/// keys a new database; an existing database still unlocks with its passphrase
+/// after the minimum is raised.
+pub fn enforce_passphrase_policy(passphrase: &str, min_score: Option<u8>) -> Result<(), AppError> {
+    let Some(min_score) = min_score else {
+        return Ok(());
+    };
+
+    let strength = evaluate_passphrase(passphrase);
+    if strength.score < min_score {
+        return Err(AppError::Validation(format!(
+            "Passphrase is too weak (score {} of {}, minimum {}). {}",
+            strength.score,
+            MAX_PASSPHRASE_SCORE,
+            min_score,
+            strength.suggestions.join(". ")
+        )));
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn check_passphrase_strength(passphrase: String) -> Result<PassphraseStrength, AppError> {
+    // Never log the passphrase itself
+    log::info!("Checking passphrase strength");
+
+    Ok(evaluate_passphrase(&passphrase))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weak_passphrases_score_low() {
+        for weak in ["password1!", "qwertz", "aaaaaaaa", "abc123", "Geheim"] {
+            let strength = evaluate_passphrase(weak);
+            assert!(strength.score <= 1, "{} scored {}", weak, strength.score);
+            assert!(!strength.suggestions.is_empty());
+        }
+    }
+
+    #[test]
+    fn strong_passphrases_score_high() {
+        for strong in ["Violet tram, 7 owls & a kettle", "correct-Horse-battery-staple-42"] {
+            let strength = evaluate_passphrase(strong);
+            assert_eq!(strength.score, MAX_PASSPHRASE_SCORE, "{} scored {}", strong, strength.score);
+        }
+    }
+
+    #[test]
+    fn weak_and_strong_passphrases_are_far_apart() {
+        let weak = evaluate_passphrase("letmein");
+        let strong = evaluate_passphrase("Violet tram, 7 owls & a kettle");
+        assert!(strong.score >= weak.score + 3);
+        assert!(strong.entropy_bits > weak.entropy_bits * 4.0);
+    }
+
+    #[test]
+    fn common_passwords_are_flagged_with_trivial_suffixes() {
+        let strength = evaluate_passphrase("Transrapport2!");
+        assert_eq!(strength.score, 0);
+        assert!(strength.suggestions.iter().any(|s| s.contains("common passwords")));
+    }
+
+    #[test]
+    fn sequences_count_for_little() {
+        let sequence = evaluate_passphrase("abcdefghijklmnop");
+        let varied = evaluate_passphrase("kwmbrtzqlxvhnpje");
+        assert!(sequence.entropy_bits < varied.entropy_bits / 2.0);
+    }
+
+    #[test]
+    fn policy_rejects_passphrases_below_the_minimum() {
+        assert!(enforce_passphrase_policy("letmein", None).is_ok());
+        assert!(matches!(enforce_passphrase_policy("letmein", Some(2)), Err(AppError::Validation(_))));
+        assert!(enforce_passphrase_policy("Violet tram, 7 owls & a kettle", Some(MAX_PASSPHRASE_SCORE)).is_ok());
+    }
+}
@@ -0,0 +1,416 @@
+use tauri::State;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+use crate::error::AppError;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    /// Derive a session name from its transcript when it still has a default name
+    pub auto_name_sessions: bool,
+    /// Key the database with a passphrase given to `unlock_database` instead of a
+    /// key kept in the OS keyring. Applies after a restart, and only to a database
+    /// created with it; an existing database keeps the key it was created with.
+    pub database_passphrase: bool,
+    /// Minimum passphrase score (0-4) required when a passphrase keys a new database
+    pub min_passphrase_score: Option<u8>,
+    /// Minutes between scheduled database backups; `None` disables them
+    pub auto_backup_interval_minutes: Option<u64>,
+    /// Where backups are written; defaults to `backups/` in the app data directory
+    pub backup_directory: Option<String>,
+    /// Number of most recent backups kept when rotating
+    pub backup_keep_count: usize,
+    /// Re-read and validate export files after writing them
+    pub verify_exports: bool,
+    /// Store the sanitized Python command line of each pipeline run in `run_metrics`
+    pub capture_python_command_lines: bool,
+    /// Longest audio file accepted by imports
+    pub max_import_duration_minutes: u32,
+    /// Minutes a WhisperX run may take before it is killed
+    pub transcription_timeout_minutes: u32,
+    /// Python processes allowed to run at once; further calls queue. Applies after a restart.
+    pub max_python_processes: usize,
+    /// Names scrubbed from transcript text, alongside the client reference, in redacted exports
+    pub redaction_terms: Vec<String>,
+    /// Whisper model used when a transcription doesn't name one; `None` leaves it to WhisperX
+    pub default_model_size: Option<String>,
+    /// Language code used when a transcription doesn't name one; `None` lets WhisperX detect it
+    pub default_language: Option<String>,
+    /// Where exports are written; defaults to `exports/` in the app data directory
+    pub export_directory: Option<String>,
+    /// Confidentiality stamped on reports that don't set their own
+    pub default_confidentiality_level: String,
+    /// Milliseconds between input device scans by the device watcher
+    pub device_poll_interval_ms: u64,
+    /// Seconds after which a recording stops itself; `None` records until stopped
+    pub max_duration_secs: Option<u64>,
+    /// Allow recordings to emit live captions. They run a small model on 10 s
+    /// windows, so they trail the audio by ten seconds or more and are less
+    /// accurate than the transcript made after recording, which they don't replace.
+    pub live_transcription: bool,
+}
+
+/// Fastest device scan allowed; enumerating devices is not free on every host
+pub const MIN_DEVICE_POLL_INTERVAL_MS: u64 = 250;
+
+/// Accepted `default_confidentiality_level` values, matching session privacy levels
+pub const CONFIDENTIALITY_LEVELS: &[&str] = &["standard", "confidential", "privileged"];
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            auto_name_sessions: true,
+            database_passphrase: false,
+            min_passphrase_score: None,
+            auto_backup_interval_minutes: None,
+            backup_directory: None,
+            backup_keep_count: 7,
+            verify_exports: true,
+            capture_python_command_lines: false,
+            max_import_duration_minutes: 6 * 60,
+            transcription_timeout_minutes: 12 * 60,
+            max_python_processes: 2,
+            redaction_terms: Vec::new(),
+            default_model_size: None,
+            default_language: None,
+            export_directory: None,
+            default_confidentiality_level: "confidential".to_string(),
+            device_poll_interval_ms: 2000,
+            max_duration_secs: None,
+            live_transcription: false,
+        }
+    }
+}
+
+impl AppSettings {
+    pub fn validate(&self) -> Result<(), AppError> {
+        if let Some(score) = self.min_passphrase_score {
+            if score > crate::security_commands::MAX_PASSPHRASE_SCORE {
+                return Err(AppError::Validation(format!(
+                    "min_passphrase_score must be between 0 and {}",
+                    crate::security_commands::MAX_PASSPHRASE_SCORE
+                )));
+            }
+        }
+
+        if self.auto_backup_interval_minutes == Some(0) {
+            return Err(AppError::Validation("auto_backup_interval_minutes must be at least 1".to_string()));
+        }
+        if self.backup_keep_count == 0 {
+            return Err(AppError::Validation("backup_keep_count must be at least 1".to_string()));
+        }
+        if self.max_import_duration_minutes == 0 {
+            return Err(AppError::Validation("max_import_duration_minutes must be at least 1".to_string()));
+        }
+        if self.transcription_timeout_minutes == 0 {
+            return Err(AppError::Validation("transcription_timeout_minutes must be at least 1".to_string()));
+        }
+        if self.max_python_processes == 0 {
+            return Err(AppError::Validation("max_python_processes must be at least 1".to_string()));
+        }
+        if let Some(model) = &self.default_model_size {
+            crate::python_integration::validate_model_size(model)
+                .map_err(|e| AppError::Validation(format!("default_model_size: {}", e)))?;
+        }
+        if let Some(language) = &self.default_language {
+            let is_code = (2..=3).contains(&language.len()) && language.chars().all(|c| c.is_ascii_lowercase());
+            if !is_code {
+                return Err(AppError::Validation(
+                    "default_language must be a lowercase ISO 639 language code such as \"de\"".to_string(),
+                ));
+            }
+        }
+        if self.export_directory.as_deref().is_some_and(|dir| dir.trim().is_empty()) {
+            return Err(AppError::Validation("export_directory must not be empty".to_string()));
+        }
+        if !CONFIDENTIALITY_LEVELS.contains(&self.default_confidentiality_level.as_str()) {
+            return Err(AppError::Validation(format!(
+                "default_confidentiality_level must be one of: {}",
+                CONFIDENTIALITY_LEVELS.join(", ")
+            )));
+        }
+        if self.device_poll_interval_ms < MIN_DEVICE_POLL_INTERVAL_MS {
+            return Err(AppError::Validation(format!(
+                "device_poll_interval_ms must be at least {}",
+                MIN_DEVICE_POLL_INTERVAL_MS
+            )));
+        }
+        if self.max_duration_secs == Some(0) {
+            return Err(AppError::Validation("max_duration_secs must be at least 1".to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Time limit passed to WhisperX runs
+    pub fn transcription_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.transcription_timeout_minutes as u64 * 60)
+    }
+
+    /// Overlay the fields present in `partial` on these settings. Unknown fields
+    /// are rejected so a typo doesn't silently change nothing.
+    pub fn merge(&self, partial: serde_json::Value) -> Result<AppSettings, AppError> {
+        let serde_json::Value::Object(changes) = partial else {
+            return Err(AppError::Validation("Settings update must be an object".to_string()));
+        };
+
+        let mut merged = serde_json::to_value(self)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize settings: {}", e)))?;
+        let fields = merged
+            .as_object_mut()
+            .ok_or_else(|| AppError::Internal("Settings did not serialize to an object".to_string()))?;
+        for (key, value) in changes {
+            if !fields.contains_key(&key) {
+                return Err(AppError::Validation(format!("Unknown setting: {}", key)));
+            }
+            fields.insert(key, value);
+        }
+
+        serde_json::from_value(merged).map_err(|e| AppError::Validation(format!("Invalid settings: {}", e)))
+    }
+}
+
+/// Version of the preset bundle format written by `export_preset`
+pub const PRESET_VERSION: u32 = 1;
+
+/// Portable settings bundle used to standardize configuration across machines
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PresetBundle {
+    pub version: u32,
+    pub exported_at: String,
+    pub settings: AppSettings,
+}
+
+/// Parse a preset bundle, rejecting unknown format versions before reading any settings
+pub fn parse_preset(contents: &str) -> Result<PresetBundle, AppError> {
+    let value: serde_json::Value = serde_json::from_str(contents)
+        .map_err(|e| AppError::Validation(format!("Preset is not valid JSON: {}", e)))?;
+
+    let version = value
+        .get("version")
+        .and_then(|v| v.as_u64())
+        .ok_or_else(|| AppError::Validation("Preset is missing a numeric version".to_string()))?;
+    if version != PRESET_VERSION as u64 {
+        return Err(AppError::Validation(format!(
+            "Preset version {} is not supported; this version of TransRapport reads version {}",
+            version, PRESET_VERSION
+        )));
+    }
+
+    serde_json::from_value(value).map_err(|e| AppError::Validation(format!("Invalid preset: {}", e)))
+}
+
+/// Settings persisted as JSON in the app config directory
+pub struct SettingsStore {
+    path: PathBuf,
+    current: RwLock<AppSettings>,
+}
+
+impl SettingsStore {
+    /// Load settings from `path`, falling back to defaults when missing or unreadable
+    pub fn load(path: PathBuf) -> Self {
+        let current = match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                log::warn!("Ignoring unreadable settings file {}: {}", path.display(), e);
+                AppSettings::default()
+            }),
+            Err(_) => AppSettings::default(),
+        };
+
+        Self {
+            path,
+            current: RwLock::new(current),
+        }
+    }
+
+    pub fn get(&self) -> AppSettings {
+        self.current.read().unwrap().clone()
+    }
+
+    /// Persist `settings` to disk, then make them current
+    pub fn update(&self, settings: AppSettings) -> Result<(), AppError> {
+        settings.validate()?;
+
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| AppError::Io(format!("Failed to create settings directory: {}", e)))?;
+        }
+
+        let contents = serde_json::to_string_pretty(&settings)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize settings: {}", e)))?;
+
+        // Write to a sibling file first so a crash never leaves half-written settings
+        let tmp_path = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, contents)
+            .map_err(|e| AppError::Io(format!("Failed to write settings: {}", e)))?;
+        std::fs::rename(&tmp_path, &self.path)
+            .map_err(|e| AppError::Io(format!("Failed to replace settings: {}", e)))?;
+
+        *self.current.write().unwrap() = settings;
+        Ok(())
+    }
+
+    /// Replace the settings with those of a preset bundle
+    pub fn import_preset(&self, contents: &str) -> Result<(), AppError> {
+        let mut settings = parse_preset(contents)?.settings;
+
+        // Backup location is machine-specific and stays as configured locally
+        settings.backup_directory = self.get().backup_directory;
+
+        // update validates the whole set before persisting, so a bad preset changes nothing
+        self.update(settings)
+    }
+}
+
+#[tauri::command]
+pub async fn get_settings(store: State<'_, SettingsStore>) -> Result<AppSettings, AppError> {
+    Ok(store.get())
+}
+
+/// Change the settings named in `partial`, keeping the rest, and persist them
+#[tauri::command]
+pub async fn update_settings(
+    store: State<'_, SettingsStore>,
+    partial: serde_json::Value
+) -> Result<AppSettings, AppError> {
+    log::info!("Updating application settings");
+
+    let settings = store.get().merge(partial)?;
+    store.update(settings)?;
+    Ok(store.get())
+}
+
+#[tauri::command]
+pub async fn export_preset(
+    store: State<'_, SettingsStore>,
+    path: String
+) -> Result<String, AppError> {
+    log::info!("Exporting settings preset to: {}", path);
+
+    let bundle = PresetBundle {
+        version: PRESET_VERSION,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        settings: store.get(),
+    };
+    let contents = serde_json::to_string_pretty(&bundle)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize preset: {}", e)))?;
+
+    tokio::fs::write(&path, contents)
+        .await
+        .map_err(|e| AppError::Io(format!("Failed to write preset: {}", e)))?;
+
+    Ok(path)
+}
+
+#[tauri::command]
+pub async fn import_preset(
+    store: State<'_, SettingsStore>,
+    path: String
+) -> Result<AppSettings, AppError> {
+    log::info!("Importing settings preset from: {}", path);
+
+    let contents = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| AppError::Io(format!("Failed to read preset: {}", e)))?;
+    store.import_preset(&contents)?;
+    Ok(store.get())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+
+    fn bundle(version: serde_json::Value, settings: serde_json::Value) -> String {
+        serde_json::json!({
+            "version": version,
+            "exported_at": "2026-01-01T00:00:00Z",
+            "settings": settings,
+        })
+        .to_string()
+    }
+
+    fn validation_message(result: Result<PresetBundle, AppError>) -> String {
+        match result {
+            Err(AppError::Validation(message)) => message,
+            other => panic!("expected a validation error, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn exported_bundle_parses_back() {
+        let settings = AppSettings {
+            backup_keep_count: 3,
+            redaction_terms: vec!["Anna".to_string()],
+            default_language: Some("de".to_string()),
+            ..AppSettings::default()
+        };
+        let exported = serde_json::to_string(&PresetBundle {
+            version: PRESET_VERSION,
+            exported_at: "2026-01-01T00:00:00Z".to_string(),
+            settings,
+        })
+        .unwrap();
+
+        let parsed = parse_preset(&exported).unwrap().settings;
+        assert_eq!(parsed.backup_keep_count, 3);
+        assert_eq!(parsed.redaction_terms, ["Anna"]);
+        assert_eq!(parsed.default_language.as_deref(), Some("de"));
+    }
+
+    #[test]
+    fn settings_missing_from_a_bundle_take_their_defaults() {
+        let parsed = parse_preset(&bundle(serde_json::json!(1), serde_json::json!({ "verify_exports": false })))
+            .unwrap()
+            .settings;
+        assert!(!parsed.verify_exports);
+        assert_eq!(parsed.backup_keep_count, AppSettings::default().backup_keep_count);
+    }
+
+    #[test]
+    fn rejects_unreadable_and_unsupported_bundles() {
+        assert!(validation_message(parse_preset("{ not json")).contains("not valid JSON"));
+        assert!(validation_message(parse_preset(r#"{"settings": {}}"#)).contains("missing a numeric version"));
+        assert!(validation_message(parse_preset(&bundle(serde_json::json!("1"), serde_json::json!({}))))
+            .contains("missing a numeric version"));
+        // A newer format is refused before its settings are looked at
+        assert!(validation_message(parse_preset(&bundle(serde_json::json!(2), serde_json::json!("anything"))))
+            .contains("version 2 is not supported"));
+        assert!(validation_message(parse_preset(&bundle(
+            serde_json::json!(1),
+            serde_json::json!({ "backup_keep_count": "seven" })
+        )))
+        .starts_with("Invalid preset"));
+    }
+
+    #[test]
+    fn import_keeps_the_local_backup_directory_and_rejects_invalid_presets() {
+        let dir = temp_dir();
+        let store = SettingsStore::load(dir.join("settings.json"));
+        store
+            .update(AppSettings {
+                backup_directory: Some("/local/backups".to_string()),
+                ..AppSettings::default()
+            })
+            .unwrap();
+
+        store
+            .import_preset(&bundle(
+                serde_json::json!(1),
+                serde_json::json!({ "backup_directory": "/elsewhere", "backup_keep_count": 2 }),
+            ))
+            .unwrap();
+        assert_eq!(store.get().backup_directory.as_deref(), Some("/local/backups"));
+        assert_eq!(store.get().backup_keep_count, 2);
+
+        let invalid = bundle(serde_json::json!(1), serde_json::json!({ "backup_keep_count": 0 }));
+        assert!(matches!(store.import_preset(&invalid), Err(AppError::Validation(_))));
+        assert_eq!(store.get().backup_keep_count, 2);
+        let persisted = SettingsStore::load(dir.join("settings.json"));
+        assert_eq!(persisted.get().backup_keep_count, 2);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::audio_commands::Recordings;
+use crate::error::AppError;
+use crate::storage_commands::DatabaseKey;
+use crate::transcription_commands::{TranscriptionJobs, TranscriptionQueue};
+
+/// Shared state managed by Tauri, built in `setup` once the database is open
+pub struct AppState {
+    pub db: SqlitePool,
+    /// Key `db` was opened with, for checking other database files such as backups
+    pub database_key: DatabaseKey,
+    /// Recordings started in this run, keyed by session id
+    pub recordings: Recordings,
+    /// WhisperX runs started in this run, keyed by session id
+    pub transcriptions: TranscriptionJobs,
+    /// Files waiting for or going through transcription via the queue
+    pub transcription_queue: TranscriptionQueue,
+    /// Resolved on first use; a failed lookup isn't cached so installing Python fixes it
+    python_interpreter: tokio::sync::OnceCell<PathBuf>,
+    /// One permit per Python process allowed to run at once
+    python_permits: Arc<Semaphore>,
+    max_python_processes: usize,
+    /// Callers waiting for a permit
+    python_queued: AtomicUsize,
+}
+
+/// Python process load reported to the UI
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackendLoad {
+    pub running: usize,
+    pub queued: usize,
+    pub max_concurrent: usize,
+}
+
+/// Counts a caller as queued until it gets its permit or gives up waiting
+struct QueuedCaller<'a>(&'a AtomicUsize);
+
+impl Drop for QueuedCaller<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl AppState {
+    pub fn new(db: SqlitePool, database_key: DatabaseKey, max_python_processes: usize) -> Self {
+        Self {
+            db,
+            database_key,
+            recordings: Recordings::default(),
+            transcriptions: TranscriptionJobs::default(),
+            transcription_queue: TranscriptionQueue::default(),
+            python_interpreter: tokio::sync::OnceCell::new(),
+            python_permits: Arc::new(Semaphore::new(max_python_processes)),
+            max_python_processes,
+            python_queued: AtomicUsize::new(0),
+        }
+    }
+
+    /// Python interpreter for the pipeline scripts, probed once and then reused
+    pub async fn python_interpreter(&self, app: &AppHandle) -> Result<&Path, AppError> {
+        let resource_dir = app.path().resource_dir().ok();
+        self.python_interpreter
+            .get_or_try_init(|| crate::python_integration::resolve_python_interpreter(resource_dir.as_deref()))
+            .await
+            .map(PathBuf::as_path)
+    }
+
+    /// Wait for a free Python process slot; dropping the permit frees it
+    pub async fn acquire_python_permit(&self) -> OwnedSemaphorePermit {
+        self.python_queued.fetch_add(1, Ordering::SeqCst);
+        let _queued = QueuedCaller(&self.python_queued);
+        self.python_permits
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("Python semaphore is never closed")
+    }
+
+    pub fn backend_load(&self) -> BackendLoad {
+        BackendLoad {
+            running: self.max_python_processes - self.python_permits.available_permits(),
+            queued: self.python_queued.load(Ordering::SeqCst),
+            max_concurrent: self.max_python_processes,
+        }
+    }
+}
+
+/// Manage the state that needs the open database. Sessions a crash left
+/// mid-recording or mid-transcription are reconciled first; commands taking
+/// `AppState` work from then on.
+pub async fn manage_database(app: &AppHandle, pool: SqlitePool, database_key: DatabaseKey) {
+    let recovery = crate::recovery::recover_sessions(&pool).await.unwrap_or_else(|e| {
+        log::error!("Failed to recover interrupted sessions: {}", e);
+        crate::recovery::RecoverySummary::default()
+    });
+    app.manage(recovery);
+    let max_python_processes = app.state::<crate::settings::SettingsStore>().get().max_python_processes;
+    app.manage(AppState::new(pool, database_key, max_python_processes));
+}
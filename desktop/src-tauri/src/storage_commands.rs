@@ -1,8 +1,13 @@
-use tauri::State;
+use tauri::{Manager, State};
 use serde::{Deserialize, Serialize};
 use sqlx::{SqlitePool, Row};
+use sqlx::sqlite::SqliteConnectOptions;
 use chrono::{DateTime, Utc};
 
+use crate::error::AppError;
+use crate::settings::SettingsStore;
+use crate::state::AppState;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ConversationSession {
     pub id: String,
@@ -11,114 +16,1234 @@ pub struct ConversationSession {
     pub client_reference: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
-    pub status: String, // "recording", "transcribing", "analyzing", "completed"
+    pub status: String, // "recording", "transcribing", "analyzing", "completed", "interrupted"
     pub duration: Option<f64>,
     pub file_path: Option<String>,
 }
 
-pub async fn initialize_database() -> Result<(), Box<dyn std::error::Error>> {
-    // TODO: Initialize SQLCipher database with encryption
-    log::info!("Initializing encrypted database");
+/// Session names that still count as "unnamed" for automatic naming
+const DEFAULT_SESSION_NAMES: &[&str] = &["", "untitled", "untitled session", "new session"];
+
+/// Filler words skipped when deriving a session name from the transcript
+const NAME_FILLER_WORDS: &[&str] = &[
+    "a", "an", "and", "the", "so", "well", "um", "uh", "erm", "hmm", "mhm", "okay", "ok",
+    "yeah", "yes", "no", "hi", "hello", "right", "i", "you", "it", "is", "to", "of",
+    "ja", "nein", "also", "äh", "ähm", "und", "hallo", "gut", "na", "ich", "du", "das",
+];
+
+const AUTO_NAME_MAX_WORDS: usize = 5;
+const AUTO_NAME_MAX_CHARS: usize = 60;
+
+/// Full-text index over transcript text, backed by `transcript_segments` rows.
+/// Created by the initial migration; kept here for index rebuilds.
+pub const TRANSCRIPT_SEARCH_DDL: &str = r#"
+    CREATE VIRTUAL TABLE IF NOT EXISTS transcript_search USING fts5(
+        text,
+        content = 'transcript_segments',
+        content_rowid = 'rowid'
+    )
+    "#;
+
+/// OS keyring entry holding the database key
+const KEYRING_SERVICE: &str = "TransRapport";
+const KEYRING_ACCOUNT: &str = "database-key";
+
+/// Bytes of randomness in a generated database key
+const DATABASE_KEY_BYTES: usize = 32;
+
+/// Header every unencrypted SQLite file starts with; SQLCipher files look random
+const PLAINTEXT_SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+/// Hex database key from the OS keyring, generated and stored on first run
+fn database_key() -> Result<String, String> {
+    let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_ACCOUNT)
+        .map_err(|e| format!("Failed to open OS keyring: {}", e))?;
+
+    match entry.get_password() {
+        Ok(key) => Ok(key),
+        Err(keyring::Error::NoEntry) => {
+            log::info!("No database key in OS keyring; generating one");
+            let mut bytes = [0u8; DATABASE_KEY_BYTES];
+            getrandom::getrandom(&mut bytes)
+                .map_err(|e| format!("Failed to generate database key: {}", e))?;
+            let key: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+            entry
+                .set_password(&key)
+                .map_err(|e| format!("Failed to store database key in OS keyring: {}", e))?;
+            Ok(key)
+        }
+        Err(e) => Err(format!("Failed to read database key from OS keyring: {}", e)),
+    }
+}
+
+/// Refuse to open an existing unencrypted database with a key, which SQLCipher
+/// would reject as corrupt
+fn ensure_not_plaintext(path: &std::path::Path) -> Result<(), String> {
+    let mut header = [0u8; 16];
+    let read = std::fs::File::open(path).and_then(|mut file| {
+        use std::io::Read;
+        file.read_exact(&mut header)
+    });
+    match read {
+        Ok(()) if &header == PLAINTEXT_SQLITE_HEADER => Err(format!(
+            "Database {} is not encrypted; export its data and move it aside so an encrypted database can be created",
+            path.display()
+        )),
+        // Missing or shorter than a header: SQLCipher creates or initializes it
+        _ => Ok(()),
+    }
+}
+
+/// Where the database key comes from
+pub enum DatabaseKey {
+    /// Random key generated on first run and kept in the OS keyring
+    Keyring,
+    /// Passphrase given to `unlock_database`; SQLCipher derives the key from it
+    Passphrase(String),
+}
+
+impl DatabaseKey {
+    /// Value for `PRAGMA key`, which sqlx passes through unquoted
+    async fn pragma_value(&self) -> Result<String, String> {
+        match self {
+            DatabaseKey::Keyring => {
+                let key = tokio::task::spawn_blocking(database_key)
+                    .await
+                    .map_err(|e| format!("Database key lookup failed: {}", e))??;
+                Ok(format!("\"x'{}'\"", key))
+            }
+            DatabaseKey::Passphrase(passphrase) => Ok(format!("'{}'", passphrase.replace('\'', "''"))),
+        }
+    }
+}
+
+/// SQLite result code for a file that is not a database
+const SQLITE_NOTADB: &str = "26";
+
+/// Whether SQLite refused the file as not a database, which is how SQLCipher
+/// reports a wrong key
+fn is_rejected_key(error: &sqlx::Error) -> bool {
+    error
+        .as_database_error()
+        .and_then(|e| e.code())
+        .is_some_and(|code| code == SQLITE_NOTADB)
+}
+
+pub async fn initialize_database(database_path: &std::path::Path, key: &DatabaseKey) -> Result<SqlitePool, AppError> {
+    log::info!("Initializing encrypted database at: {}", database_path.display());
+    
+    ensure_not_plaintext(database_path).map_err(AppError::Validation)?;
+    let pragma_key = key.pragma_value().await.map_err(AppError::Internal)?;
+    let rejected = |e: sqlx::Error| match key {
+        DatabaseKey::Passphrase(_) if is_rejected_key(&e) => {
+            AppError::WrongPassphrase("Wrong passphrase for the database".to_string())
+        }
+        _ => AppError::Database(format!("Database key was rejected; the database may belong to another key: {}", e)),
+    };
+    
+    // sqlx issues `key` before any other pragma, as SQLCipher requires
+    let options = SqliteConnectOptions::new()
+        .filename(database_path)
+        .create_if_missing(true)
+        .pragma("key", pragma_key);
+    let pool = SqlitePool::connect_with(options).await.map_err(|e| {
+        if is_rejected_key(&e) {
+            rejected(e)
+        } else {
+            AppError::Database(format!("Failed to open database: {}", e))
+        }
+    })?;
+    
+    // The key is only checked once the file is read
+    sqlx::query("SELECT count(*) FROM sqlite_master")
+        .execute(&pool)
+        .await
+        .map_err(rejected)?;
+    
+    // Apply pending schema migrations from `migrations/`
+    let migrator = sqlx::migrate!("./migrations");
+    // The bookkeeping table doesn't exist before the first run
+    let applied_before: Vec<i64> =
+        sqlx::query_scalar("SELECT version FROM _sqlx_migrations WHERE success = 1")
+            .fetch_all(&pool)
+            .await
+            .unwrap_or_default();
+    migrator
+        .run(&pool)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to apply database migrations: {}", e)))?;
+    for migration in migrator.iter() {
+        if migration.migration_type.is_up_migration() && !applied_before.contains(&migration.version) {
+            log::info!("Applied database migration {}: {}", migration.version, migration.description);
+        }
+    }
+    
+    log::info!("Database initialized successfully");
+    Ok(pool)
+}
+
+/// Open the database with a passphrase when `database_passphrase` is set; until
+/// then, commands that need the database fail. The first unlock creates the
+/// database keyed with the passphrase, which must meet `min_passphrase_score`.
+#[tauri::command]
+pub async fn unlock_database(
+    app: tauri::AppHandle,
+    paths: State<'_, crate::paths::AppPaths>,
+    settings: State<'_, SettingsStore>,
+    passphrase: String
+) -> Result<(), AppError> {
+    // Never log the passphrase itself
+    log::info!("Unlocking database");
+    
+    if app.try_state::<AppState>().is_some() {
+        return Ok(());
+    }
+    if !settings.get().database_passphrase {
+        return Err(AppError::Validation("The database is keyed from the OS keyring and takes no passphrase".to_string()));
+    }
+    if passphrase.is_empty() {
+        return Err(AppError::Validation("Passphrase must not be empty".to_string()));
+    }
+    
+    let database = paths.database();
+    if !database.exists() {
+        crate::security_commands::enforce_passphrase_policy(&passphrase, settings.get().min_passphrase_score)?;
+        log::info!("Creating a passphrase-protected database");
+    }
+    
+    let key = DatabaseKey::Passphrase(passphrase);
+    let pool = initialize_database(&database, &key).await?;
+    crate::state::manage_database(&app, pool, key).await;
+    Ok(())
+}
+
+/// Open a database file read-only with `key` and check it is an intact
+/// TransRapport database. Returns the number of sessions it holds.
+pub async fn check_database_file(path: &std::path::Path, key: &DatabaseKey) -> Result<i64, String> {
+    ensure_not_plaintext(path)?;
+    let pragma_key = key.pragma_value().await?;
     
-    // For now, use SQLite without encryption - will be upgraded to SQLCipher
-    let database_url = "sqlite:transrapport.db";
-    let pool = SqlitePool::connect(database_url).await?;
+    let options = SqliteConnectOptions::new()
+        .filename(path)
+        .read_only(true)
+        .pragma("key", pragma_key);
+    let pool = SqlitePool::connect_with(options)
+        .await
+        .map_err(|e| format!("cannot be opened: {}", e))?;
     
-    // Create tables
+    let checked = async {
+        let integrity: String = sqlx::query_scalar("PRAGMA quick_check")
+            .fetch_one(&pool)
+            .await
+            .map_err(|e| format!("cannot be read with the database key: {}", e))?;
+        if integrity != "ok" {
+            return Err(format!("failed its integrity check: {}", integrity));
+        }
+        sqlx::query_scalar("SELECT count(*) FROM conversation_sessions")
+            .fetch_one(&pool)
+            .await
+            .map_err(|_| "is not a TransRapport database".to_string())
+    }
+    .await;
+    pool.close().await;
+    checked
+}
+
+/// Whether a session name is a placeholder that automatic naming may replace
+pub fn is_default_session_name(name: &str) -> bool {
+    let normalized = name.trim().to_lowercase();
+    DEFAULT_SESSION_NAMES.contains(&normalized.as_str())
+}
+
+/// Derive a filesystem-safe session name from the first content words of a transcript
+pub fn derive_session_name(
+    segments: &[crate::transcription_commands::SpeakerSegment]
+) -> Option<String> {
+    let mut ordered: Vec<_> = segments.iter().collect();
+    ordered.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+
+    let words: Vec<String> = ordered
+        .iter()
+        .flat_map(|segment| segment.text.split(|c: char| !c.is_alphanumeric()))
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .filter(|word| !NAME_FILLER_WORDS.contains(&word.as_str()))
+        .filter(|word| !word.chars().all(|c| c.is_numeric()))
+        .take(AUTO_NAME_MAX_WORDS)
+        .collect();
+
+    if words.is_empty() {
+        return None;
+    }
+
+    let mut name = String::new();
+    for word in &words {
+        if name.chars().count() + word.chars().count() + 1 > AUTO_NAME_MAX_CHARS {
+            break;
+        }
+        if !name.is_empty() {
+            name.push(' ');
+        }
+        name.push_str(word);
+    }
+
+    if name.is_empty() {
+        // A single over-long word; cut it at the character limit
+        name = words[0].chars().take(AUTO_NAME_MAX_CHARS).collect();
+    }
+
+    let mut chars = name.chars();
+    chars.next().map(|first| first.to_uppercase().chain(chars).collect())
+}
+
+/// Column list matching `session_from_row`
+pub const SESSION_COLUMNS: &str =
+    "id, name, session_type, client_reference, created_at, updated_at, status, duration, file_path";
+
+fn parse_timestamp(row: &sqlx::sqlite::SqliteRow, column: &str) -> Result<DateTime<Utc>, sqlx::Error> {
+    let raw: String = row.try_get(column)?;
+    DateTime::parse_from_rfc3339(&raw)
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+        .map_err(|e| sqlx::Error::ColumnDecode {
+            index: column.to_string(),
+            source: Box::new(e),
+        })
+}
+
+pub fn session_from_row(row: &sqlx::sqlite::SqliteRow) -> Result<ConversationSession, sqlx::Error> {
+    Ok(ConversationSession {
+        id: row.try_get("id")?,
+        name: row.try_get("name")?,
+        session_type: row.try_get("session_type")?,
+        client_reference: row.try_get("client_reference")?,
+        created_at: parse_timestamp(row, "created_at")?,
+        updated_at: parse_timestamp(row, "updated_at")?,
+        status: row.try_get("status")?,
+        duration: row.try_get("duration")?,
+        file_path: row.try_get("file_path")?,
+    })
+}
+
+/// `LIKE ... ESCAPE '\\'` pattern matching `text` literally anywhere in a column
+pub fn like_pattern(text: &str) -> String {
+    let escaped = text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+/// Tags compare lowercased and trimmed; `None` for a blank tag
+pub fn normalize_tag(tag: &str) -> Option<String> {
+    let tag = tag.trim().to_lowercase();
+    (!tag.is_empty()).then_some(tag)
+}
+
+/// Session list filters shared by `get_sessions` and `export_session_list`
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionFilters {
+    pub session_type: Option<String>, // "therapy", "legal", "business"
+    pub status: Option<String>,
+    /// Free text matched against name and client reference
+    pub query: Option<String>,
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+    /// Sessions must carry every one of these tags
+    pub tags: Vec<String>,
+}
+
+impl SessionFilters {
+    /// Append a WHERE clause for the filters that are set, using bound parameters only
+    pub fn push_where(&self, builder: &mut sqlx::QueryBuilder<'_, sqlx::Sqlite>) {
+        let mut separator = " WHERE ";
+
+        if let Some(session_type) = &self.session_type {
+            builder.push(separator).push("session_type = ").push_bind(session_type.clone());
+            separator = " AND ";
+        }
+        if let Some(status) = &self.status {
+            builder.push(separator).push("status = ").push_bind(status.clone());
+            separator = " AND ";
+        }
+        if let Some(query) = self.query.as_deref().map(str::trim).filter(|q| !q.is_empty()) {
+            let pattern = like_pattern(query);
+            builder
+                .push(separator)
+                .push("(name LIKE ")
+                .push_bind(pattern.clone())
+                .push(" ESCAPE '\\' OR client_reference LIKE ")
+                .push_bind(pattern)
+                .push(" ESCAPE '\\')");
+            separator = " AND ";
+        }
+        if let Some(after) = &self.created_after {
+            builder.push(separator).push("created_at >= ").push_bind(after.to_rfc3339());
+            separator = " AND ";
+        }
+        if let Some(before) = &self.created_before {
+            builder.push(separator).push("created_at < ").push_bind(before.to_rfc3339());
+            separator = " AND ";
+        }
+        let tags: std::collections::BTreeSet<String> = self
+            .tags
+            .iter()
+            .filter_map(|tag| normalize_tag(tag))
+            .collect();
+        if !tags.is_empty() {
+            builder.push(separator).push(
+                "id IN (SELECT st.session_id FROM session_tags st \
+                 JOIN tags t ON t.id = st.tag_id WHERE t.name IN (",
+            );
+            let mut separated = builder.separated(", ");
+            for tag in &tags {
+                separated.push_bind(tag.clone());
+            }
+            separated.push_unseparated(") GROUP BY st.session_id HAVING COUNT(*) = ");
+            builder.push_bind(tags.len() as i64).push(")");
+        }
+    }
+}
+
+/// Message of the `NotFound` error commands return for unknown session ids
+pub const SESSION_NOT_FOUND: &str = "session not found";
+
+/// Fetch a single session, `None` when the id is unknown
+pub async fn fetch_session(
+    pool: &SqlitePool,
+    session_id: &str
+) -> Result<Option<ConversationSession>, sqlx::Error> {
+    let sql = format!("SELECT {} FROM conversation_sessions WHERE id = ?", SESSION_COLUMNS);
+    let row = sqlx::query(&sql)
+        .bind(session_id)
+        .fetch_optional(pool)
+        .await?;
+
+    row.as_ref().map(session_from_row).transpose()
+}
+
+/// Load a session's stored transcript segments ordered by start time
+pub async fn load_segments(
+    pool: &SqlitePool,
+    session_id: &str
+) -> Result<Vec<crate::transcription_commands::SpeakerSegment>, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT id, speaker_id, speaker_label, start_time, end_time, text, confidence, words \
+         FROM transcript_segments WHERE session_id = ? ORDER BY start_time",
+    )
+    .bind(session_id)
+    .fetch_all(pool)
+    .await?;
+
+    rows.iter()
+        .map(|row| {
+            Ok(crate::transcription_commands::SpeakerSegment {
+                id: Some(row.try_get("id")?),
+                speaker_id: row.try_get("speaker_id")?,
+                speaker_label: row.try_get("speaker_label")?,
+                start_time: row.try_get("start_time")?,
+                end_time: row.try_get("end_time")?,
+                text: row.try_get("text")?,
+                confidence: row.try_get("confidence")?,
+                words: row
+                    .try_get::<Option<String>, _>("words")?
+                    .map(|words| serde_json::from_str(&words))
+                    .transpose()
+                    .map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+            })
+        })
+        .collect()
+}
+
+/// Replace all stored segments of a session in a single transaction
+pub async fn store_segments(
+    pool: &SqlitePool,
+    session_id: &str,
+    segments: &[crate::transcription_commands::SpeakerSegment]
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    write_segments(&mut tx, session_id, segments).await?;
+    tx.commit().await
+}
+
+/// Replace a session's segments and record their fingerprint on an open connection
+pub async fn write_segments(
+    conn: &mut sqlx::SqliteConnection,
+    session_id: &str,
+    segments: &[crate::transcription_commands::SpeakerSegment]
+) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM transcript_segments WHERE session_id = ?")
+        .bind(session_id)
+        .execute(&mut *conn)
+        .await?;
+
+    for segment in segments {
+        insert_segment(&mut *conn, session_id, segment).await?;
+    }
+
+    // Record what was saved so later tampering can be detected
+    record_fingerprint(conn, session_id, segments).await
+}
+
+/// Insert one segment, keeping its id when it has one; returns the stored id
+pub async fn insert_segment(
+    conn: &mut sqlx::SqliteConnection,
+    session_id: &str,
+    segment: &crate::transcription_commands::SpeakerSegment
+) -> Result<String, sqlx::Error> {
+    let id = segment
+        .id
+        .clone()
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    let words = segment
+        .words
+        .as_ref()
+        .map(serde_json::to_string)
+        .transpose()
+        .map_err(|e| sqlx::Error::Protocol(format!("Failed to encode word timings: {}", e)))?;
+
     sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS conversation_sessions (
-            id TEXT PRIMARY KEY,
-            name TEXT NOT NULL,
-            session_type TEXT NOT NULL,
-            client_reference TEXT,
-            created_at TEXT NOT NULL,
-            updated_at TEXT NOT NULL,
-            status TEXT NOT NULL DEFAULT 'created',
-            duration REAL,
-            file_path TEXT
-        )
-        "#,
+        "INSERT INTO transcript_segments \
+         (id, session_id, speaker_id, speaker_label, start_time, end_time, text, confidence, words) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
     )
-    .execute(&pool)
+    .bind(&id)
+    .bind(session_id)
+    .bind(&segment.speaker_id)
+    .bind(&segment.speaker_label)
+    .bind(segment.start_time)
+    .bind(segment.end_time)
+    .bind(&segment.text)
+    .bind(segment.confidence)
+    .bind(words)
+    .execute(conn)
     .await?;
-    
-    log::info!("Database initialized successfully");
+
+    Ok(id)
+}
+
+pub async fn insert_session(
+    conn: &mut sqlx::SqliteConnection,
+    session: &ConversationSession
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO conversation_sessions \
+         (id, name, session_type, client_reference, created_at, updated_at, status, duration, file_path) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&session.id)
+    .bind(&session.name)
+    .bind(&session.session_type)
+    .bind(&session.client_reference)
+    .bind(session.created_at.to_rfc3339())
+    .bind(session.updated_at.to_rfc3339())
+    .bind(&session.status)
+    .bind(session.duration)
+    .bind(&session.file_path)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Tables holding per-session data, cleared before the session row itself
+const SESSION_DATA_TABLES: &[&str] = &[
+    "transcript_segments",
+    "session_embeddings",
+    "transcript_fingerprints",
+    "marker_events",
+    "rapport_indicators",
+    "speaker_id_mappings",
+    "run_metrics",
+    "session_tags",
+];
+
+/// Delete a session row and everything stored for it; returns the rows removed
+pub async fn delete_session_rows(
+    conn: &mut sqlx::SqliteConnection,
+    session_id: &str
+) -> Result<u64, sqlx::Error> {
+    let mut removed = 0;
+    for table in SESSION_DATA_TABLES {
+        removed += sqlx::query(&format!("DELETE FROM {} WHERE session_id = ?", table))
+            .bind(session_id)
+            .execute(&mut *conn)
+            .await?
+            .rows_affected();
+    }
+    removed += sqlx::query("DELETE FROM conversation_sessions WHERE id = ?")
+        .bind(session_id)
+        .execute(&mut *conn)
+        .await?
+        .rows_affected();
+
+    Ok(removed)
+}
+
+/// Store the fingerprint of a session's transcript as it is being written
+pub async fn record_fingerprint(
+    conn: &mut sqlx::SqliteConnection,
+    session_id: &str,
+    segments: &[crate::transcription_commands::SpeakerSegment]
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO transcript_fingerprints (session_id, fingerprint, created_at) VALUES (?, ?, ?) \
+         ON CONFLICT(session_id) DO UPDATE SET \
+         fingerprint = excluded.fingerprint, created_at = excluded.created_at",
+    )
+    .bind(session_id)
+    .bind(crate::integrity_commands::compute_transcript_fingerprint(segments))
+    .bind(Utc::now().to_rfc3339())
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Replace a placeholder session name with one derived from its transcript
+async fn apply_auto_session_name(
+    pool: &SqlitePool,
+    session_id: &str,
+    segments: &[crate::transcription_commands::SpeakerSegment]
+) -> Result<(), sqlx::Error> {
+    let row = sqlx::query("SELECT name FROM conversation_sessions WHERE id = ?")
+        .bind(session_id)
+        .fetch_optional(pool)
+        .await?;
+
+    let Some(row) = row else {
+        return Ok(());
+    };
+
+    let current_name: String = row.try_get("name")?;
+    if !is_default_session_name(&current_name) {
+        return Ok(());
+    }
+
+    if let Some(name) = derive_session_name(segments) {
+        log::info!("Auto-naming session {}", session_id);
+        sqlx::query("UPDATE conversation_sessions SET name = ?, updated_at = ? WHERE id = ?")
+            .bind(&name)
+            .bind(Utc::now().to_rfc3339())
+            .bind(session_id)
+            .execute(pool)
+            .await?;
+    }
+
     Ok(())
 }
 
 #[tauri::command]
 pub async fn create_session(
+    state: State<'_, AppState>,
     name: String,
     session_type: String,
     client_reference: Option<String>
-) -> Result<ConversationSession, String> {
-    // TODO: Implement session creation with database storage
-    log::info!("Creating new session: {} of type: {}", name, session_type);
+) -> Result<ConversationSession, AppError> {
+    log::info!("Creating new session of type: {}", session_type);
     
+    let now = Utc::now();
     let session = ConversationSession {
         id: uuid::Uuid::new_v4().to_string(),
         name,
         session_type,
         client_reference,
-        created_at: Utc::now(),
-        updated_at: Utc::now(),
+        created_at: now,
+        updated_at: now,
         status: "created".to_string(),
         duration: None,
         file_path: None,
     };
     
+    let mut conn = state.db
+        .acquire()
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to connect to database: {}", e)))?;
+    insert_session(&mut conn, &session)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to create session: {}", e)))?;
+    
     Ok(session)
 }
 
+/// Sessions matching `filters`, newest first, paged by `limit` and `offset`
+pub async fn find_sessions(
+    pool: &SqlitePool,
+    filters: &SessionFilters,
+    limit: Option<u32>,
+    offset: Option<u32>
+) -> Result<Vec<ConversationSession>, sqlx::Error> {
+    let mut builder = sqlx::QueryBuilder::new(format!("SELECT {} FROM conversation_sessions", SESSION_COLUMNS));
+    filters.push_where(&mut builder);
+    // Id breaks ties so pages neither skip nor repeat sessions
+    builder.push(" ORDER BY created_at DESC, id");
+    if limit.is_some() || offset.is_some() {
+        // SQLite only accepts OFFSET after a LIMIT; -1 means unlimited
+        builder
+            .push(" LIMIT ")
+            .push_bind(limit.map(i64::from).unwrap_or(-1))
+            .push(" OFFSET ")
+            .push_bind(offset.unwrap_or(0) as i64);
+    }
+
+    let rows = builder.build().fetch_all(pool).await?;
+    rows.iter().map(session_from_row).collect()
+}
+
 #[tauri::command]
-pub async fn get_sessions(limit: Option<u32>) -> Result<Vec<ConversationSession>, String> {
-    // TODO: Implement session retrieval from database
-    log::info!("Retrieving sessions with limit: {:?}", limit);
-    
-    // Mock data for now
-    Ok(vec![
-        ConversationSession {
-            id: "session-1".to_string(),
-            name: "Client A - Therapy Session".to_string(),
-            session_type: "therapy".to_string(),
-            client_reference: Some("CLIENT-001-2025".to_string()),
-            created_at: Utc::now(),
-            updated_at: Utc::now(),
-            status: "completed".to_string(),
-            duration: Some(3600.0),
-            file_path: Some("/tmp/session1.wav".to_string()),
-        }
-    ])
+pub async fn get_sessions(
+    state: State<'_, AppState>,
+    filters: Option<SessionFilters>,
+    limit: Option<u32>,
+    offset: Option<u32>
+) -> Result<Vec<ConversationSession>, AppError> {
+    log::info!("Retrieving sessions with limit: {:?}, offset: {:?}", limit, offset);
+    
+    find_sessions(&state.db, &filters.unwrap_or_default(), limit, offset)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load sessions: {}", e)))
 }
 
 #[tauri::command]
 pub async fn save_transcript(
+    state: State<'_, AppState>,
+    settings: State<'_, SettingsStore>,
     session_id: String,
     segments: Vec<crate::transcription_commands::SpeakerSegment>
-) -> Result<String, String> {
-    // TODO: Implement transcript saving to encrypted database
+) -> Result<usize, AppError> {
     log::info!("Saving transcript for session: {} with {} segments", 
                session_id, segments.len());
     
-    Ok("Transcript saved successfully".to_string())
+    save_segments(&state.db, &session_id, &segments, settings.get().auto_name_sessions).await?;
+    
+    Ok(segments.len())
+}
+
+/// Store a session's transcript; with `auto_name`, a placeholder session name is
+/// then replaced with one derived from it
+async fn save_segments(
+    pool: &SqlitePool,
+    session_id: &str,
+    segments: &[crate::transcription_commands::SpeakerSegment],
+    auto_name: bool
+) -> Result<(), AppError> {
+    store_segments(pool, session_id, segments)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to save transcript: {}", e)))?;
+    
+    if auto_name {
+        apply_auto_session_name(pool, session_id, segments)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to auto-name session: {}", e)))?;
+    }
+    
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn load_transcript(
+    state: State<'_, AppState>,
+    session_id: String
+) -> Result<Vec<crate::transcription_commands::SpeakerSegment>, AppError> {
+    log::info!("Loading transcript for session: {}", session_id);
+    
+    load_segments(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load transcript: {}", e)))
 }
 
 #[tauri::command]
-pub async fn load_session(session_id: String) -> Result<ConversationSession, String> {
-    // TODO: Implement session loading from database
+pub async fn load_session(
+    state: State<'_, AppState>,
+    session_id: String
+) -> Result<ConversationSession, AppError> {
     log::info!("Loading session: {}", session_id);
     
-    // Mock session for now
-    Ok(ConversationSession {
-        id: session_id.clone(),
-        name: "Loaded Session".to_string(),
-        session_type: "therapy".to_string(),
+    fetch_session(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load session: {}", e)))?
+        .ok_or_else(|| AppError::NotFound(SESSION_NOT_FOUND.to_string()))
+}
+
+/// Dashboard statistics of one session; zeros and `None` where nothing has been
+/// transcribed or analyzed yet
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionSummary {
+    pub session_id: String,
+    pub duration: f64,
+    pub segment_count: i64,
+    pub word_count: i64,
+    pub speaker_count: i64,
+    pub marker_counts_by_type: std::collections::BTreeMap<String, i64>,
+    pub avg_rapport: Option<f64>,
+    /// Lowest and highest rapport value
+    pub rapport_range: Option<(f64, f64)>,
+}
+
+#[tauri::command]
+pub async fn get_session_summary(
+    state: State<'_, AppState>,
+    session_id: String
+) -> Result<SessionSummary, AppError> {
+    log::info!("Summarizing session: {}", session_id);
+    
+    let session = fetch_session(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load session: {}", e)))?
+        .ok_or_else(|| AppError::NotFound(SESSION_NOT_FOUND.to_string()))?;
+    
+    // Words are runs separated by single spaces, which is how segment text is stored
+    let (segment_count, word_count, speaker_count, transcript_end): (i64, i64, i64, Option<f64>) = sqlx::query_as(
+        "SELECT count(*), \
+                COALESCE(SUM(CASE WHEN trim(text) = '' THEN 0 \
+                    ELSE length(trim(text)) - length(replace(trim(text), ' ', '')) + 1 END), 0), \
+                count(DISTINCT speaker_id), \
+                MAX(end_time) \
+         FROM transcript_segments WHERE session_id = ?",
+    )
+    .bind(&session_id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to summarize transcript: {}", e)))?;
+    
+    let marker_counts_by_type: std::collections::BTreeMap<String, i64> = sqlx::query_as(
+        "SELECT marker_type, count(*) FROM marker_events WHERE session_id = ? GROUP BY marker_type",
+    )
+    .bind(&session_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to summarize markers: {}", e)))?
+    .into_iter()
+    .collect();
+    
+    let (avg_rapport, min_rapport, max_rapport): (Option<f64>, Option<f64>, Option<f64>) = sqlx::query_as(
+        "SELECT AVG(value), MIN(value), MAX(value) FROM rapport_indicators WHERE session_id = ?",
+    )
+    .bind(&session_id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to summarize rapport: {}", e)))?;
+    
+    Ok(SessionSummary {
+        session_id,
+        // Imported transcripts without audio still have a length
+        duration: session.duration.or(transcript_end).unwrap_or(0.0),
+        segment_count,
+        word_count,
+        speaker_count,
+        marker_counts_by_type,
+        avg_rapport,
+        rapport_range: min_rapport.zip(max_rapport),
+    })
+}
+
+/// Kinds of conversation a session can record
+pub const SESSION_TYPES: &[&str] = &["therapy", "legal", "business"];
+
+/// Pipeline states a session can be in
+pub const SESSION_STATUSES: &[&str] = &[
+    "created", "recording", "transcribing", "analyzing", "completed", "cancelled", "interrupted",
+];
+
+/// Fields `update_session` changes; unset fields are left as they are
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionPatch {
+    pub name: Option<String>,
+    pub session_type: Option<String>,
+    pub client_reference: Option<String>,
+    pub status: Option<String>,
+}
+
+impl SessionPatch {
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.name.as_deref().is_some_and(|name| name.trim().is_empty()) {
+            return Err(AppError::Validation("Session name must not be empty".to_string()));
+        }
+        if let Some(session_type) = self.session_type.as_deref().filter(|t| !SESSION_TYPES.contains(t)) {
+            return Err(AppError::Validation(format!(
+                "Unknown session type: {} (expected one of: {})",
+                session_type,
+                SESSION_TYPES.join(", ")
+            )));
+        }
+        if let Some(status) = self.status.as_deref().filter(|s| !SESSION_STATUSES.contains(s)) {
+            return Err(AppError::Validation(format!(
+                "Unknown session status: {} (expected one of: {})",
+                status,
+                SESSION_STATUSES.join(", ")
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[tauri::command]
+pub async fn update_session(
+    state: State<'_, AppState>,
+    session_id: String,
+    patch: SessionPatch
+) -> Result<ConversationSession, AppError> {
+    log::info!("Updating session: {}", session_id);
+    
+    patch.validate()?;
+    
+    let mut builder = sqlx::QueryBuilder::new("UPDATE conversation_sessions SET updated_at = ");
+    builder.push_bind(Utc::now().to_rfc3339());
+    if let Some(name) = &patch.name {
+        builder.push(", name = ").push_bind(name.trim().to_string());
+    }
+    if let Some(session_type) = &patch.session_type {
+        builder.push(", session_type = ").push_bind(session_type.clone());
+    }
+    if let Some(client_reference) = &patch.client_reference {
+        // An empty reference clears it
+        let client_reference = Some(client_reference.trim()).filter(|r| !r.is_empty()).map(str::to_string);
+        builder.push(", client_reference = ").push_bind(client_reference);
+    }
+    if let Some(status) = &patch.status {
+        builder.push(", status = ").push_bind(status.clone());
+    }
+    builder.push(" WHERE id = ").push_bind(session_id.clone());
+    
+    let updated = builder
+        .build()
+        .execute(&state.db)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to update session: {}", e)))?;
+    if updated.rows_affected() == 0 {
+        return Err(AppError::NotFound(SESSION_NOT_FOUND.to_string()));
+    }
+    
+    fetch_session(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load session: {}", e)))?
+        .ok_or_else(|| AppError::NotFound(SESSION_NOT_FOUND.to_string()))
+}
+
+/// Session statuses during which a session's data is still being written
+pub const BUSY_SESSION_STATUSES: &[&str] = &["recording", "transcribing"];
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionDeletion {
+    pub rows_removed: u64,
+    pub files_removed: u32,
+}
+
+#[tauri::command]
+pub async fn delete_session(
+    state: State<'_, AppState>,
+    session_id: String
+) -> Result<SessionDeletion, AppError> {
+    log::info!("Deleting session: {}", session_id);
+    
+    let session = fetch_session(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load session: {}", e)))?
+        .ok_or_else(|| AppError::NotFound(SESSION_NOT_FOUND.to_string()))?;
+    if BUSY_SESSION_STATUSES.contains(&session.status.as_str()) {
+        return Err(AppError::Validation(format!("Cannot delete a session while it is {}", session.status)));
+    }
+    
+    let mut tx = state.db.begin().await.map_err(AppError::from)?;
+    let rows_removed = delete_session_rows(&mut tx, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to delete session: {}", e)))?;
+    tx.commit().await.map_err(|e| AppError::Database(format!("Failed to delete session: {}", e)))?;
+    
+    // Audio goes only once the rows are gone, so a failed delete keeps it playable
+    let mut files_removed = 0;
+    if let Some(path) = &session.file_path {
+        match tokio::fs::remove_file(path).await {
+            Ok(()) => files_removed += 1,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => log::warn!("Failed to remove audio of deleted session {}: {}", session_id, e),
+        }
+    }
+    
+    Ok(SessionDeletion { rows_removed, files_removed })
+}
+
+#[tauri::command]
+pub async fn add_session_tag(
+    state: State<'_, AppState>,
+    session_id: String,
+    tag: String
+) -> Result<String, AppError> {
+    log::info!("Tagging session: {}", session_id);
+    
+    let tag = normalize_tag(&tag).ok_or_else(|| AppError::Validation("Tag must not be empty".to_string()))?;
+    if fetch_session(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load session: {}", e)))?
+        .is_none()
+    {
+        return Err(AppError::NotFound(SESSION_NOT_FOUND.to_string()));
+    }
+    
+    let mut tx = state.db.begin().await.map_err(AppError::from)?;
+    sqlx::query("INSERT INTO tags (name) VALUES (?) ON CONFLICT(name) DO NOTHING")
+        .bind(&tag)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to create tag: {}", e)))?;
+    sqlx::query(
+        "INSERT INTO session_tags (session_id, tag_id) \
+         SELECT ?, id FROM tags WHERE name = ? \
+         ON CONFLICT(session_id, tag_id) DO NOTHING",
+    )
+    .bind(&session_id)
+    .bind(&tag)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to tag session: {}", e)))?;
+    tx.commit().await.map_err(|e| AppError::Database(format!("Failed to tag session: {}", e)))?;
+    
+    Ok(tag)
+}
+
+#[tauri::command]
+pub async fn remove_session_tag(
+    state: State<'_, AppState>,
+    session_id: String,
+    tag: String
+) -> Result<bool, AppError> {
+    log::info!("Removing tag from session: {}", session_id);
+    
+    let Some(tag) = normalize_tag(&tag) else {
+        return Ok(false);
+    };
+    let removed = sqlx::query(
+        "DELETE FROM session_tags WHERE session_id = ? \
+         AND tag_id = (SELECT id FROM tags WHERE name = ?)",
+    )
+    .bind(&session_id)
+    .bind(&tag)
+    .execute(&state.db)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to remove tag: {}", e)))?
+    .rows_affected();
+    
+    Ok(removed > 0)
+}
+
+/// All tags in use with the number of sessions carrying each, by name
+#[tauri::command]
+pub async fn list_tags(state: State<'_, AppState>) -> Result<Vec<(String, u32)>, AppError> {
+    log::info!("Listing session tags");
+    
+    let rows = sqlx::query(
+        "SELECT t.name, COUNT(st.session_id) AS session_count FROM tags t \
+         LEFT JOIN session_tags st ON st.tag_id = t.id GROUP BY t.id ORDER BY t.name",
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to list tags: {}", e)))?;
+    
+    rows.iter()
+        .map(|row| Ok((row.try_get("name")?, row.try_get::<i64, _>("session_count")? as u32)))
+        .collect::<Result<Vec<_>, sqlx::Error>>()
+        .map_err(|e| AppError::Database(format!("Failed to read tag: {}", e)))
+}
+
+#[tauri::command]
+pub async fn import_plaintext_transcript(
+    state: State<'_, AppState>,
+    text: String,
+    name: String,
+    session_type: String
+) -> Result<ConversationSession, AppError> {
+    log::info!("Importing plain-text transcript as a session of type: {}", session_type);
+    
+    let segments = crate::transcription_commands::parse_plaintext_transcript(&text);
+    if segments.is_empty() {
+        return Err(AppError::Validation("No transcript text found to import".to_string()));
+    }
+    
+    let now = Utc::now();
+    let session = ConversationSession {
+        id: uuid::Uuid::new_v4().to_string(),
+        name,
+        session_type,
         client_reference: None,
-        created_at: Utc::now(),
-        updated_at: Utc::now(),
+        created_at: now,
+        updated_at: now,
         status: "completed".to_string(),
-        duration: Some(1800.0),
-        file_path: Some("/tmp/loaded_session.wav".to_string()),
-    })
-}
\ No newline at end of file
+        duration: segments.iter().map(|segment| segment.end_time).reduce(f64::max),
+        file_path: None,
+    };
+    
+    let mut tx = state.db.begin().await.map_err(AppError::from)?;
+    insert_session(&mut tx, &session)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to create session: {}", e)))?;
+    write_segments(&mut tx, &session.id, &segments)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to save transcript: {}", e)))?;
+    tx.commit().await.map_err(|e| AppError::Database(format!("Failed to import transcript: {}", e)))?;
+    
+    log::info!("Imported {} segments into session: {}", segments.len(), session.id);
+    Ok(session)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{insert, memory_pool, segment, session, temp_dir};
+
+    fn transcript() -> Vec<crate::transcription_commands::SpeakerSegment> {
+        vec![
+            segment("SPEAKER_01", 4.0, 7.5, "Mostly about sleep problems lately."),
+            segment("SPEAKER_00", 0.0, 3.5, "So, um, welcome back to the practice."),
+        ]
+    }
+
+    async fn session_name(pool: &SqlitePool, session_id: &str) -> String {
+        fetch_session(pool, session_id).await.unwrap().unwrap().name
+    }
+
+    #[tokio::test]
+    async fn blank_session_is_named_from_its_transcript() {
+        let pool = memory_pool().await;
+        insert(&pool, &session("s1", "  ")).await;
+
+        save_segments(&pool, "s1", &transcript(), true).await.unwrap();
+
+        assert_eq!(session_name(&pool, "s1").await, "Welcome back practice mostly about");
+    }
+
+    #[tokio::test]
+    async fn sessions_keep_their_name_with_auto_naming_off() {
+        let pool = memory_pool().await;
+        insert(&pool, &session("s1", "")).await;
+
+        save_segments(&pool, "s1", &transcript(), false).await.unwrap();
+
+        assert_eq!(session_name(&pool, "s1").await, "");
+        assert_eq!(load_segments(&pool, "s1").await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn named_sessions_are_not_renamed() {
+        let pool = memory_pool().await;
+        insert(&pool, &session("s1", "Intake Smith")).await;
+
+        save_segments(&pool, "s1", &transcript(), true).await.unwrap();
+
+        assert_eq!(session_name(&pool, "s1").await, "Intake Smith");
+    }
+
+    #[tokio::test]
+    async fn database_opens_only_with_its_passphrase() {
+        let dir = temp_dir();
+        let path = dir.join("transrapport.db");
+        let passphrase = || DatabaseKey::Passphrase("it's four unrelated words".to_string());
+
+        // First unlock keys the new file
+        initialize_database(&path, &passphrase()).await.unwrap().close().await;
+
+        let wrong = initialize_database(&path, &DatabaseKey::Passphrase("its four unrelated words".to_string())).await;
+        assert!(matches!(wrong, Err(AppError::WrongPassphrase(_))));
+        initialize_database(&path, &passphrase()).await.unwrap().close().await;
+        assert_eq!(check_database_file(&path, &passphrase()).await, Ok(0));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    async fn tag(pool: &SqlitePool, session_id: &str, tag: &str) {
+        sqlx::query("INSERT INTO tags (name) VALUES (?) ON CONFLICT(name) DO NOTHING")
+            .bind(tag)
+            .execute(pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO session_tags (session_id, tag_id) SELECT ?, id FROM tags WHERE name = ?")
+            .bind(session_id)
+            .bind(tag)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    /// Four sessions; "c" and "d" were created at the same instant
+    async fn session_corpus() -> SqlitePool {
+        use chrono::TimeZone;
+
+        let pool = memory_pool().await;
+        let created = |month| Utc.with_ymd_and_hms(2026, month, 10, 9, 0, 0).unwrap();
+        let mut a = session("a", "Intake Smith");
+        a.created_at = created(1);
+        let mut b = session("b", "Follow-up");
+        b.created_at = created(2);
+        b.status = "recording".to_string();
+        let mut c = session("c", "Review");
+        c.created_at = created(3);
+        c.client_reference = Some("smith-family".to_string());
+        let mut d = session("d", "Closing");
+        d.created_at = created(3);
+        for session in [&a, &b, &c, &d] {
+            insert(&pool, session).await;
+        }
+        tag(&pool, "a", "grief").await;
+        tag(&pool, "b", "grief").await;
+        tag(&pool, "b", "sleep").await;
+        pool
+    }
+
+    async fn found_ids(
+        pool: &SqlitePool,
+        filters: SessionFilters,
+        limit: Option<u32>,
+        offset: Option<u32>
+    ) -> Vec<String> {
+        find_sessions(pool, &filters, limit, offset)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|session| session.id)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn sessions_list_newest_created_first_with_id_breaking_ties() {
+        let pool = session_corpus().await;
+
+        assert_eq!(found_ids(&pool, SessionFilters::default(), None, None).await, ["c", "d", "b", "a"]);
+        assert_eq!(found_ids(&pool, SessionFilters::default(), Some(2), Some(1)).await, ["d", "b"]);
+        assert_eq!(found_ids(&pool, SessionFilters::default(), None, Some(3)).await, ["a"]);
+    }
+
+    #[tokio::test]
+    async fn session_filters_narrow_the_list() {
+        use chrono::TimeZone;
+
+        let pool = session_corpus().await;
+        let status = SessionFilters { status: Some("recording".to_string()), ..Default::default() };
+        assert_eq!(found_ids(&pool, status, None, None).await, ["b"]);
+
+        // Matches the name of one session and the client reference of another
+        let query = SessionFilters { query: Some(" SMITH ".to_string()), ..Default::default() };
+        assert_eq!(found_ids(&pool, query, None, None).await, ["c", "a"]);
+        let wildcard = SessionFilters { query: Some("%".to_string()), ..Default::default() };
+        assert!(found_ids(&pool, wildcard, None, None).await.is_empty());
+
+        let february = SessionFilters {
+            created_after: Some(Utc.with_ymd_and_hms(2026, 2, 1, 0, 0, 0).unwrap()),
+            created_before: Some(Utc.with_ymd_and_hms(2026, 3, 10, 9, 0, 0).unwrap()),
+            ..Default::default()
+        };
+        assert_eq!(found_ids(&pool, february, None, None).await, ["b"]);
+
+        let grief = SessionFilters { tags: vec!["grief".to_string()], ..Default::default() };
+        assert_eq!(found_ids(&pool, grief, None, None).await, ["b", "a"]);
+        // Every tag must be present; tags are normalized before matching
+        let both = SessionFilters {
+            tags: vec![" Grief ".to_string(), "sleep".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(found_ids(&pool, both, None, None).await, ["b"]);
+
+        let combined = SessionFilters {
+            status: Some("completed".to_string()),
+            tags: vec!["grief".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(found_ids(&pool, combined, None, None).await, ["a"]);
+    }
+}
@@ -0,0 +1,107 @@
+//! Fixtures shared by the unit tests
+
+use chrono::Utc;
+use sqlx::sqlite::SqlitePoolOptions;
+use sqlx::SqlitePool;
+
+use crate::analysis_commands::{MarkerEvent, MarkerType};
+use crate::storage_commands::ConversationSession;
+use crate::transcription_commands::SpeakerSegment;
+
+/// Empty in-memory database with every migration applied. It has a single
+/// connection that never expires, since each `:memory:` connection is its own database.
+pub async fn memory_pool() -> SqlitePool {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .idle_timeout(None)
+        .max_lifetime(None)
+        .connect("sqlite::memory:")
+        .await
+        .expect("in-memory database opens");
+    sqlx::migrate!("./migrations").run(&pool).await.expect("migrations apply");
+    pool
+}
+
+/// A therapy session named `name`, created now
+pub fn session(id: &str, name: &str) -> ConversationSession {
+    let now = Utc::now();
+    ConversationSession {
+        id: id.to_string(),
+        name: name.to_string(),
+        session_type: "therapy".to_string(),
+        client_reference: None,
+        created_at: now,
+        updated_at: now,
+        status: "completed".to_string(),
+        duration: None,
+        file_path: None,
+    }
+}
+
+/// Insert `session` into the database
+pub async fn insert(pool: &SqlitePool, session: &ConversationSession) {
+    let mut conn = pool.acquire().await.expect("connection");
+    crate::storage_commands::insert_session(&mut conn, session)
+        .await
+        .expect("session inserts");
+}
+
+/// Fresh empty directory under the system temp dir, for tests that write files
+pub fn temp_dir() -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("transrapport-test-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).expect("temp dir is created");
+    dir
+}
+
+/// Write a 16-bit WAV of `secs` seconds of a quiet 440 Hz tone on every channel
+pub fn write_tone_wav(path: &std::path::Path, sample_rate: u32, channels: u16, secs: f64) {
+    let spec = hound::WavSpec {
+        channels,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let frames = (secs * sample_rate as f64) as usize;
+    let samples: Vec<f32> = (0..frames)
+        .flat_map(|frame| {
+            let t = frame as f32 / sample_rate as f32;
+            std::iter::repeat((t * 440.0 * std::f32::consts::TAU).sin() * 0.25).take(channels as usize)
+        })
+        .collect();
+    crate::audio_processing::write_wav(path, spec, &samples).expect("tone WAV is written");
+}
+
+/// An unsaved segment without word timings
+pub fn segment(speaker_id: &str, start_time: f64, end_time: f64, text: &str) -> SpeakerSegment {
+    SpeakerSegment {
+        id: None,
+        speaker_id: speaker_id.to_string(),
+        speaker_label: speaker_id.to_string(),
+        start_time,
+        end_time,
+        text: text.to_string(),
+        confidence: 0.9,
+        words: None,
+    }
+}
+
+/// A marker of `marker_type` covering `start_time..end_time`
+pub fn marker(
+    id: &str,
+    marker_type: MarkerType,
+    start_time: f64,
+    end_time: f64,
+    confidence: f64,
+    speaker: Option<&str>
+) -> MarkerEvent {
+    MarkerEvent {
+        id: id.to_string(),
+        marker_type,
+        start_time,
+        end_time,
+        confidence,
+        evidence: format!("evidence for {}", id),
+        explanation: format!("explanation of {}", id),
+        speaker: speaker.map(str::to_string),
+    }
+}
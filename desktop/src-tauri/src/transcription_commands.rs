@@ -1,64 +1,1630 @@
 use tauri::State;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
 
-#[derive(Debug, Serialize, Deserialize)]
+use crate::error::AppError;
+use crate::state::AppState;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TranscriptionProgress {
     pub session_id: String,
     pub progress: f64, // 0.0 to 1.0
-    pub current_stage: String,
+    pub current_stage: String, // "load model", "Downloading model", "transcribe", "align", "diarize", "completed", "failed", "timeout"
     pub estimated_remaining: Option<u64>, // seconds
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// A WhisperX run started in this process
+pub struct TranscriptionState {
+    started: Instant,
+    /// Length of the audio being transcribed, when its container declares it
+    audio_duration: Option<f64>,
+    progress: TranscriptionProgress,
+    /// Whether the process is still running; its kill switch is the
+    /// `CancellationManager` operation registered under the run id
+    running: bool,
+}
+
+/// Stage reported once a run has been cancelled; later output is ignored
+const CANCELLED_STAGE: &str = "cancelled";
+
+/// Stage of a run killed for exceeding its time limit
+pub const TIMEOUT_STAGE: &str = "timeout";
+
+/// Slowest pace, in seconds of processing per second of audio, an estimate may
+/// assume. Progress in the first seconds of a run is too coarse to extrapolate
+/// from and would otherwise predict hours.
+const MAX_REAL_TIME_FACTOR: f64 = 3.0;
+
+/// Seconds left at the pace so far, `None` until there is progress to go on.
+/// Capped at what the slowest plausible pace would need for the remaining audio.
+pub fn estimate_remaining_secs(elapsed_secs: f64, progress: f64, audio_duration: Option<f64>) -> Option<u64> {
+    if progress <= 0.0 {
+        return None;
+    }
+    let mut remaining = elapsed_secs * (1.0 - progress.min(1.0)) / progress;
+    if let Some(duration) = audio_duration.filter(|duration| *duration > 0.0) {
+        remaining = remaining.min(duration * (1.0 - progress.min(1.0)) * MAX_REAL_TIME_FACTOR);
+    }
+    Some(remaining.round() as u64)
+}
+
+/// Live progress of WhisperX runs keyed by session id, fed from their output; held in `AppState`
+#[derive(Default)]
+pub struct TranscriptionJobs(Mutex<HashMap<String, TranscriptionState>>);
+
+impl TranscriptionJobs {
+    /// Track a new run over `audio_duration` seconds of audio
+    pub fn start(&self, session_id: &str, audio_duration: Option<f64>) {
+        let progress = TranscriptionProgress {
+            session_id: session_id.to_string(),
+            progress: 0.0,
+            current_stage: crate::python_integration::WHISPERX_STAGES[0].0.to_string(),
+            estimated_remaining: None,
+        };
+        self.0.lock().unwrap().insert(
+            session_id.to_string(),
+            TranscriptionState { started: Instant::now(), audio_duration, progress, running: true },
+        );
+    }
+
+    /// Mark the run cancelled so output still buffered is ignored; false when no
+    /// process is running for the session. The caller signals the run's token.
+    pub fn cancel(&self, session_id: &str) -> bool {
+        let mut jobs = self.0.lock().unwrap();
+        let Some(job) = jobs.get_mut(session_id).filter(|job| job.running) else {
+            return false;
+        };
+        job.running = false;
+
+        job.progress.current_stage = CANCELLED_STAGE.to_string();
+        job.progress.estimated_remaining = Some(0);
+        true
+    }
+
+    pub fn set_stage(&self, session_id: &str, stage: &str) {
+        self.update(session_id, |progress| {
+            progress.current_stage = stage.to_string();
+            progress.progress = progress.progress.max(crate::python_integration::overall_progress(stage, 0.0));
+        });
+    }
+
+    pub fn set_stage_progress(&self, session_id: &str, stage_fraction: f64) {
+        self.update(session_id, |progress| {
+            let overall = crate::python_integration::overall_progress(&progress.current_stage, stage_fraction);
+            // Progress bars restart per file and stage; never report going backwards
+            progress.progress = progress.progress.max(overall);
+        });
+    }
+
+    /// Mark a run as ended with `stage` "completed", "failed" or "timeout"
+    pub fn finish(&self, session_id: &str, stage: &str) {
+        if let Some(job) = self.0.lock().unwrap().get_mut(session_id) {
+            job.running = false;
+            if job.progress.current_stage == CANCELLED_STAGE {
+                return;
+            }
+            if stage == "completed" {
+                job.progress.progress = 1.0;
+            }
+            job.progress.current_stage = stage.to_string();
+            job.progress.estimated_remaining = Some(0);
+        }
+    }
+
+    /// Succeeds only once the run for `session_id` has finished successfully, so
+    /// its output is complete
+    pub fn ensure_completed(&self, session_id: &str) -> Result<(), AppError> {
+        let stage = self.progress(session_id).map(|progress| progress.current_stage);
+        match stage.as_deref() {
+            Some("completed") => Ok(()),
+            Some(CANCELLED_STAGE) => Err(AppError::Cancelled(format!(
+                "Transcription for session {} was cancelled",
+                session_id
+            ))),
+            Some(TIMEOUT_STAGE) => Err(AppError::Timeout(format!(
+                "Transcription for session {} timed out",
+                session_id
+            ))),
+            Some(stage) => Err(AppError::NotFound(format!(
+                "Transcription for session {} has not completed (stage: {})",
+                session_id, stage
+            ))),
+            None => Err(AppError::NotFound(format!("No transcription found for session: {}", session_id))),
+        }
+    }
+
+    pub fn progress(&self, session_id: &str) -> Option<TranscriptionProgress> {
+        self.0
+            .lock()
+            .unwrap()
+            .get(session_id)
+            .map(|job| job.progress.clone())
+    }
+
+    fn update(&self, session_id: &str, apply: impl FnOnce(&mut TranscriptionProgress)) {
+        let mut jobs = self.0.lock().unwrap();
+        let Some(job) = jobs.get_mut(session_id) else {
+            return;
+        };
+        // Output still buffered when the process was killed must not revive the run
+        if job.progress.current_stage == CANCELLED_STAGE {
+            return;
+        }
+        apply(&mut job.progress);
+
+        job.progress.estimated_remaining =
+            estimate_remaining_secs(job.started.elapsed().as_secs_f64(), job.progress.progress, job.audio_duration);
+    }
+}
+
+/// A file waiting for or going through transcription via the queue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedTranscription {
+    pub queue_id: String,
+    pub audio_file_path: String,
+    pub language: Option<String>,
+    pub model_size: Option<String>,
+    pub status: String, // "pending", "running", "done", "failed", "cancelled"
+    /// Transcription session, once the run has started
+    pub session_id: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Files queued for transcription in enqueue order; held in `AppState`. Every
+/// change is emitted as a `transcription-queue` event carrying all entries.
+/// A pending entry waits for a Python process slot as a `CancellationManager`
+/// operation registered under its queue id.
+#[derive(Default)]
+pub struct TranscriptionQueue(Mutex<Vec<QueuedTranscription>>);
+
+impl TranscriptionQueue {
+    pub fn entries(&self) -> Vec<QueuedTranscription> {
+        self.0.lock().unwrap().clone()
+    }
+
+    fn push(&self, app: &tauri::AppHandle, entry: QueuedTranscription) {
+        self.change(app, |entries| entries.push(entry));
+    }
+
+    /// Apply `apply` to an entry; false when it has been dequeued meanwhile
+    fn update(&self, app: &tauri::AppHandle, queue_id: &str, apply: impl FnOnce(&mut QueuedTranscription)) -> bool {
+        let mut found = false;
+        self.change(app, |entries| {
+            if let Some(entry) = entries.iter_mut().find(|entry| entry.queue_id == queue_id) {
+                apply(entry);
+                found = true;
+            }
+        });
+        found
+    }
+
+    /// Drop a pending entry and stop its wait for a process slot; running and
+    /// finished entries stay
+    fn remove_pending(
+        &self,
+        app: &tauri::AppHandle,
+        cancellations: &crate::cancellation::CancellationManager,
+        queue_id: &str
+    ) -> Result<(), AppError> {
+        let mut result = Ok(());
+        self.change(app, |entries| {
+            match entries.iter().position(|entry| entry.queue_id == queue_id) {
+                None => result = Err(AppError::NotFound(format!("No queued transcription: {}", queue_id))),
+                Some(index) if entries[index].status != "pending" => {
+                    result = Err(AppError::Validation(format!(
+                        "Transcription {} is already {}",
+                        queue_id, entries[index].status
+                    )))
+                }
+                Some(index) => {
+                    entries.remove(index);
+                    cancellations.cancel(queue_id);
+                }
+            }
+        });
+        result
+    }
+
+    fn change(&self, app: &tauri::AppHandle, apply: impl FnOnce(&mut Vec<QueuedTranscription>)) {
+        use tauri::Emitter;
+
+        let snapshot = {
+            let mut entries = self.0.lock().unwrap();
+            apply(&mut entries);
+            entries.clone()
+        };
+        if let Err(e) = app.emit("transcription-queue", &snapshot) {
+            log::warn!("Failed to emit transcription queue: {}", e);
+        }
+    }
+}
+
+/// Wait for a process slot, run WhisperX and record how the run ended
+async fn run_queued_transcription(
+    app: tauri::AppHandle,
+    entry: QueuedTranscription,
+    dequeued: crate::cancellation::CancellationToken
+) {
+    use tauri::Manager;
+
+    let state = app.state::<AppState>();
+    let cancellations = app.state::<crate::cancellation::CancellationManager>();
+    let queue = &state.transcription_queue;
+    let session_id = uuid::Uuid::new_v4().to_string();
+    let timeout = app.state::<crate::settings::SettingsStore>().get().transcription_timeout();
+
+    // Starting blocks on the Python semaphore, which is where a pending entry waits
+    let started = tokio::select! {
+        started = crate::python_integration::start_whisperx_transcription(
+            app.clone(),
+            &session_id,
+            &entry.audio_file_path,
+            entry.language.as_deref(),
+            entry.model_size.as_deref(),
+            None,
+            Some(timeout),
+        ) => Some(started),
+        _ = dequeued.cancelled() => None,
+    };
+    // The entry is no longer pending; a running run is cancelled by its own id
+    cancellations.finish(&entry.queue_id);
+    let Some(started) = started else {
+        log::info!("Dequeued transcription {} before it started", entry.queue_id);
+        return;
+    };
+
+    let run = match started {
+        Ok(run) => run,
+        Err(e) => {
+            log::error!("Queued transcription {} failed to start: {}", entry.queue_id, e);
+            queue.update(&app, &entry.queue_id, |queued| {
+                queued.status = "failed".to_string();
+                queued.error = Some(e.to_string());
+            });
+            return;
+        }
+    };
+
+    let tracked = queue.update(&app, &entry.queue_id, |queued| {
+        queued.status = "running".to_string();
+        queued.session_id = Some(session_id.clone());
+    });
+    if !tracked {
+        // Dequeued while the process was being launched
+        cancel_run(&state, &cancellations, &session_id);
+    }
+
+    let error = match run.await {
+        Ok(outcome) => outcome.err(),
+        Err(e) => {
+            log::warn!("WhisperX task for queued transcription {} ended abnormally: {}", entry.queue_id, e);
+            None
+        }
+    };
+
+    let stage = state.transcriptions.progress(&session_id).map(|progress| progress.current_stage);
+    queue.update(&app, &entry.queue_id, |queued| {
+        queued.status = match stage.as_deref() {
+            Some("completed") => "done",
+            Some(CANCELLED_STAGE) => "cancelled",
+            _ => "failed",
+        }
+        .to_string();
+        if queued.status == "failed" {
+            queued.error = error.map(|e| e.to_string());
+        }
+    });
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WordTiming {
+    pub text: String,
+    pub start: f64,
+    pub end: f64,
+    pub confidence: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SpeakerSegment {
+    /// Storage id; `None` for segments that haven't been saved yet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
     pub speaker_id: String,
     pub speaker_label: String,
     pub start_time: f64,
     pub end_time: f64,
     pub text: String,
     pub confidence: f64,
+    /// Word-level alignment; absent for transcripts stored before it existed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub words: Option<Vec<WordTiming>>,
+}
+
+/// Check that a segment's times are non-negative numbers in order
+pub fn validate_segment_times(segment: &SpeakerSegment) -> Result<(), String> {
+    if !segment.start_time.is_finite() || !segment.end_time.is_finite() || segment.start_time < 0.0 {
+        return Err("times must be non-negative numbers".to_string());
+    }
+    if segment.start_time > segment.end_time {
+        return Err("start_time is after end_time".to_string());
+    }
+    Ok(())
+}
+
+/// Word tokens of a segment with `(start, end)` times, taken from the word
+/// alignment when present. Otherwise the segment span is shared out in
+/// proportion to each word's length.
+pub fn word_spans(segment: &SpeakerSegment) -> Vec<(String, f64, f64)> {
+    if let Some(words) = segment.words.as_ref().filter(|words| !words.is_empty()) {
+        return words
+            .iter()
+            .map(|word| (word.text.clone(), word.start, word.end))
+            .collect();
+    }
+
+    let words: Vec<&str> = segment.text.split_whitespace().collect();
+    let total_chars: usize = words.iter().map(|word| word.chars().count()).sum();
+    if words.is_empty() || total_chars == 0 {
+        return Vec::new();
+    }
+
+    let span = (segment.end_time - segment.start_time).max(0.0);
+    let mut cursor = segment.start_time;
+    words
+        .iter()
+        .map(|word| {
+            let share = span * word.chars().count() as f64 / total_chars as f64;
+            let start = cursor;
+            cursor += share;
+            (word.to_string(), start, cursor)
+        })
+        .collect()
+}
+
+/// Word-shingle similarity to a neighbouring segment above which a segment looks repeated
+pub const DEFAULT_REPEAT_SIMILARITY: f64 = 0.8;
+
+/// Fraction of a segment lying in detected silence above which it looks invented
+pub const DEFAULT_SILENCE_OVERLAP: f64 = 0.8;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SuspectSegment {
+    pub segment_index: usize,
+    pub speaker_id: String,
+    pub start_time: f64,
+    pub end_time: f64,
+    pub text: String,
+    pub reasons: Vec<String>, // "repeat", "silence"
+    pub repeat_similarity: f64,
+    pub silence_overlap: Option<f64>,
+}
+
+/// Flag segments that repeat a neighbour or mostly fall into silence.
+/// `silence` is `None` when no audio is available, which disables the silence check.
+pub fn find_suspect_segments(
+    segments: &[SpeakerSegment],
+    silence: Option<&[(f64, f64)]>,
+    repeat_threshold: f64,
+    silence_threshold: f64
+) -> Vec<SuspectSegment> {
+    use crate::duplicate_commands::{jaccard_similarity, transcript_shingles};
+
+    let shingles: Vec<_> = segments.iter().map(|s| transcript_shingles(&s.text)).collect();
+
+    segments
+        .iter()
+        .enumerate()
+        .filter_map(|(index, segment)| {
+            let previous = index
+                .checked_sub(1)
+                .map(|prev| jaccard_similarity(&shingles[index], &shingles[prev]))
+                .unwrap_or(0.0);
+            let next = shingles
+                .get(index + 1)
+                .map(|following| jaccard_similarity(&shingles[index], following))
+                .unwrap_or(0.0);
+            let repeat_similarity = previous.max(next);
+
+            let silence_overlap = silence.map(|regions| {
+                crate::audio_processing::overlap_fraction(segment.start_time, segment.end_time, regions)
+            });
+
+            let mut reasons = Vec::new();
+            if repeat_similarity >= repeat_threshold {
+                reasons.push("repeat".to_string());
+            }
+            if silence_overlap.map(|overlap| overlap >= silence_threshold).unwrap_or(false) {
+                reasons.push("silence".to_string());
+            }
+            if reasons.is_empty() {
+                return None;
+            }
+
+            Some(SuspectSegment {
+                segment_index: index,
+                speaker_id: segment.speaker_id.clone(),
+                start_time: segment.start_time,
+                end_time: segment.end_time,
+                text: segment.text.clone(),
+                reasons,
+                repeat_similarity,
+                silence_overlap,
+            })
+        })
+        .collect()
+}
+
+/// Speaking pace used to estimate how long the last pasted segment lasts
+const PLAINTEXT_WORDS_PER_SEC: f64 = 2.5;
+
+/// Longest `Name:` prefix accepted as a speaker name in pasted transcripts
+const MAX_SPEAKER_NAME_CHARS: usize = 40;
+
+/// Parse a leading `[mm:ss]`, `(hh:mm:ss)` or bare `mm:ss` timestamp, returning
+/// the time in seconds and the rest of the line
+fn parse_leading_timestamp(line: &str) -> Option<(f64, &str)> {
+    let (inner, rest) = match line.chars().next()? {
+        '[' => line[1..].split_once(']')?,
+        '(' => line[1..].split_once(')')?,
+        _ => {
+            let end = line
+                .find(|c: char| !(c.is_ascii_digit() || c == ':' || c == '.'))
+                .unwrap_or(line.len());
+            (&line[..end], &line[end..])
+        }
+    };
+
+    let parts: Vec<&str> = inner.trim().split(':').collect();
+    if !(2..=3).contains(&parts.len()) {
+        return None;
+    }
+    let mut seconds = 0.0;
+    for (index, part) in parts.iter().enumerate() {
+        let is_last = index == parts.len() - 1;
+        let valid = !part.is_empty()
+            && part.chars().all(|c| c.is_ascii_digit() || (is_last && c == '.'));
+        if !valid {
+            return None;
+        }
+        seconds = seconds * 60.0 + part.parse::<f64>().ok()?;
+    }
+
+    Some((seconds, rest.trim_start_matches([' ', '\t', '-', '–'])))
+}
+
+/// Split a leading `Name:` speaker prefix off a line
+fn parse_speaker_prefix(line: &str) -> Option<(&str, &str)> {
+    let (name, text) = line.split_once(':')?;
+    let name = name.trim();
+    let plausible = !name.is_empty()
+        && name.chars().count() <= MAX_SPEAKER_NAME_CHARS
+        && name.split_whitespace().count() <= 4
+        && name.chars().next().map(|c| c.is_alphabetic()).unwrap_or(false)
+        && !name.contains(['.', '?', '!', ',']);
+    plausible.then(|| (name, text.trim()))
+}
+
+/// Heuristically turn a pasted transcript into segments. Timestamped lines start
+/// a segment ending where the next one starts; other lines join the previous one.
+pub fn parse_plaintext_transcript(text: &str) -> Vec<SpeakerSegment> {
+    let mut speaker_ids: Vec<String> = Vec::new();
+    let mut segments: Vec<SpeakerSegment> = Vec::new();
+
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let Some((start_time, rest)) = parse_leading_timestamp(line) else {
+            match segments.last_mut() {
+                Some(previous) => {
+                    if !previous.text.is_empty() {
+                        previous.text.push(' ');
+                    }
+                    previous.text.push_str(line);
+                }
+                None => segments.push(SpeakerSegment {
+                    id: None,
+                    speaker_id: "SPEAKER_UNKNOWN".to_string(),
+                    speaker_label: "Unknown".to_string(),
+                    start_time: 0.0,
+                    end_time: 0.0,
+                    text: line.to_string(),
+                    confidence: 1.0,
+                    words: None,
+                }),
+            }
+            continue;
+        };
+
+        let (speaker_id, speaker_label, text) = match parse_speaker_prefix(rest) {
+            Some((name, text)) => {
+                let index = match speaker_ids.iter().position(|known| known == name) {
+                    Some(index) => index,
+                    None => {
+                        speaker_ids.push(name.to_string());
+                        speaker_ids.len() - 1
+                    }
+                };
+                (format!("SPEAKER_{:02}", index), name.to_string(), text)
+            }
+            // Without a name the previous speaker is most likely still talking
+            None => match segments.last() {
+                Some(previous) => (previous.speaker_id.clone(), previous.speaker_label.clone(), rest),
+                None => ("SPEAKER_UNKNOWN".to_string(), "Unknown".to_string(), rest),
+            },
+        };
+
+        segments.push(SpeakerSegment {
+            id: None,
+            speaker_id,
+            speaker_label,
+            start_time,
+            end_time: start_time,
+            text: text.to_string(),
+            confidence: 1.0,
+            words: None,
+        });
+    }
+
+    let starts: Vec<f64> = segments.iter().map(|segment| segment.start_time).collect();
+    for (index, segment) in segments.iter_mut().enumerate() {
+        segment.end_time = match starts.get(index + 1) {
+            Some(next) if *next > segment.start_time => *next,
+            _ => {
+                let words = segment.text.split_whitespace().count() as f64;
+                segment.start_time + (words / PLAINTEXT_WORDS_PER_SEC).max(1.0)
+            }
+        };
+    }
+
+    segments
+}
+
+#[derive(Debug, Deserialize)]
+struct WhisperxOutput {
+    segments: Vec<WhisperxSegment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WhisperxSegment {
+    start: f64,
+    end: f64,
+    text: String,
+    speaker: Option<String>,
+    #[serde(default)]
+    words: Vec<WhisperxWord>,
+    avg_logprob: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WhisperxWord {
+    word: String,
+    start: Option<f64>,
+    end: Option<f64>,
+    score: Option<f64>,
+}
+
+/// Map WhisperX JSON output to segments. Diarization speakers become `SPEAKER_NN`
+/// labelled "Speaker N" in order of first appearance; without diarization everything
+/// is attributed to one speaker. Confidence is the mean aligned word score, falling
+/// back to the segment's average token probability, else 0.0. Words WhisperX
+/// couldn't align (e.g. numerals) get a zero-length timing after the previous word.
+pub fn parse_whisperx_output(json: &str) -> Result<Vec<SpeakerSegment>, String> {
+    let output: WhisperxOutput = serde_json::from_str(json)
+        .map_err(|e| format!("Invalid WhisperX output: {}", e))?;
+
+    let mut speakers: Vec<String> = Vec::new();
+    Ok(output
+        .segments
+        .into_iter()
+        .map(|segment| {
+            let raw_speaker = segment.speaker.unwrap_or_default();
+            let index = match speakers.iter().position(|known| *known == raw_speaker) {
+                Some(index) => index,
+                None => {
+                    speakers.push(raw_speaker);
+                    speakers.len() - 1
+                }
+            };
+
+            let scores: Vec<f64> = segment.words.iter().filter_map(|word| word.score).collect();
+            let confidence = if !scores.is_empty() {
+                scores.iter().sum::<f64>() / scores.len() as f64
+            } else {
+                segment.avg_logprob.map(|logprob| logprob.exp().clamp(0.0, 1.0)).unwrap_or(0.0)
+            };
+
+            let mut cursor = segment.start;
+            let words: Vec<WordTiming> = segment
+                .words
+                .iter()
+                .map(|word| {
+                    let start = word.start.unwrap_or(cursor);
+                    let end = word.end.unwrap_or(start).max(start);
+                    cursor = end;
+                    WordTiming {
+                        text: word.word.trim().to_string(),
+                        start,
+                        end,
+                        confidence: word.score.unwrap_or(confidence),
+                    }
+                })
+                .collect();
+
+            SpeakerSegment {
+                id: None,
+                speaker_id: format!("SPEAKER_{:02}", index),
+                speaker_label: format!("Speaker {}", index + 1),
+                start_time: segment.start,
+                end_time: segment.end,
+                text: segment.text.trim().to_string(),
+                confidence,
+                words: (!words.is_empty()).then_some(words),
+            }
+        })
+        .collect())
+}
+
+/// Temporary prefix while renaming speakers, so swapped ids never collide mid-update
+const SPEAKER_RENAME_PREFIX: &str = "renaming:";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SpeakerIdMapping {
+    pub speaker_id: String,
+    pub original_speaker_id: String,
+}
+
+/// Map each speaker id to `SPEAKER_NN` numbered by first appearance.
+/// Simultaneous first appearances are ordered by their current id.
+pub fn first_appearance_ids(segments: &[SpeakerSegment]) -> Vec<(String, String)> {
+    let mut first_seen: Vec<(&str, f64)> = Vec::new();
+    for segment in segments {
+        match first_seen.iter_mut().find(|(id, _)| *id == segment.speaker_id) {
+            Some((_, start)) => *start = start.min(segment.start_time),
+            None => first_seen.push((segment.speaker_id.as_str(), segment.start_time)),
+        }
+    }
+    first_seen.sort_by(|a, b| a.1.total_cmp(&b.1).then_with(|| a.0.cmp(b.0)));
+
+    first_seen
+        .into_iter()
+        .enumerate()
+        .map(|(index, (id, _))| (id.to_string(), format!("SPEAKER_{:02}", index)))
+        .collect()
 }
 
 #[tauri::command]
 pub async fn start_transcription(
+    app: tauri::AppHandle,
+    settings: State<'_, crate::settings::SettingsStore>,
     audio_file_path: String,
     language: Option<String>,
-    model_size: Option<String>
-) -> Result<String, String> {
-    // TODO: Implement Whisper transcription start
+    model_size: Option<String>,
+    chunk_length_secs: Option<u32>
+) -> Result<String, AppError> {
+    let defaults = settings.get();
+    let timeout = defaults.transcription_timeout();
+    let language = language.or(defaults.default_language);
+    let model_size = model_size.or(defaults.default_model_size);
     log::info!("Starting transcription for: {} with language: {:?}", 
                audio_file_path, language);
     
+    // Reject bad model and chunk sizes before anything is launched
+    if let Some(model) = &model_size {
+        crate::python_integration::validate_model_size(model).map_err(AppError::Validation)?;
+    }
+    let chunk_length = crate::python_integration::resolve_chunk_length(chunk_length_secs)
+        .map_err(AppError::Validation)?;
+    log::info!("Using WhisperX chunk length: {}s", chunk_length);
+    
     let session_id = uuid::Uuid::new_v4().to_string();
     
-    // TODO: Start background transcription process with WhisperX
+    crate::python_integration::start_whisperx_transcription(
+        app,
+        &session_id,
+        &audio_file_path,
+        language.as_deref(),
+        model_size.as_deref(),
+        Some(chunk_length),
+        Some(timeout),
+    )
+    .await?;
+    
     Ok(session_id)
 }
 
+/// Queue a file for transcription; it starts once a Python process slot is free
 #[tauri::command]
-pub async fn get_transcription_progress(session_id: String) -> Result<TranscriptionProgress, String> {
-    // TODO: Implement transcription progress tracking
+pub async fn enqueue_transcription(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    settings: State<'_, crate::settings::SettingsStore>,
+    cancellations: State<'_, crate::cancellation::CancellationManager>,
+    audio_file_path: String,
+    language: Option<String>,
+    model_size: Option<String>
+) -> Result<String, AppError> {
+    log::info!("Queueing transcription for: {}", audio_file_path);
+    
+    let defaults = settings.get();
+    let language = language.or(defaults.default_language);
+    let model_size = model_size.or(defaults.default_model_size);
+    
+    if let Some(model) = &model_size {
+        crate::python_integration::validate_model_size(model).map_err(AppError::Validation)?;
+    }
+    if !std::path::Path::new(&audio_file_path).is_file() {
+        return Err(AppError::NotFound(format!("Audio file not found: {}", audio_file_path)));
+    }
+    
+    let entry = QueuedTranscription {
+        queue_id: uuid::Uuid::new_v4().to_string(),
+        audio_file_path,
+        language,
+        model_size,
+        status: "pending".to_string(),
+        session_id: None,
+        error: None,
+    };
+    let queue_id = entry.queue_id.clone();
+    let dequeued = cancellations.register_with_id(&queue_id);
+    state.transcription_queue.push(&app, entry.clone());
+    tauri::async_runtime::spawn(run_queued_transcription(app, entry, dequeued));
+    
+    Ok(queue_id)
+}
+
+#[tauri::command]
+pub async fn get_transcription_queue(state: State<'_, AppState>) -> Result<Vec<QueuedTranscription>, AppError> {
+    Ok(state.transcription_queue.entries())
+}
+
+/// Remove a pending entry from the queue; running jobs are stopped with `cancel_transcription`
+#[tauri::command]
+pub async fn dequeue_transcription(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    cancellations: State<'_, crate::cancellation::CancellationManager>,
+    queue_id: String
+) -> Result<(), AppError> {
+    log::info!("Dequeueing transcription: {}", queue_id);
+    
+    state.transcription_queue.remove_pending(&app, &cancellations, &queue_id)
+}
+
+/// Probe script printing the detected language of an audio file as JSON
+const LANGUAGE_DETECTION_SCRIPT: &str = "src/lib/transcription/language_detection_cli.py";
+
+/// Audio fed to the probe; Whisper decides the language from one 30 s window
+const LANGUAGE_PROBE_SECS: f64 = 30.0;
+
+/// Model load dominates the probe; it must still answer within seconds to a minute
+const LANGUAGE_DETECTION_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DetectedLanguage {
+    pub language: String,
+    pub confidence: f64,
+}
+
+/// Detect the spoken language from the first `LANGUAGE_PROBE_SECS` of a file so
+/// the user can confirm it before a long run. WAV input is cut to that clip
+/// before Python sees it; other formats are trimmed by the probe itself.
+#[tauri::command]
+pub async fn detect_language(
+    app: tauri::AppHandle,
+    temp_files: State<'_, crate::secure_temp::SecureTempRegistry>,
+    audio_file_path: String
+) -> Result<DetectedLanguage, AppError> {
+    log::info!("Detecting language of: {}", audio_file_path);
+    
+    let source = std::path::PathBuf::from(&audio_file_path);
+    let format = crate::audio_processing::detect_audio_format(&source).map_err(AppError::Audio)?;
+    
+    let operation_id = format!("language-probe-{}", uuid::Uuid::new_v4());
+    let temp_guard = temp_files.track(&operation_id);
+    let probe_path = if format == "wav" {
+        let clip_path = std::env::temp_dir().join(format!("{}.wav", operation_id));
+        temp_guard.register(clip_path.clone());
+        let write_path = clip_path.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            let clip = crate::audio_processing::read_wav_head(&source, LANGUAGE_PROBE_SECS)?;
+            crate::audio_processing::write_wav(&write_path, clip.spec, &clip.samples)
+        })
+        .await
+        .map_err(|e| AppError::Internal(format!("Language probe clip task failed: {}", e)))?
+        .map_err(AppError::Audio)?;
+        clip_path.to_string_lossy().to_string()
+    } else {
+        audio_file_path
+    };
+    
+    let args = vec![
+        "--audio".to_string(),
+        probe_path,
+        "--sample_duration".to_string(),
+        LANGUAGE_PROBE_SECS.to_string(),
+    ];
+    let result = crate::python_integration::execute_python_script(
+        &app,
+        LANGUAGE_DETECTION_SCRIPT,
+        args,
+        Some(LANGUAGE_DETECTION_TIMEOUT),
+    )
+    .await?;
+    drop(temp_guard);
+    
+    if !result.success {
+        return Err(result.into());
+    }
+    serde_json::from_str(result.stdout.trim())
+        .map_err(|e| AppError::Python(format!("Failed to parse language detection output: {}", e)))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WhisperModel {
+    pub name: String,
+    pub approximate_size_mb: u64,
+    /// Already downloaded to the Hugging Face cache
+    pub downloaded: bool,
+}
+
+#[tauri::command]
+pub async fn list_whisper_models(app: tauri::AppHandle) -> Result<Vec<WhisperModel>, AppError> {
+    use tauri::Manager;
+
+    let cache_dir = crate::python_integration::huggingface_cache_dir(app.path().home_dir().ok().as_deref());
+    Ok(crate::python_integration::WHISPER_MODELS
+        .iter()
+        .map(|(name, size_mb)| WhisperModel {
+            name: name.to_string(),
+            approximate_size_mb: *size_mb,
+            downloaded: cache_dir
+                .as_deref()
+                .map(|dir| crate::python_integration::whisper_model_present(dir, name))
+                .unwrap_or(false),
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn get_transcription_progress(
+    state: State<'_, AppState>,
+    session_id: String
+) -> Result<TranscriptionProgress, AppError> {
     log::info!("Getting transcription progress for session: {}", session_id);
     
-    Ok(TranscriptionProgress {
-        session_id: session_id.clone(),
-        progress: 0.75, // Mock progress
-        current_stage: "Speaker diarization".to_string(),
-        estimated_remaining: Some(30),
+    state.transcriptions.progress(&session_id)
+        .ok_or_else(|| AppError::NotFound(format!("No transcription found for session: {}", session_id)))
+}
+
+#[tauri::command]
+pub async fn get_transcription_result(
+    state: State<'_, AppState>,
+    paths: State<'_, crate::paths::AppPaths>,
+    session_id: String
+) -> Result<Vec<SpeakerSegment>, AppError> {
+    log::info!("Loading transcription result for session: {}", session_id);
+    
+    // A running, failed or killed WhisperX may have left partial output behind
+    state.transcriptions.ensure_completed(&session_id)?;
+    
+    // WhisperX names its JSON after the audio file, so take whichever it wrote
+    let output_dir = paths.transcription_output(&session_id);
+    let mut entries = tokio::fs::read_dir(&output_dir)
+        .await
+        .map_err(|e| AppError::NotFound(format!("No transcription output for session {}: {}", session_id, e)))?;
+    let mut json_files = Vec::new();
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .map_err(|e| AppError::Io(format!("Failed to read transcription output: {}", e)))?
+    {
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) == Some("json") {
+            json_files.push(path);
+        }
+    }
+    json_files.sort();
+    let output_file = json_files
+        .first()
+        .ok_or_else(|| AppError::NotFound(format!("WhisperX wrote no JSON output to {}", output_dir.display())))?;
+    
+    let contents = tokio::fs::read_to_string(output_file)
+        .await
+        .map_err(|e| AppError::Io(format!("Failed to read {}: {}", output_file.display(), e)))?;
+    parse_whisperx_output(&contents).map_err(AppError::Python)
+}
+
+/// Move a running WhisperX run to the "cancelled" stage and signal its
+/// cancellation token, which kills the process; false when none is running
+fn cancel_run(
+    state: &AppState,
+    cancellations: &crate::cancellation::CancellationManager,
+    session_id: &str
+) -> bool {
+    if !state.transcriptions.cancel(session_id) {
+        return false;
+    }
+    cancellations.cancel(session_id);
+    true
+}
+
+/// Stop a running transcription. `session_id` is the run id `start_transcription`
+/// returned; it has no row in `conversation_sessions`, so only the run's
+/// progress changes, to the "cancelled" stage.
+#[tauri::command]
+pub async fn cancel_transcription(
+    state: State<'_, AppState>,
+    cancellations: State<'_, crate::cancellation::CancellationManager>,
+    session_id: String
+) -> Result<String, AppError> {
+    log::info!("Cancelling transcription for session: {}", session_id);
+    
+    if !cancel_run(&state, &cancellations, &session_id) {
+        return Err(AppError::NotFound(format!("No active transcription for session: {}", session_id)));
+    }
+    
+    Ok("Transcription cancelled".to_string())
+}
+
+/// Combine segments of one speaker into a single turn spanning all of them.
+/// Text is joined in time order; confidence is weighted by segment duration.
+pub fn merge_segment_group(segments: &[&SpeakerSegment]) -> Result<SpeakerSegment, String> {
+    let (first, rest) = segments
+        .split_first()
+        .ok_or_else(|| "No segments to merge".to_string())?;
+    if let Some(other) = rest.iter().find(|segment| segment.speaker_id != first.speaker_id) {
+        return Err(format!(
+            "Only segments of one speaker can be merged (found {} and {})",
+            first.speaker_id, other.speaker_id
+        ));
+    }
+
+    let mut ordered: Vec<&SpeakerSegment> = segments.to_vec();
+    ordered.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+
+    let text = ordered
+        .iter()
+        .map(|segment| segment.text.trim())
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let total_duration: f64 = ordered
+        .iter()
+        .map(|segment| (segment.end_time - segment.start_time).max(0.0))
+        .sum();
+    // Zero-length segments (e.g. imported without end times) count equally
+    let confidence = if total_duration > 0.0 {
+        ordered
+            .iter()
+            .map(|segment| segment.confidence * (segment.end_time - segment.start_time).max(0.0))
+            .sum::<f64>()
+            / total_duration
+    } else {
+        ordered.iter().map(|segment| segment.confidence).sum::<f64>() / ordered.len() as f64
+    };
+
+    Ok(SpeakerSegment {
+        id: None,
+        speaker_id: first.speaker_id.clone(),
+        speaker_label: ordered[0].speaker_label.clone(),
+        start_time: ordered.iter().map(|segment| segment.start_time).fold(f64::INFINITY, f64::min),
+        end_time: ordered.iter().map(|segment| segment.end_time).fold(f64::NEG_INFINITY, f64::max),
+        text,
+        confidence,
+        // Partial alignment would misplace words, so keep it only if every part has one
+        words: ordered
+            .iter()
+            .map(|segment| segment.words.clone())
+            .collect::<Option<Vec<_>>>()
+            .map(|parts| parts.concat()),
     })
 }
 
+/// Divide a segment at `split_time`, which must lie strictly inside it.
+/// Each word goes to the side containing its timing midpoint.
+pub fn split_segment_at(segment: &SpeakerSegment, split_time: f64) -> Result<(SpeakerSegment, SpeakerSegment), String> {
+    if !(segment.start_time < split_time && split_time < segment.end_time) {
+        return Err(format!(
+            "split_time must fall strictly inside the segment ({:.3}..{:.3})",
+            segment.start_time, segment.end_time
+        ));
+    }
+
+    let (words_before, words_after) = match &segment.words {
+        Some(words) => {
+            let (before, after): (Vec<WordTiming>, Vec<WordTiming>) = words
+                .iter()
+                .cloned()
+                .partition(|word| (word.start + word.end) / 2.0 < split_time);
+            (Some(before), Some(after))
+        }
+        None => (None, None),
+    };
+
+    let (before, after): (Vec<_>, Vec<_>) = word_spans(segment)
+        .into_iter()
+        .partition(|(_, start, end)| (start + end) / 2.0 < split_time);
+    let join = |words: Vec<(String, f64, f64)>| {
+        words.into_iter().map(|(word, _, _)| word).collect::<Vec<_>>().join(" ")
+    };
+
+    let part = |start_time: f64, end_time: f64, text: String, words: Option<Vec<WordTiming>>| SpeakerSegment {
+        id: None,
+        speaker_id: segment.speaker_id.clone(),
+        speaker_label: segment.speaker_label.clone(),
+        start_time,
+        end_time,
+        text,
+        confidence: segment.confidence,
+        words,
+    };
+    Ok((
+        part(segment.start_time, split_time, join(before), words_before),
+        part(split_time, segment.end_time, join(after), words_after),
+    ))
+}
+
+/// Swap the segments `removed_ids` for `added` in a session's stored transcript and
+/// re-record the fingerprint over `kept` plus `added`, all in one transaction.
+/// Returns `added` with their new storage ids.
+async fn replace_segments(
+    pool: &sqlx::SqlitePool,
+    session_id: &str,
+    removed_ids: &[&str],
+    mut added: Vec<SpeakerSegment>,
+    kept: Vec<SpeakerSegment>
+) -> Result<Vec<SpeakerSegment>, AppError> {
+    let mut tx = pool.begin().await.map_err(AppError::from)?;
+    for id in removed_ids {
+        sqlx::query("DELETE FROM transcript_segments WHERE session_id = ? AND id = ?")
+            .bind(session_id)
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to remove segment {}: {}", id, e)))?;
+    }
+    for segment in added.iter_mut() {
+        let id = crate::storage_commands::insert_segment(&mut tx, session_id, segment)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to store segment: {}", e)))?;
+        segment.id = Some(id);
+    }
+
+    let mut transcript = kept;
+    transcript.extend(added.iter().cloned());
+    crate::storage_commands::record_fingerprint(&mut tx, session_id, &transcript)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to update transcript fingerprint: {}", e)))?;
+    tx.commit().await.map_err(|e| AppError::Database(format!("Failed to save transcript edit: {}", e)))?;
+
+    Ok(added)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SpeakerLabelUpdate {
+    pub updated_segments: usize,
+    /// Speaker ids from the request that don't occur in the transcript
+    pub unmatched: Vec<String>,
+}
+
 #[tauri::command]
 pub async fn update_speaker_labels(
+    state: State<'_, AppState>,
     session_id: String,
     speaker_mappings: Vec<(String, String)> // (speaker_id, new_label)
-) -> Result<String, String> {
-    // TODO: Implement speaker label updates
+) -> Result<SpeakerLabelUpdate, AppError> {
     log::info!("Updating speaker labels for session: {}", session_id);
     
-    for (speaker_id, new_label) in speaker_mappings {
-        log::info!("Mapping speaker {} to label: {}", speaker_id, new_label);
+    let mut segments = crate::storage_commands::load_segments(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load transcript: {}", e)))?;
+    
+    let mut tx = state.db.begin().await.map_err(AppError::from)?;
+    let mut updated_segments = 0;
+    let mut unmatched = Vec::new();
+    for (speaker_id, new_label) in &speaker_mappings {
+        log::info!("Mapping speaker {} to a new label", speaker_id);
+        
+        let matching: Vec<&mut SpeakerSegment> = segments
+            .iter_mut()
+            .filter(|segment| segment.speaker_id == *speaker_id)
+            .collect();
+        if matching.is_empty() {
+            unmatched.push(speaker_id.clone());
+            continue;
+        }
+        updated_segments += matching.len();
+        for segment in matching {
+            segment.speaker_label = new_label.clone();
+        }
+        
+        sqlx::query("UPDATE transcript_segments SET speaker_label = ? WHERE session_id = ? AND speaker_id = ?")
+            .bind(new_label)
+            .bind(&session_id)
+            .bind(speaker_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to update speaker labels: {}", e)))?;
+    }
+    
+    // Labels are part of the fingerprint, so record the relabelled transcript
+    crate::storage_commands::record_fingerprint(&mut tx, &session_id, &segments)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to update transcript fingerprint: {}", e)))?;
+    tx.commit().await.map_err(|e| AppError::Database(format!("Failed to update speaker labels: {}", e)))?;
+    
+    Ok(SpeakerLabelUpdate { updated_segments, unmatched })
+}
+
+#[tauri::command]
+pub async fn merge_segments(
+    state: State<'_, AppState>,
+    session_id: String,
+    segment_ids: Vec<String>
+) -> Result<SpeakerSegment, AppError> {
+    log::info!("Merging {} segments in session: {}", segment_ids.len(), session_id);
+    
+    let unique: std::collections::BTreeSet<&str> = segment_ids.iter().map(String::as_str).collect();
+    if unique.len() < 2 {
+        return Err(AppError::Validation("Merging needs at least two distinct segments".to_string()));
+    }
+    
+    let segments = crate::storage_commands::load_segments(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load transcript: {}", e)))?;
+    let selected: Vec<&SpeakerSegment> = segments
+        .iter()
+        .filter(|segment| segment.id.as_deref().is_some_and(|id| unique.contains(id)))
+        .collect();
+    if selected.len() != unique.len() {
+        let missing: Vec<&str> = unique
+            .iter()
+            .filter(|id| !selected.iter().any(|segment| segment.id.as_deref() == Some(**id)))
+            .copied()
+            .collect();
+        return Err(AppError::NotFound(format!("Segments not found in session: {}", missing.join(", "))));
+    }
+    
+    let merged = merge_segment_group(&selected).map_err(AppError::Validation)?;
+    let removed: Vec<&str> = unique.into_iter().collect();
+    let kept: Vec<SpeakerSegment> = segments
+        .iter()
+        .filter(|segment| !segment.id.as_deref().is_some_and(|id| removed.contains(&id)))
+        .cloned()
+        .collect();
+    
+    replace_segments(&state.db, &session_id, &removed, vec![merged], kept)
+        .await?
+        .pop()
+        .ok_or_else(|| AppError::Internal("Merged segment was not stored".to_string()))
+}
+
+#[tauri::command]
+pub async fn split_segment(
+    state: State<'_, AppState>,
+    session_id: String,
+    segment_id: String,
+    split_time: f64
+) -> Result<(SpeakerSegment, SpeakerSegment), AppError> {
+    log::info!("Splitting segment {} of session {} at {}s", segment_id, session_id, split_time);
+    
+    let segments = crate::storage_commands::load_segments(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load transcript: {}", e)))?;
+    let (target, kept): (Vec<SpeakerSegment>, Vec<SpeakerSegment>) = segments
+        .into_iter()
+        .partition(|segment| segment.id.as_deref() == Some(segment_id.as_str()));
+    let target = target
+        .first()
+        .ok_or_else(|| AppError::NotFound(format!("Segment not found in session: {}", segment_id)))?;
+    
+    let (before, after) = split_segment_at(target, split_time).map_err(AppError::Validation)?;
+    let mut stored = replace_segments(&state.db, &session_id, &[segment_id.as_str()], vec![before, after], kept).await?;
+    
+    let after = stored.pop().ok_or_else(|| AppError::Internal("Split segment was not stored".to_string()))?;
+    let before = stored.pop().ok_or_else(|| AppError::Internal("Split segment was not stored".to_string()))?;
+    Ok((before, after))
+}
+
+#[tauri::command]
+pub async fn detect_hallucinations(
+    state: State<'_, AppState>,
+    session_id: String,
+    repeat_similarity: Option<f64>,
+    silence_overlap: Option<f64>
+) -> Result<Vec<SuspectSegment>, AppError> {
+    let repeat_threshold = repeat_similarity.unwrap_or(DEFAULT_REPEAT_SIMILARITY);
+    let silence_threshold = silence_overlap.unwrap_or(DEFAULT_SILENCE_OVERLAP);
+    log::info!("Detecting hallucinations for session: {}", session_id);
+    
+    for (name, value) in [("repeat_similarity", repeat_threshold), ("silence_overlap", silence_threshold)] {
+        if !(0.0..=1.0).contains(&value) || value == 0.0 {
+            return Err(AppError::Validation(format!("{} must be within (0.0, 1.0]", name)));
+        }
     }
     
-    Ok("Speaker labels updated successfully".to_string())
-}
\ No newline at end of file
+    let session = crate::storage_commands::fetch_session(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load session: {}", e)))?
+        .ok_or_else(|| AppError::NotFound(format!("Session not found: {}", session_id)))?;
+    let segments = crate::storage_commands::load_segments(&state.db, &session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load transcript: {}", e)))?;
+    
+    // Without readable audio only the repeat check can run
+    let silence = match session.file_path {
+        Some(path) => {
+            let regions = tauri::async_runtime::spawn_blocking(move || {
+                crate::audio_processing::read_wav(std::path::Path::new(&path)).map(|audio| {
+                    crate::audio_processing::silent_regions(&audio, crate::audio_processing::VAD_SILENCE_DBFS)
+                })
+            })
+            .await
+            .map_err(|e| AppError::Internal(format!("Silence detection task failed: {}", e)))?;
+            match regions {
+                Ok(regions) => Some(regions),
+                Err(e) => {
+                    log::warn!("Skipping silence check for session {}: {}", session_id, e);
+                    None
+                }
+            }
+        }
+        None => None,
+    };
+    
+    Ok(find_suspect_segments(&segments, silence.as_deref(), repeat_threshold, silence_threshold))
+}
+
+#[tauri::command]
+pub async fn normalize_speaker_ids(
+    state: State<'_, AppState>,
+    session_id: String
+) -> Result<Vec<SpeakerIdMapping>, AppError> {
+    log::info!("Normalizing speaker ids for session: {}", session_id);
+    
+    normalize_session_speakers(&state.db, &session_id).await
+}
+
+/// Renumber a stored transcript's speakers by first appearance, carrying marker
+/// speakers along, and record which diarization id each new id came from
+async fn normalize_session_speakers(
+    pool: &sqlx::SqlitePool,
+    session_id: &str
+) -> Result<Vec<SpeakerIdMapping>, AppError> {
+    let mut segments = crate::storage_commands::load_segments(pool, session_id)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to load transcript: {}", e)))?;
+    if segments.is_empty() {
+        return Err(AppError::Validation("Session has no transcript to normalize".to_string()));
+    }
+
+    // Earlier normalizations already know the diarization ids the current ones came from
+    let previous: std::collections::HashMap<String, String> = sqlx::query_as(
+        "SELECT speaker_id, original_speaker_id FROM speaker_id_mappings WHERE session_id = ?",
+    )
+    .bind(session_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to load speaker mappings: {}", e)))?
+    .into_iter()
+    .collect();
+
+    let renames = first_appearance_ids(&segments);
+    let mut tx = pool.begin().await.map_err(AppError::from)?;
+
+    for (current_id, new_id) in &renames {
+        let staged = format!("{}{}", SPEAKER_RENAME_PREFIX, new_id);
+        for statement in [
+            "UPDATE transcript_segments SET speaker_id = ? WHERE session_id = ? AND speaker_id = ?",
+            "UPDATE marker_events SET speaker = ? WHERE session_id = ? AND speaker = ?",
+        ] {
+            sqlx::query(statement)
+                .bind(&staged)
+                .bind(session_id)
+                .bind(current_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| AppError::Database(format!("Failed to rename speaker {}: {}", current_id, e)))?;
+        }
+    }
+
+    for statement in [
+        "UPDATE transcript_segments SET speaker_id = substr(speaker_id, ?) WHERE session_id = ? AND speaker_id LIKE ?",
+        "UPDATE marker_events SET speaker = substr(speaker, ?) WHERE session_id = ? AND speaker LIKE ?",
+    ] {
+        sqlx::query(statement)
+            .bind(SPEAKER_RENAME_PREFIX.len() as i64 + 1)
+            .bind(session_id)
+            .bind(format!("{}%", SPEAKER_RENAME_PREFIX))
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to finish speaker renaming: {}", e)))?;
+    }
+
+    // Labels that were just the raw id follow the rename
+    let rename_map: std::collections::HashMap<&str, &str> = renames
+        .iter()
+        .map(|(current, new)| (current.as_str(), new.as_str()))
+        .collect();
+    for (current_id, new_id) in &renames {
+        sqlx::query(
+            "UPDATE transcript_segments SET speaker_label = ? \
+             WHERE session_id = ? AND speaker_id = ? AND speaker_label = ?",
+        )
+        .bind(new_id)
+        .bind(session_id)
+        .bind(new_id)
+        .bind(current_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to update speaker labels: {}", e)))?;
+    }
+    for segment in &mut segments {
+        let new_id = rename_map[segment.speaker_id.as_str()].to_string();
+        if segment.speaker_label == segment.speaker_id {
+            segment.speaker_label = new_id.clone();
+        }
+        segment.speaker_id = new_id;
+    }
+
+    sqlx::query("DELETE FROM speaker_id_mappings WHERE session_id = ?")
+        .bind(session_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to replace speaker mappings: {}", e)))?;
+
+    let mut mappings = Vec::with_capacity(renames.len());
+    for (current_id, new_id) in renames {
+        let original_id = previous.get(&current_id).cloned().unwrap_or(current_id);
+        sqlx::query(
+            "INSERT INTO speaker_id_mappings (session_id, speaker_id, original_speaker_id) VALUES (?, ?, ?)",
+        )
+        .bind(session_id)
+        .bind(&new_id)
+        .bind(&original_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to store speaker mapping: {}", e)))?;
+
+        mappings.push(SpeakerIdMapping {
+            speaker_id: new_id,
+            original_speaker_id: original_id,
+        });
+    }
+
+    // Speaker ids are part of the fingerprint, so record the renamed transcript
+    crate::storage_commands::record_fingerprint(&mut tx, session_id, &segments)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to update transcript fingerprint: {}", e)))?;
+
+    tx.commit().await.map_err(|e| AppError::Database(format!("Failed to normalize speaker ids: {}", e)))?;
+
+    Ok(mappings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{insert, marker, memory_pool, segment, session};
+
+    fn hallucination_fixture() -> Vec<SpeakerSegment> {
+        vec![
+            segment("SPEAKER_00", 0.0, 3.0, "How have you been sleeping this week?"),
+            segment("SPEAKER_01", 3.0, 6.0, "Thank you for watching, please subscribe."),
+            segment("SPEAKER_01", 6.0, 9.0, "Thank you for watching. Please subscribe!"),
+            segment("SPEAKER_01", 9.0, 12.0, "I woke up twice most nights."),
+            segment("SPEAKER_00", 20.0, 22.0, "Okay."),
+        ]
+    }
+
+    #[test]
+    fn flags_repeated_neighbours_and_speech_in_silence() {
+        let silence = [(0.0, 0.5), (19.0, 23.0)];
+        let suspects = find_suspect_segments(
+            &hallucination_fixture(),
+            Some(&silence),
+            DEFAULT_REPEAT_SIMILARITY,
+            DEFAULT_SILENCE_OVERLAP,
+        );
+
+        let flagged: Vec<(usize, Vec<&str>)> = suspects
+            .iter()
+            .map(|suspect| (suspect.segment_index, suspect.reasons.iter().map(String::as_str).collect()))
+            .collect();
+        assert_eq!(flagged, [(1, vec!["repeat"]), (2, vec!["repeat"]), (4, vec!["silence"])]);
+        assert_eq!(suspects[0].repeat_similarity, 1.0);
+        assert_eq!(suspects[2].silence_overlap, Some(1.0));
+        assert_eq!((suspects[2].start_time, suspects[2].end_time), (20.0, 22.0));
+    }
+
+    #[test]
+    fn silence_check_is_skipped_without_audio() {
+        let suspects = find_suspect_segments(
+            &hallucination_fixture(),
+            None,
+            DEFAULT_REPEAT_SIMILARITY,
+            DEFAULT_SILENCE_OVERLAP,
+        );
+
+        assert_eq!(suspects.iter().map(|suspect| suspect.segment_index).collect::<Vec<_>>(), [1, 2]);
+        assert!(suspects.iter().all(|suspect| suspect.silence_overlap.is_none()));
+    }
+
+    #[test]
+    fn partial_repeats_and_short_silences_stay_below_the_thresholds() {
+        let segments = vec![
+            segment("SPEAKER_00", 0.0, 4.0, "We talked about your sister and the move last time"),
+            segment("SPEAKER_01", 4.0, 8.0, "We talked about my sister a lot, yes"),
+        ];
+        // Half of the second segment is silent
+        let suspects = find_suspect_segments(&segments, Some(&[(6.0, 8.0)]), 0.8, 0.8);
+        assert!(suspects.is_empty());
+
+        let suspects = find_suspect_segments(&segments, Some(&[(6.0, 8.0)]), 0.8, 0.5);
+        assert_eq!(suspects.len(), 1);
+        assert_eq!(suspects[0].reasons, ["silence"]);
+    }
+
+    #[test]
+    fn speakers_are_numbered_by_first_appearance() {
+        let segments = vec![
+            segment("spk_7", 5.0, 8.0, "Go on."),
+            segment("spk_2", 0.0, 4.0, "Hello."),
+            segment("spk_9", 5.0, 6.0, "Mm."),
+            segment("spk_2", 8.0, 9.0, "So."),
+        ];
+
+        let renames = first_appearance_ids(&segments);
+
+        // spk_7 and spk_9 first speak at the same time, so their ids break the tie
+        assert_eq!(
+            renames,
+            [
+                ("spk_2".to_string(), "SPEAKER_00".to_string()),
+                ("spk_7".to_string(), "SPEAKER_01".to_string()),
+                ("spk_9".to_string(), "SPEAKER_02".to_string()),
+            ]
+        );
+        let mut reversed = segments.clone();
+        reversed.reverse();
+        assert_eq!(first_appearance_ids(&reversed), renames);
+    }
+
+    async fn stored_speakers(pool: &sqlx::SqlitePool) -> Vec<(String, String)> {
+        crate::storage_commands::load_segments(pool, "s1")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|segment| (segment.speaker_id, segment.speaker_label))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn normalizing_renames_segments_and_markers_and_is_repeatable() {
+        let pool = memory_pool().await;
+        insert(&pool, &session("s1", "Intake")).await;
+        let mut therapist = segment("SPEAKER_01", 0.0, 4.0, "Hello.");
+        therapist.speaker_label = "Therapist".to_string();
+        let segments = vec![therapist, segment("SPEAKER_00", 5.0, 8.0, "Hi."), segment("SPEAKER_01", 9.0, 10.0, "So.")];
+        crate::storage_commands::store_segments(&pool, "s1", &segments).await.unwrap();
+        let client_marker = marker("m1", crate::analysis_commands::MarkerType::Ato, 5.0, 6.0, 0.8, Some("SPEAKER_00"));
+        let mut conn = pool.acquire().await.unwrap();
+        crate::analysis_commands::insert_markers(&mut conn, "s1", &[&client_marker]).await.unwrap();
+        drop(conn);
+
+        let mappings = normalize_session_speakers(&pool, "s1").await.unwrap();
+
+        let pairs: Vec<(&str, &str)> = mappings
+            .iter()
+            .map(|mapping| (mapping.speaker_id.as_str(), mapping.original_speaker_id.as_str()))
+            .collect();
+        assert_eq!(pairs, [("SPEAKER_00", "SPEAKER_01"), ("SPEAKER_01", "SPEAKER_00")]);
+        // Custom labels stay, labels that were the raw id follow it
+        let speakers = stored_speakers(&pool).await;
+        assert_eq!(
+            speakers,
+            [
+                ("SPEAKER_00".to_string(), "Therapist".to_string()),
+                ("SPEAKER_01".to_string(), "SPEAKER_01".to_string()),
+                ("SPEAKER_00".to_string(), "SPEAKER_00".to_string()),
+            ]
+        );
+        let markers = crate::analysis_commands::load_markers(&pool, "s1").await.unwrap();
+        assert_eq!(markers[0].speaker.as_deref(), Some("SPEAKER_01"));
+
+        // Already normalized: nothing moves and the original ids are remembered
+        let again = normalize_session_speakers(&pool, "s1").await.unwrap();
+        let pairs_again: Vec<(&str, &str)> = again
+            .iter()
+            .map(|mapping| (mapping.speaker_id.as_str(), mapping.original_speaker_id.as_str()))
+            .collect();
+        assert_eq!(pairs_again, pairs);
+        assert_eq!(stored_speakers(&pool).await, speakers);
+    }
+
+    #[tokio::test]
+    async fn normalizing_an_empty_transcript_is_rejected() {
+        let pool = memory_pool().await;
+        insert(&pool, &session("s1", "Intake")).await;
+        assert!(matches!(
+            normalize_session_speakers(&pool, "s1").await,
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn pasted_transcript_timestamps_speakers_and_continuations() {
+        let pasted = "\
+[00:05] Therapist: How was your week?
+(00:00:12) Client: Busy, honestly.
+   It was a lot.
+
+00:20 - Therapist: Tell me more.
+[00:31.5] I mean the work part.";
+
+        let segments = parse_plaintext_transcript(pasted);
+
+        let parsed: Vec<(&str, &str, f64, &str)> = segments
+            .iter()
+            .map(|s| (s.speaker_id.as_str(), s.speaker_label.as_str(), s.start_time, s.text.as_str()))
+            .collect();
+        assert_eq!(
+            parsed,
+            [
+                ("SPEAKER_00", "Therapist", 5.0, "How was your week?"),
+                ("SPEAKER_01", "Client", 12.0, "Busy, honestly. It was a lot."),
+                ("SPEAKER_00", "Therapist", 20.0, "Tell me more."),
+                ("SPEAKER_00", "Therapist", 31.5, "I mean the work part."),
+            ]
+        );
+        // Each segment runs to the next; the last is estimated from its word count
+        let ends: Vec<f64> = segments.iter().map(|s| s.end_time).collect();
+        assert_eq!(ends[..3], [12.0, 20.0, 31.5]);
+        assert!((ends[3] - (31.5 + 6.0 / PLAINTEXT_WORDS_PER_SEC)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn pasted_transcript_rejects_implausible_speaker_names() {
+        let segments = parse_plaintext_transcript(
+            "Session notes\n[01:00] Well, the meeting ran until: late\n[01:00] Dr. Who: hi\n[1:2:3:4] not a time",
+        );
+
+        assert_eq!(segments[0].speaker_id, "SPEAKER_UNKNOWN");
+        assert_eq!((segments[0].start_time, segments[0].text.as_str()), (0.0, "Session notes"));
+        assert_eq!(segments[1].speaker_label, "Unknown");
+        assert_eq!(segments[1].text, "Well, the meeting ran until: late");
+        assert_eq!(segments[2].text, "Dr. Who: hi [1:2:3:4] not a time");
+        // Same start as the next segment, so its end comes from the word count
+        assert_eq!(segments[1].end_time, 60.0 + 6.0 / PLAINTEXT_WORDS_PER_SEC);
+        assert_eq!(segments.len(), 3);
+    }
+
+    #[test]
+    fn results_are_only_available_after_a_completed_run() {
+        let jobs = TranscriptionJobs::default();
+        assert!(matches!(jobs.ensure_completed("run-1"), Err(AppError::NotFound(_))));
+
+        jobs.start("run-1", Some(60.0));
+        jobs.set_stage("run-1", "align");
+        assert!(matches!(jobs.ensure_completed("run-1"), Err(AppError::NotFound(_))));
+        jobs.finish("run-1", "completed");
+        assert!(jobs.ensure_completed("run-1").is_ok());
+
+        jobs.start("run-2", None);
+        jobs.finish("run-2", "failed");
+        assert!(matches!(jobs.ensure_completed("run-2"), Err(AppError::NotFound(_))));
+
+        jobs.start("run-3", None);
+        assert!(jobs.cancel("run-3"));
+        assert!(!jobs.cancel("run-3"), "a cancelled run is no longer running");
+        // Finishing after the kill does not override the cancellation
+        jobs.finish("run-3", "completed");
+        assert!(matches!(jobs.ensure_completed("run-3"), Err(AppError::Cancelled(_))));
+
+        jobs.start("run-4", None);
+        jobs.finish("run-4", TIMEOUT_STAGE);
+        assert_eq!(jobs.progress("run-4").unwrap().current_stage, "timeout");
+        assert!(matches!(jobs.ensure_completed("run-4"), Err(AppError::Timeout(_))));
+    }
+}
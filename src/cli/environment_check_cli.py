@@ -0,0 +1,49 @@
+#!/usr/bin/env python3
+"""
+TransRapport Python Environment Check
+
+Reports whether the transcription and LD-3.4 analysis backend can run,
+as a single JSON object on stdout.
+"""
+
+import importlib
+import importlib.util
+import json
+import platform
+import sys
+from pathlib import Path
+
+# Run from anywhere: make the repository root importable for src.lib.*
+sys.path.insert(0, str(Path(__file__).resolve().parents[2]))
+
+REQUIRED_PACKAGES = ["whisperx", "torch"]
+ANALYSIS_MODULES = ["src.lib.analysis"]
+
+
+def main():
+    missing = [name for name in REQUIRED_PACKAGES if importlib.util.find_spec(name) is None]
+
+    for module in ANALYSIS_MODULES:
+        try:
+            importlib.import_module(module)
+        except Exception:
+            missing.append(module)
+
+    torch_cuda = False
+    if "torch" not in missing:
+        try:
+            import torch
+            torch_cuda = bool(torch.cuda.is_available())
+        except Exception:
+            missing.append("torch")
+
+    print(json.dumps({
+        "python_version": platform.python_version(),
+        "whisperx_available": "whisperx" not in missing,
+        "torch_cuda": torch_cuda,
+        "missing_packages": missing,
+    }))
+
+
+if __name__ == "__main__":
+    main()
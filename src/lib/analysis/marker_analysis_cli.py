@@ -0,0 +1,83 @@
+#!/usr/bin/env python3
+"""
+TransRapport Marker Analysis
+
+Runs the LD-3.4 levels (ATO, SEM, CLU, MEMA) over a transcript one at a time,
+printing {"stage": <level>, "markers": <count>} on stdout as each finishes.
+The last stdout line is the detected markers as a single JSON array, each
+{"id", "marker_type", "start_time", "end_time", "confidence", "evidence",
+"explanation", "speaker"}. The transcript is a JSON array of segments as the
+desktop app stores them, with speaker_id, start_time, end_time and text.
+"""
+
+import argparse
+import json
+import sys
+from pathlib import Path
+
+# Run from anywhere: make the repository root importable for src.*
+sys.path.insert(0, str(Path(__file__).resolve().parents[3]))
+
+from src.lib.analysis.pipeline import LD34AnalysisPipeline
+
+LEVELS = ["ATO", "SEM", "CLU", "MEMA"]
+
+
+def load_segments(path):
+    with open(path, encoding="utf-8") as f:
+        segments = json.load(f)
+    # The engines read the speaker from "speaker"
+    return [
+        {
+            "speaker": segment.get("speaker_id") or segment.get("speaker") or "unknown",
+            "start_time": float(segment.get("start_time", 0.0)),
+            "end_time": float(segment.get("end_time", 0.0)),
+            "text": segment.get("text", ""),
+        }
+        for segment in segments
+    ]
+
+
+def main():
+    parser = argparse.ArgumentParser(description="Detect LD-3.4 markers in a transcript")
+    parser.add_argument("--transcript", required=True, help="Transcript JSON file")
+    parser.add_argument("--session_id", required=True, help="Session the markers belong to")
+    parser.add_argument("--output_format", choices=["json"], default="json", help="Output format")
+    args = parser.parse_args()
+
+    # Stage lines and the marker array are the only things on stdout; engine output goes to stderr
+    output = sys.stdout
+    sys.stdout = sys.stderr
+
+    def emit(line):
+        output.write(line + "\n")
+        output.flush()
+
+    pipeline = LD34AnalysisPipeline()
+    segments = load_segments(args.transcript)
+    detected = []
+    for level in LEVELS:
+        level_markers = pipeline.analyze_segments_by_engine(segments, [level])[level]
+        detected.extend(level_markers)
+        emit(json.dumps({"stage": level, "markers": len(level_markers)}))
+    detected.sort(key=lambda marker: marker.start_time)
+
+    markers = [
+        {
+            "id": marker.id,
+            "marker_type": marker.marker_type.value,
+            "start_time": float(marker.start_time),
+            "end_time": float(marker.end_time),
+            "confidence": min(max(float(marker.confidence), 0.0), 1.0),
+            "evidence": marker.evidence,
+            "explanation": marker.explanation,
+            "speaker": marker.speaker,
+        }
+        for marker in detected
+    ]
+
+    emit(json.dumps(markers, ensure_ascii=False))
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,41 @@
+#!/usr/bin/env python3
+"""
+TransRapport Language Detection Probe
+
+Detects the spoken language from the start of an audio file and prints
+{"language": ..., "confidence": ...} as a single JSON object on stdout.
+"""
+
+import argparse
+import json
+import sys
+from pathlib import Path
+
+# Run from anywhere: make the repository root importable for src.lib.*
+sys.path.insert(0, str(Path(__file__).resolve().parents[3]))
+
+from src.lib.transcription.language_detection import LanguageDetection
+
+
+def main():
+    parser = argparse.ArgumentParser(description="Detect the language of an audio file")
+    parser.add_argument("--audio", required=True, help="Audio file to probe")
+    parser.add_argument("--sample_duration", type=float, default=30.0,
+                        help="Seconds from the start of the file to analyze")
+    parser.add_argument("--model", default="base", help="Whisper model size")
+    args = parser.parse_args()
+
+    detector = LanguageDetection(model_size=args.model)
+    try:
+        result = detector.detect_language(args.audio, sample_duration=args.sample_duration)
+    finally:
+        detector.cleanup()
+
+    print(json.dumps({
+        "language": result["language"],
+        "confidence": float(result["confidence"]),
+    }))
+
+
+if __name__ == "__main__":
+    main()
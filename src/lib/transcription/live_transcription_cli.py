@@ -0,0 +1,60 @@
+#!/usr/bin/env python3
+"""
+TransRapport Live Transcription
+
+Keeps a Whisper model loaded and transcribes short chunks of a running
+recording as they arrive. Reads one audio file path per line on stdin and
+answers each with one JSON line on stdout: {"segments": [...]} with times
+relative to the chunk, or {"error": ...} when that chunk failed. Exits when
+stdin is closed.
+
+The captions are a preview: chunks are transcribed independently by a small
+model, without alignment or diarization. The transcript made from the finished
+recording stays authoritative.
+"""
+
+import argparse
+import json
+import sys
+from pathlib import Path
+
+# Run from anywhere: make the repository root importable for src.lib.*
+sys.path.insert(0, str(Path(__file__).resolve().parents[3]))
+
+from src.lib.transcription.whisper_service import WhisperService
+
+
+def main():
+    parser = argparse.ArgumentParser(description="Transcribe recording chunks as they arrive")
+    parser.add_argument("--model", default="base", help="Whisper model size")
+    parser.add_argument("--language", default=None, help="Language code; detected per chunk when unset")
+    args = parser.parse_args()
+
+    # Replies are the only thing on stdout; anything the model prints goes to stderr
+    replies = sys.stdout
+    sys.stdout = sys.stderr
+
+    service = WhisperService(model_size=args.model)
+    try:
+        for line in sys.stdin:
+            chunk_path = line.strip()
+            if not chunk_path:
+                continue
+            try:
+                result = service.transcribe(chunk_path, language=args.language)
+                reply = {
+                    "segments": [
+                        {"start": segment.start, "end": segment.end, "text": segment.text}
+                        for segment in result.segments
+                    ]
+                }
+            except Exception as e:
+                reply = {"error": str(e)}
+            replies.write(json.dumps(reply) + "\n")
+            replies.flush()
+    finally:
+        service.cleanup()
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,108 @@
+#!/usr/bin/env python3
+"""
+TransRapport WhisperX Transcription
+
+Transcribes an audio file with WhisperX, aligns word timings and, when a
+diarization model is available, attributes segments to speakers. The result is
+written to <output_dir>/<audio stem>.json as {"segments": [...]} in WhisperX's
+own segment format.
+
+Progress goes to stdout as one JSON object per line,
+{"stage": ..., "progress": ...}, with stages load_model, transcribe, align and
+diarize and progress the completed fraction of the stage. Everything else the
+libraries print goes to stderr.
+"""
+
+import argparse
+import json
+import os
+import sys
+from pathlib import Path
+
+# WhisperX samples audio at 16 kHz
+SAMPLE_RATE = 16000
+
+
+def str_to_bool(value):
+    return str(value).strip().lower() in ("1", "true", "yes")
+
+
+def main():
+    parser = argparse.ArgumentParser(description="Transcribe an audio file with WhisperX")
+    parser.add_argument("--audio", required=True, help="Audio file to transcribe")
+    parser.add_argument("--output_dir", required=True, help="Directory the JSON result is written to")
+    parser.add_argument("--chunk_size", type=int, default=30, help="Seconds of audio per chunk")
+    parser.add_argument("--print_progress", type=str_to_bool, default=False,
+                        help="Emit JSON progress lines on stdout")
+    parser.add_argument("--language", default=None, help="Language code; detected when unset")
+    parser.add_argument("--model", default="base", help="Whisper model size")
+    args = parser.parse_args()
+
+    # Progress lines are the only thing on stdout
+    progress_out = sys.stdout
+    sys.stdout = sys.stderr
+
+    def report(stage, progress):
+        if args.print_progress:
+            progress_out.write(json.dumps({"stage": stage, "progress": progress}) + "\n")
+            progress_out.flush()
+
+    import torch
+    import whisperx
+
+    device = "cuda" if torch.cuda.is_available() else "cpu"
+    compute_type = "float16" if device == "cuda" else "int8"
+
+    report("load_model", 0.0)
+    model = whisperx.load_model(args.model, device, compute_type=compute_type, language=args.language)
+    audio = whisperx.load_audio(args.audio)
+    report("load_model", 1.0)
+
+    report("transcribe", 0.0)
+    result = model.transcribe(audio, chunk_size=args.chunk_size, language=args.language)
+    language = result.get("language") or args.language
+    del model
+    report("transcribe", 1.0)
+
+    report("align", 0.0)
+    try:
+        align_model, metadata = whisperx.load_align_model(language_code=language, device=device)
+        result = whisperx.align(result["segments"], align_model, metadata, audio, device,
+                                return_char_alignments=False)
+        del align_model
+    except ValueError as e:
+        # No alignment model for the language; segments keep their coarse timings
+        print(f"Skipping alignment: {e}", file=sys.stderr)
+    report("align", 1.0)
+
+    token = os.environ.get("HF_TOKEN") or os.environ.get("HUGGING_FACE_HUB_TOKEN")
+    pipeline_class = getattr(whisperx, "DiarizationPipeline", None)
+    if pipeline_class is None:
+        try:
+            from whisperx.diarize import DiarizationPipeline as pipeline_class
+        except ImportError:
+            pipeline_class = None
+    if pipeline_class is not None:
+        report("diarize", 0.0)
+        try:
+            diarize_model = pipeline_class(use_auth_token=token, device=device)
+            diarize_segments = diarize_model(audio)
+            result = whisperx.assign_word_speakers(diarize_segments, result)
+        except Exception as e:
+            # Without a diarization model everything stays with one speaker
+            print(f"Skipping diarization: {e}", file=sys.stderr)
+        report("diarize", 1.0)
+
+    output_dir = Path(args.output_dir)
+    output_dir.mkdir(parents=True, exist_ok=True)
+    output_file = output_dir / f"{Path(args.audio).stem}.json"
+    with open(output_file, "w", encoding="utf-8") as f:
+        json.dump({
+            "segments": result["segments"],
+            "language": language,
+            "duration": len(audio) / SAMPLE_RATE,
+        }, f, ensure_ascii=False, default=float)
+
+
+if __name__ == "__main__":
+    main()